@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+/// Parsed command line arguments of a subcommand.
+#[derive(Debug, Default)]
+pub struct Args {
+    flags: Vec<String>,
+    options: HashMap<String, String>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    /// Parse arguments, `flags` are known switches, `options` are known switches followed by a value, other
+    /// arguments not starts with `-` are positional.
+    pub fn parse(args: &[String], flags: &[&str], options: &[&str]) -> Result<Self, String> {
+        let mut result = Self::default();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            if flags.contains(&arg.as_str()) {
+                result.flags.push(arg.clone());
+            } else if options.contains(&arg.as_str()) {
+                let value = iter.next().ok_or_else(|| format!("missing value of {arg}"))?;
+                result.options.insert(arg.clone(), value.clone());
+            } else if arg.starts_with('-') {
+                return Err(format!("unknown option: {arg}"));
+            } else {
+                result.positional.push(arg.clone());
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|f| f == name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    /// Get value of option and parse it.
+    pub fn parsed_value<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.value(name)
+            .map(|v| v.parse().map_err(|_| format!("invalid value of {name}: {v}")))
+            .transpose()
+    }
+
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+}
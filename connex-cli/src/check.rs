@@ -0,0 +1,101 @@
+use std::{fs, process::ExitCode};
+
+use connex::World;
+
+use crate::args::Args;
+
+pub const USAGE: &str = "\
+Usage: connex-cli check [--unique] [--json] <files...>
+
+Check level files can be parsed and are stored in solved state.
+
+Options:
+    --unique    Also check the level has only one solution
+    --json      Print result in JSON format
+";
+
+fn check_file(path: &str, unique: bool) -> Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let world = World::parse_all_errors(&content)
+        .map_err(|errors| errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))?;
+
+    if !world.solved() {
+        return Err("not in solved state".into());
+    }
+
+    if unique && world.count_solutions(2) > 1 {
+        return Err("has multiple solutions".into());
+    }
+
+    Ok(())
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let args = match Args::parse(args, &["--unique", "--json"], &[]) {
+        Ok(args) if !args.positional().is_empty() => args,
+        Ok(_) => {
+            eprint!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let results: Vec<_> = args
+        .positional()
+        .iter()
+        .map(|path| (path, check_file(path, args.flag("--unique"))))
+        .collect();
+    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+
+    if args.flag("--json") {
+        let files: Vec<_> = results
+            .iter()
+            .map(|(path, result)| match result {
+                Ok(()) => format!("{{\"path\":{},\"ok\":true}}", json_string(path)),
+                Err(e) => format!(
+                    "{{\"path\":{},\"ok\":false,\"error\":{}}}",
+                    json_string(path),
+                    json_string(e)
+                ),
+            })
+            .collect();
+        println!(
+            "{{\"files\":[{}],\"checked\":{},\"failed\":{failed}}}",
+            files.join(","),
+            results.len()
+        );
+    } else {
+        for (path, result) in &results {
+            match result {
+                Ok(()) => println!("{path}: ok"),
+                Err(e) => println!("{path}: {e}"),
+            }
+        }
+        println!("{} files checked, {failed} failed", results.len());
+    }
+
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use connex::{Block, World};
+use serde_json::{json, Value};
+
+use crate::args::Args;
+
+pub const USAGE: &str = "\
+Usage: connex-cli convert --to <FORMAT> [options] <file> -o <output>
+       connex-cli convert --to <FORMAT> [options] <files...> --out-dir <DIR>
+
+Convert level files between formats, input format is detected from content.
+
+Formats:
+    text        Standard level text format
+    json        {\"height\": H, \"width\": W, \"rows\": [\"...\", ...]}, rows use text format characters
+
+Options:
+    --to <FORMAT>       Output format
+    --canonicalize      Accept block aliases like `|`, `L` and `+` in input, and normalize equivalent block
+                        directions
+    --trim              Remove empty rows and columns around the level
+    -o <output>         Output file path, for single input
+    --out-dir <DIR>     Output directory, for many inputs, file stems are kept
+";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format: {s}")),
+        }
+    }
+
+    fn sniff(content: &str) -> Self {
+        if content.trim_start().starts_with('{') {
+            Self::Json
+        } else {
+            Self::Text
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Json => "json",
+        }
+    }
+
+    fn decode(&self, content: &str, lenient: bool) -> Result<World, String> {
+        let parse = |text: &str| {
+            if lenient {
+                World::parse_lenient(text).map_err(String::from)
+            } else {
+                text.parse().map_err(String::from)
+            }
+        };
+
+        match self {
+            Self::Text => parse(content),
+            Self::Json => {
+                let value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+                let size = |key| value[key].as_u64().ok_or_else(|| format!("missing {key}"));
+                let (height, width) = (size("height")?, size("width")?);
+                let rows = value["rows"].as_array().ok_or("missing rows")?;
+
+                let mut text = format!("{height},{width}\n");
+                for row in rows {
+                    text.push_str(row.as_str().ok_or("row is not a string")?);
+                    text.push('\n');
+                }
+                parse(&text)
+            }
+        }
+    }
+
+    fn encode(&self, world: &World) -> String {
+        match self {
+            Self::Text => world.to_string(),
+            Self::Json => {
+                let rows: Vec<_> = world
+                    .rows()
+                    .map(|row| row.iter().map(ToString::to_string).collect::<String>())
+                    .collect();
+                let value = json!({
+                    "height": world.height().get(),
+                    "width": world.width().get(),
+                    "rows": rows,
+                });
+                format!("{value}\n")
+            }
+        }
+    }
+}
+
+/// Remove empty rows and columns around world, return None if world is all empty.
+fn trim(world: &World) -> Option<World> {
+    let non_empty: Vec<_> = world
+        .rows()
+        .enumerate()
+        .flat_map(|(row, blocks)| blocks.iter().enumerate().map(move |(col, block)| (row, col, block)))
+        .filter(|(_, _, block)| **block != Block::Empty)
+        .map(|(row, col, _)| (row, col))
+        .collect();
+
+    let top = non_empty.iter().map(|(row, _)| *row).min()?;
+    let bottom = non_empty.iter().map(|(row, _)| *row).max()?;
+    let left = non_empty.iter().map(|(_, col)| *col).min()?;
+    let right = non_empty.iter().map(|(_, col)| *col).max()?;
+
+    Some(World::new_with(
+        NonZeroUsize::new(bottom - top + 1).unwrap(),
+        NonZeroUsize::new(right - left + 1).unwrap(),
+        |row, col| world[(top + row, left + col)],
+    ))
+}
+
+fn convert_file(input: &Path, output: &Path, to: Format, args: &Args) -> Result<(), String> {
+    let content = fs::read_to_string(input).map_err(|e| format!("{}: {e}", input.display()))?;
+    let mut world = Format::sniff(&content)
+        .decode(&content, args.flag("--canonicalize"))
+        .map_err(|e| format!("{}: {e}", input.display()))?;
+
+    if args.flag("--trim") {
+        world = trim(&world).ok_or_else(|| format!("{}: can't trim an empty level", input.display()))?;
+    }
+
+    if args.flag("--canonicalize") {
+        world.normalize();
+    }
+
+    fs::write(output, to.encode(&world)).map_err(|e| format!("{}: {e}", output.display()))
+}
+
+fn convert(args: &Args) -> Result<(), String> {
+    let to = Format::parse(args.value("--to").ok_or(USAGE)?)?;
+
+    match (args.positional(), args.value("-o"), args.value("--out-dir")) {
+        ([input], Some(output), None) => convert_file(Path::new(input), Path::new(output), to, args),
+        (inputs, None, Some(dir)) if !inputs.is_empty() => {
+            fs::create_dir_all(dir).map_err(|e| format!("{dir}: {e}"))?;
+            for input in inputs {
+                let input = Path::new(input);
+                let stem = input
+                    .file_stem()
+                    .ok_or_else(|| format!("{}: not a file", input.display()))?;
+                // append extension instead of replacing, so `a.v2.txt` and `a.v3.txt` don't both become `a.json`
+                let mut name = stem.to_os_string();
+                name.push(".");
+                name.push(to.extension());
+                let output = PathBuf::from(dir).join(name);
+                convert_file(input, &output, to, args)?;
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let result =
+        Args::parse(args, &["--canonicalize", "--trim"], &["--to", "-o", "--out-dir"]).and_then(|args| convert(&args));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
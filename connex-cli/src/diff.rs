@@ -0,0 +1,97 @@
+use std::{collections::HashSet, fmt::Write, fs, process::ExitCode};
+
+use connex::{Pos, World};
+
+use crate::args::Args;
+
+pub const USAGE: &str = "\
+Usage: connex-cli diff [--art] <a> <b>
+
+Compare two level files, blocks connect the same sides are treated as equal.
+
+Exit code is 0 if they are same, 1 if differ, 2 on error.
+
+Options:
+    --art       Draw both worlds side by side, differing blocks are highlighted
+";
+
+const HIGHLIGHT: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+fn load(path: &str) -> Result<World, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    content.parse().map_err(|e| format!("{path}: {e}"))
+}
+
+fn art_line(world: &World, other: &World, row: usize, changes: &HashSet<Pos>) -> String {
+    let mut line = String::new();
+
+    for col in 0..world.width().get() {
+        // blocks only one world has are also differences
+        let differ = changes.contains(&Pos::new(row, col)) || other.get(row, col).is_none();
+        match world.get(row, col) {
+            Some(block) if differ => write!(line, "{HIGHLIGHT}{}{RESET}", block.box_char()).unwrap(),
+            Some(block) => line.push(block.box_char()),
+            None => line.push(' '),
+        }
+    }
+
+    line
+}
+
+fn art(a: &World, b: &World, changes: &[Pos]) -> String {
+    let changes: HashSet<_> = changes.iter().copied().collect();
+
+    let height = a.height().max(b.height()).get();
+    let mut result = String::new();
+    for row in 0..height {
+        let left = art_line(a, b, row, &changes);
+        let right = art_line(b, a, row, &changes);
+        writeln!(result, "{left} | {right}").unwrap();
+    }
+
+    result
+}
+
+fn diff(args: &Args) -> Result<bool, String> {
+    let (a, b) = match args.positional() {
+        [a, b] => (load(a)?, load(b)?),
+        _ => return Err(USAGE.into()),
+    };
+
+    let changes = a.diff(&b);
+    let same_size = a.size() == b.size();
+
+    if !same_size {
+        println!("size: {},{} -> {},{}", a.height(), a.width(), b.height(), b.width());
+    }
+
+    for pos in &changes {
+        println!(
+            "row {}, column {}: '{}' -> '{}'",
+            pos.row + 1,
+            pos.col + 1,
+            a[*pos],
+            b[*pos]
+        );
+    }
+
+    if args.flag("--art") {
+        print!("{}", art(&a, &b, &changes));
+    }
+
+    Ok(!same_size || !changes.is_empty())
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let result = Args::parse(args, &["--art"], &[]).and_then(|args| diff(&args));
+
+    match result {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            ExitCode::from(2)
+        }
+    }
+}
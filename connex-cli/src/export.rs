@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use connex::{PngOptions, SvgOptions, World};
+
+use crate::args::Args;
+
+pub const USAGE: &str = "\
+Usage: connex-cli export [--png <output>] [--svg <output>] [OPTIONS] <path>
+
+Render a level file to images, at least one of --png and --svg is required.
+
+If path is a directory, every level file in it is rendered, outputs are directories then,
+images are named after level files, e.g. 001.txt to 001.png.
+
+Options:
+    --png <output>          Write PNG image to output path
+    --svg <output>          Write SVG image to output path
+    --cell-px <N>           Pixels of each block in image, default 64
+    --grid                  Draw block boundaries in SVG image
+    --background <RRGGBB>   Background color, default FFFFFF
+    --line-color <RRGGBB>   Line color of unsolved world, default 202020
+";
+
+const DEFAULT_CELL_PX: u32 = 64;
+
+fn parse_color(args: &Args, name: &str) -> Result<Option<[u8; 3]>, String> {
+    args.value(name)
+        .map(|v| {
+            let v = v.strip_prefix('#').unwrap_or(v);
+            match u32::from_str_radix(v, 16) {
+                Ok(rgb) if v.len() == 6 => {
+                    let [_, r, g, b] = rgb.to_be_bytes();
+                    Ok([r, g, b])
+                }
+                _ => Err(format!("invalid value of {name}: {v}")),
+            }
+        })
+        .transpose()
+}
+
+struct Outputs<'a> {
+    png: Option<(&'a Path, PngOptions)>,
+    svg: Option<(&'a Path, SvgOptions)>,
+}
+
+impl Outputs<'_> {
+    fn render(&self, path: &Path, png: Option<PathBuf>, svg: Option<PathBuf>) -> Result<(), String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let world: World = content.parse().map_err(|e| format!("{}: {e}", path.display()))?;
+
+        if let (Some(output), Some((_, options))) = (png, &self.png) {
+            let png = world
+                .try_to_png_with(options)
+                .map_err(|e| format!("{}: {e}", path.display()))?;
+            fs::write(&output, png).map_err(|e| format!("{}: {e}", output.display()))?;
+        }
+
+        if let (Some(output), Some((_, options))) = (svg, &self.svg) {
+            fs::write(&output, world.to_svg(options)).map_err(|e| format!("{}: {e}", output.display()))?;
+        }
+
+        Ok(())
+    }
+
+    fn render_dir(&self, dir: &Path) -> Result<(), String> {
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .map_err(|e| format!("{}: {e}", dir.display()))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+            .map(|entry| entry.path())
+            .collect();
+        paths.sort_unstable();
+
+        let dirs = self
+            .png
+            .iter()
+            .map(|(dir, _)| *dir)
+            .chain(self.svg.iter().map(|(dir, _)| *dir));
+        for dir in dirs {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        }
+
+        for path in &paths {
+            let stem = path.file_stem().unwrap_or(path.as_os_str());
+            let image = |dir: &Path, ext: &str| {
+                let mut name = stem.to_os_string();
+                name.push(".");
+                name.push(ext);
+                dir.join(name)
+            };
+            self.render(
+                path,
+                self.png.as_ref().map(|(dir, _)| image(dir, "png")),
+                self.svg.as_ref().map(|(dir, _)| image(dir, "svg")),
+            )?;
+            println!("{}", path.display());
+        }
+
+        Ok(())
+    }
+}
+
+fn export(args: &Args) -> Result<(), String> {
+    let (png, svg) = (args.value("--png"), args.value("--svg"));
+    let path = match args.positional() {
+        [path] if png.is_some() || svg.is_some() => Path::new(path),
+        _ => return Err(USAGE.into()),
+    };
+
+    let cell_px = args.parsed_value("--cell-px")?.unwrap_or(DEFAULT_CELL_PX);
+    if cell_px == 0 {
+        return Err("--cell-px must be positive".into());
+    }
+    let background = parse_color(args, "--background")?;
+    let line_color = parse_color(args, "--line-color")?;
+
+    let png_options = PngOptions::default();
+    let png_options = PngOptions {
+        cell_px,
+        background: background.unwrap_or(png_options.background),
+        line_color: line_color.unwrap_or(png_options.line_color),
+        ..png_options
+    };
+    let svg_options = SvgOptions::default();
+    let svg_options = SvgOptions {
+        cell_size: cell_px,
+        stroke_width: (cell_px / 16).max(1),
+        grid: args.flag("--grid"),
+        background: background.unwrap_or(svg_options.background),
+        line_color: line_color.unwrap_or(svg_options.line_color),
+        ..svg_options
+    };
+
+    let outputs = Outputs {
+        png: png.map(|output| (Path::new(output), png_options)),
+        svg: svg.map(|output| (Path::new(output), svg_options)),
+    };
+
+    if path.is_dir() {
+        outputs.render_dir(path)
+    } else {
+        outputs.render(path, png.map(PathBuf::from), svg.map(PathBuf::from))
+    }
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let result = Args::parse(
+        args,
+        &["--grid"],
+        &["--png", "--svg", "--cell-px", "--background", "--line-color"],
+    )
+    .and_then(|args| export(&args));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::{fs, num::NonZeroUsize, path::PathBuf, process::ExitCode};
+
+use connex::{
+    analysis::Difficulty,
+    generator::{generate_solved_with, Options},
+    World,
+};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::args::Args;
+
+pub const USAGE: &str = "\
+Usage: connex-cli gen [options]
+
+Generate random solvable levels, files are stored in solved state and named 000.txt, 001.txt, ...
+
+Options:
+    --size <H>x<W>          Size of levels, default 8x8
+    --difficulty <LEVEL>    Only keep levels of difficulty: easy, normal or hard
+    --count <N>             Number of levels, default 1
+    --seed <N>              Random seed, same seed generates same levels, default random
+    --density <F>           Fraction of non-empty blocks, in range [0, 1], default 1
+    --no-cross              Do not generate cross blocks
+    -o <DIR>                Output directory, default current directory
+";
+
+/// How many levels are tried for each output level before give up finding one of required difficulty.
+const ATTEMPTS: usize = 1000;
+
+fn parse_size(s: &str) -> Option<(NonZeroUsize, NonZeroUsize)> {
+    let (height, width) = s.split_once('x')?;
+    Some((height.parse().ok()?, width.parse().ok()?))
+}
+
+fn generate_one(
+    height: NonZeroUsize, width: NonZeroUsize, options: &Options, difficulty: Option<Difficulty>, rng: &mut ChaCha8Rng,
+) -> Option<World> {
+    (0..ATTEMPTS)
+        .map(|_| generate_solved_with(height, width, options, rng))
+        .find(|world| difficulty.map(|d| world.estimate_difficulty() == d).unwrap_or(true))
+}
+
+fn gen(args: &Args) -> Result<(), String> {
+    if !args.positional().is_empty() {
+        return Err(USAGE.into());
+    }
+
+    let (height, width) = match args.value("--size") {
+        Some(size) => parse_size(size).ok_or_else(|| format!("invalid value of --size: {size}"))?,
+        None => (8.try_into().unwrap(), 8.try_into().unwrap()),
+    };
+    let difficulty = args.parsed_value::<Difficulty>("--difficulty")?;
+    let count = args.parsed_value("--count")?.unwrap_or(1);
+    let seed = args.parsed_value("--seed")?.unwrap_or_else(|| thread_rng().gen());
+    let options = Options {
+        density: args.parsed_value("--density")?.unwrap_or(1.0),
+        allow_cross: !args.flag("--no-cross"),
+        ..Options::default()
+    };
+    let dir = PathBuf::from(args.value("-o").unwrap_or("."));
+
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+
+    // ChaCha8 output is portable, so a seed reproduces the same pack on every platform
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    for i in 0..count {
+        let world = generate_one(height, width, &options, difficulty, &mut rng)
+            .ok_or_else(|| format!("can't generate level of required difficulty in {ATTEMPTS} attempts"))?;
+
+        let path = dir.join(format!("{i:03}.txt"));
+        fs::write(&path, world.to_string()).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!(
+            "{}: {}x{} {}",
+            path.display(),
+            height,
+            width,
+            world.difficulty_score().explanation()
+        );
+    }
+
+    println!("generated {count} levels with seed {seed}");
+
+    Ok(())
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let result = Args::parse(
+        args,
+        &["--no-cross"],
+        &["--size", "--difficulty", "--count", "--seed", "--density", "-o"],
+    )
+    .and_then(|args| gen(&args));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![warn(clippy::all)]
+#![warn(missing_debug_implementations)]
+#![deny(warnings)]
+#![forbid(unsafe_code)]
+
+mod args;
+mod check;
+mod convert;
+mod diff;
+mod export;
+mod gen;
+mod show;
+mod solve;
+
+use std::{env::args, process::ExitCode};
+
+const USAGE: &str = "\
+Usage: connex-cli <command> [args...]
+
+Commands:
+    check       Check level files
+    show        Print a level file as text art
+    export      Render a level file to an image
+    solve       Solve a level file
+    gen         Generate random levels
+    convert     Convert level files between formats
+    diff        Compare two level files
+    help        Print this message
+";
+
+fn main() -> ExitCode {
+    let args: Vec<_> = args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        None | Some("help" | "-h" | "--help") => {
+            print!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Some("check") => check::run(&args[1..]),
+        Some("show") => show::run(&args[1..]),
+        Some("export") => export::run(&args[1..]),
+        Some("solve") => solve::run(&args[1..]),
+        Some("gen") => gen::run(&args[1..]),
+        Some("convert") => convert::run(&args[1..]),
+        Some("diff") => diff::run(&args[1..]),
+        Some(command) => {
+            eprintln!("unknown command: {command}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::{fs, process::ExitCode};
+
+use connex::{ArtStyle, World};
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use crate::args::Args;
+
+pub const USAGE: &str = "\
+Usage: connex-cli show [--ascii | --accessible] [--shuffled [--seed <N>]] [--size] <file>
+
+Print a level file as text art.
+
+Options:
+    --ascii         Use pure ASCII characters
+    --accessible    Print text description for screen readers instead of art
+    --shuffled      Shuffle the level before print
+    --seed <N>      Random seed used by --shuffled
+    --size          Only print size and block statistics
+";
+
+fn show(args: &Args) -> Result<(), String> {
+    let path = match args.positional() {
+        [path] => path,
+        _ => return Err(USAGE.into()),
+    };
+
+    let content = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let mut world: World = content.parse().map_err(|e| format!("{path}: {e}"))?;
+
+    if args.flag("--size") {
+        let counts = world.block_counts();
+        println!("size: {}x{}", world.height(), world.width());
+        println!("empty: {}", counts.empty);
+        println!("endpoint: {}", counts.endpoint);
+        println!("through: {}", counts.through);
+        println!("turn: {}", counts.turn);
+        println!("fork: {}", counts.fork);
+        println!("cross: {}", counts.cross);
+        return Ok(());
+    }
+
+    if args.flag("--shuffled") {
+        match args.parsed_value::<u64>("--seed")? {
+            Some(seed) => world.shuffle(StdRng::seed_from_u64(seed)),
+            None => world.shuffle(thread_rng()),
+        }
+    }
+
+    if args.flag("--accessible") {
+        print!("{}", world.to_accessible_string());
+        return Ok(());
+    }
+
+    let style = if args.flag("--ascii") {
+        ArtStyle::Ascii
+    } else {
+        ArtStyle::Unicode
+    };
+    print!("{}", world.to_art(style));
+
+    Ok(())
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let result = Args::parse(args, &["--ascii", "--accessible", "--shuffled", "--size"], &["--seed"])
+        .and_then(|args| show(&args));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    fs,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use connex::{Solutions, SolveStep, World};
+
+use crate::args::Args;
+
+pub const USAGE: &str = "\
+Usage: connex-cli solve [--format world|turns] [--count <LIMIT>] [--timeout <MS>] <file>
+
+Solve a level file, print the solution or \"unsolvable\".
+
+Options:
+    --format world      Print solved level, the default
+    --format turns      Print clockwise turns of each block as `row,col: turns`, blocks need no turn are omitted
+    --count <LIMIT>     Count solutions instead, stop counting at LIMIT
+    --timeout <MS>      Abort if search takes longer than MS milliseconds
+
+Exit code is 0 if solved, 1 if unsolvable or on error, 2 on timeout.
+";
+
+/// Solver nodes visited between deadline checks.
+const STEP_BUDGET: u64 = 10_000;
+
+struct TimedOut;
+
+fn check_deadline(deadline: Option<Instant>) -> Result<(), TimedOut> {
+    match deadline {
+        Some(deadline) if Instant::now() >= deadline => Err(TimedOut),
+        _ => Ok(()),
+    }
+}
+
+fn next_solution(solutions: &mut Solutions, deadline: Option<Instant>) -> Result<Option<World>, TimedOut> {
+    loop {
+        match solutions.resume(STEP_BUDGET) {
+            SolveStep::Found(world) => return Ok(Some(world)),
+            SolveStep::Exhausted => return Ok(None),
+            SolveStep::Paused => check_deadline(deadline)?,
+        }
+    }
+}
+
+fn print_turns(world: &World, solution: &World) {
+    for (row, (blocks, solved)) in world.rows().zip(solution.rows()).enumerate() {
+        for (col, (block, solved)) in blocks.iter().zip(solved).enumerate() {
+            match block.turns_to(solved).unwrap() {
+                0 => (),
+                turns => println!("{row},{col}: {turns}"),
+            }
+        }
+    }
+}
+
+fn count(solutions: &mut Solutions, limit: usize, deadline: Option<Instant>) -> Result<ExitCode, TimedOut> {
+    let mut count = 0;
+    while count < limit && next_solution(solutions, deadline)?.is_some() {
+        count += 1;
+        // solutions may be found within the step budget one after another, without pausing
+        check_deadline(deadline)?;
+    }
+
+    if count == limit {
+        println!("solutions: at least {count}");
+    } else {
+        println!("solutions: {count}");
+    }
+
+    Ok(if count > 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn find(
+    world: &World, solutions: &mut Solutions, format: &str, deadline: Option<Instant>,
+) -> Result<ExitCode, TimedOut> {
+    match next_solution(solutions, deadline)? {
+        Some(solution) => {
+            if format == "turns" {
+                print_turns(world, &solution);
+            } else {
+                print!("{solution}");
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        None => {
+            println!("unsolvable");
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn solve(args: &Args) -> Result<ExitCode, String> {
+    let path = match args.positional() {
+        [path] => path,
+        _ => return Err(USAGE.into()),
+    };
+
+    let format = args.value("--format").unwrap_or("world");
+    if !matches!(format, "world" | "turns") {
+        return Err(format!("unknown format: {format}"));
+    }
+    let limit = args.parsed_value::<usize>("--count")?;
+    let deadline = args
+        .parsed_value("--timeout")?
+        .map(|ms| Instant::now() + Duration::from_millis(ms));
+
+    let content = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let world: World = content.parse().map_err(|e| format!("{path}: {e}"))?;
+    let mut solutions = world.solutions();
+
+    let result = match limit {
+        Some(limit) => count(&mut solutions, limit, deadline),
+        None => find(&world, &mut solutions, format, deadline),
+    };
+
+    Ok(result.unwrap_or_else(|TimedOut| {
+        eprintln!("timeout after {} nodes", solutions.nodes());
+        ExitCode::from(2)
+    }))
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let result = Args::parse(args, &[], &["--format", "--count", "--timeout"]).and_then(|args| solve(&args));
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::{arg, fixture, levels, run, temp_dir};
+
+fn stdout(args: &[&str]) -> (Option<i32>, String) {
+    let result = run(args);
+    (result.status.code(), String::from_utf8(result.stdout).unwrap())
+}
+
+#[test]
+fn bundled_levels_parse() {
+    let levels = levels();
+    let mut args = vec!["check"];
+    args.extend(levels.iter().map(|path| arg(path)));
+
+    // some bundled levels are stored unsolved, but all of them must parse
+    let (_, output) = stdout(&args);
+    let mut lines: Vec<_> = output.lines().collect();
+    assert!(lines
+        .pop()
+        .unwrap()
+        .starts_with(&format!("{} files checked, ", levels.len())));
+    for line in lines {
+        assert!(
+            line.ends_with(": ok") || line.ends_with(": not in solved state"),
+            "{line}"
+        );
+    }
+}
+
+#[test]
+fn each_failure_class_is_reported() {
+    let missing = temp_dir("check_missing").join("missing.txt");
+    let cases = [
+        (fixture("bad_block.txt"), "row 1, column 2: invalid block char: x"),
+        (fixture("missing_row.txt"), "expect 3 rows, found 2"),
+        (fixture("unsolved.txt"), "not in solved state"),
+        (missing, "No such file or directory"),
+    ];
+
+    for (path, error) in &cases {
+        let (code, output) = stdout(&["check", arg(path)]);
+        assert_eq!(code, Some(1));
+        assert!(output.starts_with(&format!("{}: {error}", path.display())), "{output}");
+        assert!(output.ends_with("1 files checked, 1 failed\n"));
+    }
+}
+
+#[test]
+fn all_parse_errors_are_reported() {
+    let path = fixture("three_errors.txt");
+    let (code, output) = stdout(&["check", arg(&path)]);
+    assert_eq!(code, Some(1));
+    assert!(
+        output.starts_with(&format!(
+            "{}: {}\n",
+            path.display(),
+            "row 1, column 2: invalid block char: x; row 3, column 3: invalid block char: ?; expect 4 rows, found 3"
+        )),
+        "{output}"
+    );
+}
+
+#[test]
+fn unique_rejects_multiple_solutions() {
+    let two_solutions = fixture("two_solutions.txt");
+
+    let (code, _) = stdout(&["check", arg(&two_solutions)]);
+    assert_eq!(code, Some(0));
+
+    let (code, output) = stdout(&["check", "--unique", arg(&two_solutions)]);
+    assert_eq!(code, Some(1));
+    assert!(output.contains("has multiple solutions"));
+
+    let (code, _) = stdout(&["check", "--unique", arg(&fixture("loop.txt"))]);
+    assert_eq!(code, Some(0));
+}
+
+#[test]
+fn mixed_results_fail_with_summary() {
+    let (code, output) = stdout(&["check", arg(&fixture("loop.txt")), arg(&fixture("unsolved.txt"))]);
+    assert_eq!(code, Some(1));
+    assert!(output.contains("loop.txt: ok\n"));
+    assert!(output.ends_with("2 files checked, 1 failed\n"));
+}
+
+#[test]
+fn json_output() {
+    let (loop_, bad) = (fixture("loop.txt"), fixture("bad_block.txt"));
+    let (code, output) = stdout(&["check", "--json", arg(&loop_), arg(&bad)]);
+    assert_eq!(code, Some(1));
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["checked"], 2);
+    assert_eq!(json["failed"], 1);
+    assert_eq!(json["files"][0]["path"], arg(&loop_));
+    assert_eq!(json["files"][0]["ok"], true);
+    assert_eq!(json["files"][1]["ok"], false);
+    assert_eq!(json["files"][1]["error"], "row 1, column 2: invalid block char: x");
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{arg, level, run, temp_dir};
+
+#[test]
+fn help_and_unknown_command() {
+    for args in [&[][..], &["help"], &["--help"]] {
+        let result = run(args);
+        assert!(result.status.success());
+        assert!(String::from_utf8(result.stdout)
+            .unwrap()
+            .starts_with("Usage: connex-cli"));
+    }
+
+    let result = run(["play"]);
+    assert_eq!(result.status.code(), Some(1));
+    assert!(String::from_utf8(result.stderr)
+        .unwrap()
+        .contains("unknown command: play"));
+}
+
+#[test]
+fn every_command_runs_on_bundled_level() {
+    let dir = temp_dir("cli_smoke");
+    let level = level();
+    let (png, json, generated) = (dir.join("level.png"), dir.join("level.json"), dir.join("gen"));
+
+    let commands: [&[&str]; 9] = [
+        &["check", arg(&level)],
+        &["show", arg(&level)],
+        &["show", "--size", arg(&level)],
+        &["export", "--png", arg(&png), "--cell-px", "4", arg(&level)],
+        &["solve", arg(&level)],
+        &["solve", "--count", "1", arg(&level)],
+        &["gen", "--size", "3x3", "--seed", "1", "-o", arg(&generated)],
+        &["convert", "--to", "json", arg(&level), "-o", arg(&json)],
+        &["diff", arg(&level), arg(&level)],
+    ];
+
+    for args in commands {
+        let result = run(args);
+        assert!(
+            result.status.success(),
+            "{args:?} failed: {}",
+            String::from_utf8_lossy(&result.stderr)
+        );
+    }
+}
+
+#[test]
+fn every_command_rejects_bad_arguments() {
+    let missing = temp_dir("cli_bad_args").join("missing.txt");
+    let level = level();
+
+    let commands: [(&[&str], i32); 12] = [
+        (&["check"], 1),
+        (&["check", arg(&missing)], 1),
+        (&["show"], 1),
+        (&["show", "--bogus", arg(&level)], 1),
+        (&["export", arg(&level)], 1),
+        (&["solve", arg(&missing)], 1),
+        (&["solve", "--format", "bogus", arg(&level)], 1),
+        (&["gen", "--size", "3"], 1),
+        (&["gen", "--count"], 1),
+        (&["convert", "--to", "json", arg(&missing)], 1),
+        (&["diff", arg(&level)], 2),
+        (&["diff", arg(&level), arg(&missing)], 2),
+    ];
+
+    for (args, code) in commands {
+        let result = run(args);
+        assert_eq!(result.status.code(), Some(code), "{args:?}");
+        // check reports failed files in its normal output, usage errors go to stderr for every command
+        assert!(
+            !result.stderr.is_empty() || args[0] == "check" && !result.stdout.is_empty(),
+            "{args:?} printed no error"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// Run connex-cli with given arguments.
+pub fn run<I, S>(args: I) -> Output
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    Command::new(env!("CARGO_BIN_EXE_connex-cli"))
+        .args(args)
+        .output()
+        .expect("run connex-cli")
+}
+
+/// Bundled level files, sorted by name.
+pub fn levels() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../connex-levels/levels");
+    let mut levels: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    levels.sort();
+    assert!(!levels.is_empty());
+    levels
+}
+
+/// The first bundled level file.
+pub fn level() -> PathBuf {
+    levels().swap_remove(0)
+}
+
+/// Path of a file in `tests/fixtures`.
+pub fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+/// Create an empty directory for a test, named after it.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Write content into a file in `dir`, returns its path.
+pub fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, content).unwrap();
+    path
+}
+
+/// Path as `&str`, for building argument lists.
+pub fn arg(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+/// Parse a level file.
+pub fn read_world(path: &Path) -> connex::World {
+    fs::read_to_string(path).unwrap().parse().unwrap()
+}
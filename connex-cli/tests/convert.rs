@@ -0,0 +1,101 @@
+mod common;
+
+use std::fs;
+
+use common::{arg, levels, read_world, run, temp_dir, write};
+
+const FORMATS: [&str; 2] = ["text", "json"];
+
+#[test]
+fn round_trip_across_all_format_pairs() {
+    let dir = temp_dir("convert_round_trip");
+    let back = dir.join("back.txt");
+
+    for level in levels() {
+        let expected = read_world(&level);
+        for from in FORMATS {
+            for to in FORMATS {
+                let source = dir.join(format!("source.{from}"));
+                let target = dir.join(format!("target.{to}"));
+
+                assert!(run(["convert", "--to", from, arg(&level), "-o", arg(&source)])
+                    .status
+                    .success());
+                assert!(run(["convert", "--to", to, arg(&source), "-o", arg(&target)])
+                    .status
+                    .success());
+                assert!(run(["convert", "--to", "text", arg(&target), "-o", arg(&back)])
+                    .status
+                    .success());
+
+                assert_eq!(read_world(&back), expected, "{} {from} -> {to}", level.display());
+            }
+        }
+    }
+}
+
+#[test]
+fn batch_mode_keeps_file_stems() {
+    let dir = temp_dir("convert_batch_stems");
+    let out = dir.join("out");
+    let a = write(&dir, "level.v2.txt", "><\n");
+    let b = write(&dir, "level.v3.txt", "v^\n");
+
+    let result = run(["convert", "--to", "json", arg(&a), arg(&b), "--out-dir", arg(&out)]);
+    assert!(result.status.success());
+
+    let mut names: Vec<_> = fs::read_dir(&out)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["level.v2.json", "level.v3.json"]);
+}
+
+#[test]
+fn canonicalize_and_trim() {
+    let dir = temp_dir("convert_canonicalize_trim");
+    let input = write(&dir, "in.txt", "v1\n3,4\n    \n >< \n    \n");
+    let output = dir.join("out.txt");
+
+    let result = run([
+        "convert",
+        "--to",
+        "text",
+        "--trim",
+        "--canonicalize",
+        arg(&input),
+        "-o",
+        arg(&output),
+    ]);
+    assert!(result.status.success());
+    assert_eq!(read_world(&output), "><".parse().unwrap());
+}
+
+#[test]
+fn canonicalize_rewrites_aliases() {
+    let dir = temp_dir("convert_canonicalize_aliases");
+    let input = write(&dir, "in.txt", "F+┐\n|L┘\n");
+    let output = dir.join("out.txt");
+
+    let result = run(["convert", "--to", "text", arg(&input), "-o", arg(&output)]);
+    assert!(!result.status.success());
+
+    let result = run([
+        "convert",
+        "--to",
+        "text",
+        "--canonicalize",
+        arg(&input),
+        "-o",
+        arg(&output),
+    ]);
+    assert!(result.status.success());
+    assert_eq!(read_world(&output), "759\n/13".parse().unwrap());
+}
+
+#[test]
+fn unknown_format_fails() {
+    let result = run(["convert", "--to", "xml", "a.txt", "-o", "b.xml"]);
+    assert!(!result.status.success());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{arg, fixture, level, run, temp_dir, write};
+
+fn diff(args: &[&str]) -> (Option<i32>, String) {
+    let mut all = vec!["diff"];
+    all.extend_from_slice(args);
+    let result = run(all);
+    (result.status.code(), String::from_utf8(result.stdout).unwrap())
+}
+
+#[test]
+fn identical_files() {
+    let level = level();
+    assert_eq!(diff(&[arg(&level), arg(&level)]), (Some(0), "".into()));
+
+    let (loop_, copy) = (fixture("loop.txt"), temp_dir("diff_identical").join("copy.txt"));
+    std::fs::copy(&loop_, &copy).unwrap();
+    assert_eq!(diff(&[arg(&loop_), arg(&copy)]), (Some(0), "".into()));
+}
+
+#[test]
+fn different_dimensions() {
+    let wide = write(&temp_dir("diff_size"), "wide.txt", "v1\n2,3\n79 \n13 \n");
+    assert_eq!(
+        diff(&[arg(&fixture("loop.txt")), arg(&wide)]),
+        (Some(1), "size: 2,2 -> 2,3\n".into())
+    );
+}
+
+#[test]
+fn single_cell_change() {
+    let changed = write(&temp_dir("diff_single"), "changed.txt", "v1\n2,2\n79\n1<\n");
+    assert_eq!(
+        diff(&[arg(&fixture("loop.txt")), arg(&changed)]),
+        (Some(1), "row 2, column 2: '3' -> '<'\n".into())
+    );
+}
+
+#[test]
+fn art_highlights_changes() {
+    let (code, output) = diff(&["--art", arg(&fixture("loop.txt")), arg(&fixture("unsolved.txt"))]);
+    assert_eq!(code, Some(1));
+
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("row 2, column 1: '1' -> '3'"));
+    assert_eq!(lines.next(), Some("row 2, column 2: '3' -> '1'"));
+    // first row is same, drawn without colors
+    assert_eq!(lines.next(), Some("┌┐ | ┌┐"));
+    assert_eq!(
+        lines.next(),
+        Some("\x1b[31m└\x1b[0m\x1b[31m┘\x1b[0m | \x1b[31m┘\x1b[0m\x1b[31m└\x1b[0m")
+    );
+    assert_eq!(lines.next(), None);
+}
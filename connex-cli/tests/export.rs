@@ -0,0 +1,48 @@
+mod common;
+
+use common::{arg, level, levels, run, temp_dir};
+
+#[test]
+fn exports_png_and_svg() {
+    let dir = temp_dir("export_single");
+    let (png, svg) = (dir.join("level.png"), dir.join("level.svg"));
+
+    let result = run([
+        "export",
+        "--png",
+        arg(&png),
+        "--svg",
+        arg(&svg),
+        "--cell-px",
+        "8",
+        arg(&level()),
+    ]);
+    assert!(result.status.success());
+    assert!(std::fs::read(&png).unwrap().starts_with(b"\x89PNG"));
+    assert!(std::fs::read_to_string(&svg).unwrap().contains("<svg"));
+}
+
+#[test]
+fn exports_directory() {
+    let dir = temp_dir("export_dir");
+    let levels_dir = level().parent().unwrap().to_path_buf();
+
+    let result = run(["export", "--png", arg(&dir), "--cell-px", "4", arg(&levels_dir)]);
+    assert!(result.status.success());
+    for level in levels() {
+        let mut name = level.file_stem().unwrap().to_os_string();
+        name.push(".png");
+        assert!(dir.join(name).is_file(), "{}", level.display());
+    }
+}
+
+#[test]
+fn too_large_image_is_an_error_not_a_panic() {
+    let dir = temp_dir("export_too_large");
+    let png = dir.join("level.png");
+
+    for cell_px in ["0", "4294967295", "100000"] {
+        let result = run(["export", "--png", arg(&png), "--cell-px", cell_px, arg(&level())]);
+        assert_eq!(result.status.code(), Some(1), "--cell-px {cell_px}");
+    }
+}
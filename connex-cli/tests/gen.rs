@@ -0,0 +1,85 @@
+mod common;
+
+use std::{fs, path::Path};
+
+use common::{arg, read_world, run, temp_dir};
+use connex::Block;
+
+fn gen(dir: &Path, args: &[&str]) -> String {
+    let mut all = vec!["gen", "-o", arg(dir)];
+    all.extend_from_slice(args);
+    let result = run(all);
+    assert!(
+        result.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    String::from_utf8(result.stdout).unwrap()
+}
+
+fn files(dir: &Path) -> Vec<Vec<u8>> {
+    let mut paths: Vec<_> = fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+    paths.sort();
+    paths.iter().map(|path| fs::read(path).unwrap()).collect()
+}
+
+#[test]
+fn generated_levels_are_solved() {
+    let dir = temp_dir("gen_solved");
+    let output = gen(&dir, &["--size", "4x6", "--count", "5", "--seed", "7"]);
+    assert!(output.ends_with("generated 5 levels with seed 7\n"));
+
+    for i in 0..5 {
+        let path = dir.join(format!("{i:03}.txt"));
+        assert!(output.contains(&format!("{}: 4x6 ", path.display())));
+        let world = read_world(&path);
+        assert_eq!((world.height().get(), world.width().get()), (4, 6));
+        assert!(world.solved(), "{}", path.display());
+    }
+}
+
+#[test]
+fn same_seed_same_files() {
+    let (a, b, c) = (temp_dir("gen_seed_a"), temp_dir("gen_seed_b"), temp_dir("gen_seed_c"));
+    let args = ["--size", "5x5", "--count", "3", "--seed", "42"];
+    gen(&a, &args);
+    gen(&b, &args);
+    gen(&c, &["--size", "5x5", "--count", "3", "--seed", "43"]);
+
+    assert_eq!(files(&a), files(&b));
+    assert_ne!(files(&a), files(&c));
+}
+
+#[test]
+fn seeded_output_is_pinned() {
+    let dir = temp_dir("gen_pinned");
+    gen(&dir, &["--size", "3x3", "--seed", "42"]);
+    // changing this breaks reproducing packs from their seeds, bump it only on purpose
+    assert_eq!(
+        fs::read_to_string(dir.join("000.txt")).unwrap(),
+        "v1\n3,3\n7<v\n4</\n1-3\n"
+    );
+}
+
+#[test]
+fn generator_options() {
+    let dir = temp_dir("gen_no_cross");
+    gen(&dir, &["--size", "6x6", "--count", "5", "--seed", "1", "--no-cross"]);
+    for i in 0..5 {
+        let world = read_world(&dir.join(format!("{i:03}.txt")));
+        assert!(world.iter().all(|(_, _, block)| *block != Block::Cross));
+    }
+
+    let dir = temp_dir("gen_density");
+    gen(&dir, &["--size", "6x6", "--seed", "1", "--density", "0.3"]);
+    let world = read_world(&dir.join("000.txt"));
+    assert!(world.solved());
+    assert!(world.iter().any(|(_, _, block)| *block == Block::Empty));
+
+    let dir = temp_dir("gen_difficulty");
+    let output = gen(
+        &dir,
+        &["--size", "5x5", "--count", "3", "--seed", "3", "--difficulty", "easy"],
+    );
+    assert_eq!(output.matches(" easy, ").count(), 3);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::{arg, fixture, run, temp_dir, write};
+
+fn solve(args: &[&str]) -> (Option<i32>, String) {
+    let mut all = vec!["solve"];
+    all.extend_from_slice(args);
+    let result = run(all);
+    (result.status.code(), String::from_utf8(result.stdout).unwrap())
+}
+
+#[test]
+fn solvable() {
+    let level = fixture("unsolved.txt");
+
+    assert_eq!(solve(&[arg(&level)]), (Some(0), "v1\n2,2\n79\n13\n".into()));
+    assert_eq!(
+        solve(&["--format", "turns", arg(&level)]),
+        (Some(0), "1,0: 1\n1,1: 3\n".into())
+    );
+    // already solved level needs no turn
+    assert_eq!(
+        solve(&["--format", "turns", arg(&fixture("loop.txt"))]),
+        (Some(0), "".into())
+    );
+}
+
+#[test]
+fn unsolvable() {
+    let level = fixture("unsolvable.txt");
+
+    assert_eq!(solve(&[arg(&level)]), (Some(1), "unsolvable\n".into()));
+    assert_eq!(
+        solve(&["--count", "5", arg(&level)]),
+        (Some(1), "solutions: 0\n".into())
+    );
+}
+
+#[test]
+fn multiple_solutions() {
+    let level = fixture("two_solutions.txt");
+
+    assert_eq!(
+        solve(&["--count", "5", arg(&level)]),
+        (Some(0), "solutions: 2\n".into())
+    );
+    assert_eq!(
+        solve(&["--count", "2", arg(&level)]),
+        (Some(0), "solutions: at least 2\n".into())
+    );
+    assert_eq!(
+        solve(&["--count", "1", arg(&level)]),
+        (Some(0), "solutions: at least 1\n".into())
+    );
+}
+
+#[test]
+fn timeout_aborts_search() {
+    // every domino tiling of the board is a solution, far too many to count
+    let rows = vec!["><".repeat(10); 20].join("\n");
+    let level = write(
+        &temp_dir("solve_timeout"),
+        "dominoes.txt",
+        &format!("v1\n20,20\n{rows}\n"),
+    );
+
+    let result = run(["solve", "--count", "1000000", "--timeout", "50", arg(&level)]);
+    assert_eq!(result.status.code(), Some(2));
+    assert!(String::from_utf8(result.stderr).unwrap().starts_with("timeout after"));
+}
@@ -0,0 +1,21 @@
+use std::{env, path::PathBuf};
+
+fn main() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    // C smoke test compiles for the same target
+    println!("cargo:rustc-env=TARGET={}", env::var("TARGET").unwrap());
+
+    // source tree may be read-only, e.g. vendored, so only write to OUT_DIR, the committed `include/connex.h` is
+    // checked against it by tests
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).expect("read cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("generate C header")
+        .write_to_file(out_dir.join("connex.h"));
+}
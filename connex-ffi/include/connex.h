@@ -0,0 +1,159 @@
+#ifndef CONNEX_H
+#define CONNEX_H
+
+/* Generated by cbindgen from connex-ffi, don't edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Commands can be applied by [`connex_game_apply`].
+ */
+typedef enum ConnexCommand {
+  /**
+   * Move cursor up.
+   */
+  CONNEX_COMMAND_MOVE_UP,
+  /**
+   * Move cursor right.
+   */
+  CONNEX_COMMAND_MOVE_RIGHT,
+  /**
+   * Move cursor down.
+   */
+  CONNEX_COMMAND_MOVE_DOWN,
+  /**
+   * Move cursor left.
+   */
+  CONNEX_COMMAND_MOVE_LEFT,
+  /**
+   * Turn block under cursor clockwise.
+   */
+  CONNEX_COMMAND_ROTATE_CURSOR_BLOCK,
+} ConnexCommand;
+
+/**
+ * Result of all functions.
+ */
+typedef enum ConnexStatus {
+  /**
+   * Success.
+   */
+  CONNEX_STATUS_OK = 0,
+  /**
+   * A pointer argument is null.
+   */
+  CONNEX_STATUS_NULL_POINTER,
+  /**
+   * Level string is not valid UTF-8.
+   */
+  CONNEX_STATUS_INVALID_UTF8,
+  /**
+   * Level string is not a valid world.
+   */
+  CONNEX_STATUS_PARSE_ERROR,
+  /**
+   * Block index out of range.
+   */
+  CONNEX_STATUS_OUT_OF_RANGE,
+  /**
+   * Output buffer is too small, the needed length is written back.
+   */
+  CONNEX_STATUS_BUFFER_TOO_SMALL,
+  /**
+   * Rust code panicked, the game may be in an inconsistent state and should be freed.
+   */
+  CONNEX_STATUS_PANIC,
+} ConnexStatus;
+
+/**
+ * Opaque game handle, created by [`connex_game_new`] and freed by [`connex_game_free`].
+ */
+typedef struct ConnexGame ConnexGame;
+
+/**
+ * Create a game from a NUL-terminated level string, see `connex::World` for the format.
+ *
+ * # Safety
+ *
+ * `level` must be null or a valid NUL-terminated string, `out` must be null or valid for writes.
+ */
+enum ConnexStatus connex_game_new(const char *level, struct ConnexGame **out);
+
+/**
+ * Free a game, null is ignored.
+ *
+ * # Safety
+ *
+ * `game` must be null or created by [`connex_game_new`] and not freed yet.
+ */
+enum ConnexStatus connex_game_free(struct ConnexGame *game);
+
+/**
+ * Apply a command to the game.
+ *
+ * # Safety
+ *
+ * `game` must be null or a valid game.
+ */
+enum ConnexStatus connex_game_apply(struct ConnexGame *game, enum ConnexCommand command);
+
+/**
+ * Get size of the game world.
+ *
+ * # Safety
+ *
+ * `game` must be null or a valid game, `height` and `width` must be null or valid for writes.
+ */
+enum ConnexStatus connex_game_size(const struct ConnexGame *game, size_t *height, size_t *width);
+
+/**
+ * Get Unicode code point of the text format char of block at `(row, col)`.
+ *
+ * # Safety
+ *
+ * `game` must be null or a valid game, `out` must be null or valid for writes.
+ */
+enum ConnexStatus connex_game_block_char_at(const struct ConnexGame *game,
+                                            size_t row,
+                                            size_t col,
+                                            uint32_t *out);
+
+/**
+ * Get cursor position.
+ *
+ * # Safety
+ *
+ * `game` must be null or a valid game, `row` and `col` must be null or valid for writes.
+ */
+enum ConnexStatus connex_game_cursor(const struct ConnexGame *game, size_t *row, size_t *col);
+
+/**
+ * Check if the game is solved.
+ *
+ * # Safety
+ *
+ * `game` must be null or a valid game, `out` must be null or valid for writes.
+ */
+enum ConnexStatus connex_game_solved(const struct ConnexGame *game, bool *out);
+
+/**
+ * Write the game world in text format to `buf` as a NUL-terminated string.
+ *
+ * `len` is the capacity of `buf` when called, and is set to the needed length including the NUL byte.
+ * [`ConnexStatus::BufferTooSmall`] is returned if the capacity is not enough, `buf` can be null then, so call it
+ * with `*len == 0` first to get the length.
+ *
+ * # Safety
+ *
+ * `game` must be null or a valid game, `len` must be null or valid for reads and writes, `buf` must be null or
+ * valid for writes of `*len` bytes.
+ */
+enum ConnexStatus connex_game_to_string(const struct ConnexGame *game,
+                                        char *buf,
+                                        size_t *len);
+
+#endif /* CONNEX_H */
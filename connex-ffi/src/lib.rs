@@ -0,0 +1,280 @@
+#![warn(clippy::all)]
+#![warn(missing_docs, missing_debug_implementations)]
+#![deny(warnings)]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+//! # Connex FFI
+//!
+//! Expose connex game logic to C and C++, the header is generated to `include/connex.h` when building.
+//!
+//! All functions return a [`ConnexStatus`] and write results through out pointers, they never unwind into C,
+//! a Rust panic is reported as [`ConnexStatus::Panic`].
+
+use std::{
+    ffi::{c_char, CStr},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+};
+
+use connex::{Command, Direction, Game, World};
+
+/// Result of all functions.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConnexStatus {
+    /// Success.
+    Ok = 0,
+    /// A pointer argument is null.
+    NullPointer,
+    /// Level string is not valid UTF-8.
+    InvalidUtf8,
+    /// Level string is not a valid world.
+    ParseError,
+    /// Block index out of range.
+    OutOfRange,
+    /// Output buffer is too small, the needed length is written back.
+    BufferTooSmall,
+    /// Rust code panicked, the game may be in an inconsistent state and should be freed.
+    Panic,
+}
+
+/// Commands can be applied by [`connex_game_apply`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConnexCommand {
+    /// Move cursor up.
+    MoveUp,
+    /// Move cursor right.
+    MoveRight,
+    /// Move cursor down.
+    MoveDown,
+    /// Move cursor left.
+    MoveLeft,
+    /// Turn block under cursor clockwise.
+    RotateCursorBlock,
+}
+
+impl From<ConnexCommand> for Command {
+    fn from(command: ConnexCommand) -> Self {
+        match command {
+            ConnexCommand::MoveUp => Command::MoveCursor(Direction::Up),
+            ConnexCommand::MoveRight => Command::MoveCursor(Direction::Right),
+            ConnexCommand::MoveDown => Command::MoveCursor(Direction::Down),
+            ConnexCommand::MoveLeft => Command::MoveCursor(Direction::Left),
+            ConnexCommand::RotateCursorBlock => Command::RotateCursorBlock,
+        }
+    }
+}
+
+/// Opaque game handle, created by [`connex_game_new`] and freed by [`connex_game_free`].
+#[derive(Debug)]
+pub struct ConnexGame(Game);
+
+/// Run `f` and turn a panic into [`ConnexStatus::Panic`].
+fn guard(f: impl FnOnce() -> ConnexStatus) -> ConnexStatus {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(ConnexStatus::Panic)
+}
+
+/// Write `value` to `out` if it's not null.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+unsafe fn write<T>(out: *mut T, value: T) -> ConnexStatus {
+    // SAFETY: caller guarantees `out` is valid when not null
+    match unsafe { out.as_mut() } {
+        Some(out) => {
+            *out = value;
+            ConnexStatus::Ok
+        }
+        None => ConnexStatus::NullPointer,
+    }
+}
+
+/// Create a game from a NUL-terminated level string, see `connex::World` for the format.
+///
+/// # Safety
+///
+/// `level` must be null or a valid NUL-terminated string, `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_new(level: *const c_char, out: *mut *mut ConnexGame) -> ConnexStatus {
+    guard(|| {
+        if level.is_null() || out.is_null() {
+            return ConnexStatus::NullPointer;
+        }
+        // SAFETY: caller guarantees `level` is a valid NUL-terminated string
+        let Ok(level) = unsafe { CStr::from_ptr(level) }.to_str() else {
+            return ConnexStatus::InvalidUtf8;
+        };
+        let Ok(world) = level.parse::<World>() else {
+            return ConnexStatus::ParseError;
+        };
+
+        let game = Box::into_raw(Box::new(ConnexGame(Game::new(world))));
+        // SAFETY: checked not null, caller guarantees it's valid for writes
+        unsafe { write(out, game) }
+    })
+}
+
+/// Free a game, null is ignored.
+///
+/// # Safety
+///
+/// `game` must be null or created by [`connex_game_new`] and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_free(game: *mut ConnexGame) -> ConnexStatus {
+    guard(|| {
+        if !game.is_null() {
+            // SAFETY: caller guarantees it's created by `Box::into_raw` in `connex_game_new`
+            drop(unsafe { Box::from_raw(game) });
+        }
+        ConnexStatus::Ok
+    })
+}
+
+/// Apply a command to the game.
+///
+/// # Safety
+///
+/// `game` must be null or a valid game.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_apply(game: *mut ConnexGame, command: ConnexCommand) -> ConnexStatus {
+    guard(|| {
+        // SAFETY: caller guarantees `game` is valid when not null
+        let Some(game) = (unsafe { game.as_mut() }) else {
+            return ConnexStatus::NullPointer;
+        };
+        game.0.apply(command.into());
+        ConnexStatus::Ok
+    })
+}
+
+/// Get size of the game world.
+///
+/// # Safety
+///
+/// `game` must be null or a valid game, `height` and `width` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_size(
+    game: *const ConnexGame, height: *mut usize, width: *mut usize,
+) -> ConnexStatus {
+    guard(|| {
+        // SAFETY: caller guarantees `game` is valid when not null
+        let Some(game) = (unsafe { game.as_ref() }) else {
+            return ConnexStatus::NullPointer;
+        };
+        if height.is_null() || width.is_null() {
+            return ConnexStatus::NullPointer;
+        }
+        let world = game.0.world();
+        // SAFETY: checked not null, caller guarantees they are valid for writes
+        unsafe {
+            write(height, world.height().get());
+            write(width, world.width().get())
+        }
+    })
+}
+
+/// Get Unicode code point of the text format char of block at `(row, col)`.
+///
+/// # Safety
+///
+/// `game` must be null or a valid game, `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_block_char_at(
+    game: *const ConnexGame, row: usize, col: usize, out: *mut u32,
+) -> ConnexStatus {
+    guard(|| {
+        // SAFETY: caller guarantees `game` is valid when not null
+        let Some(game) = (unsafe { game.as_ref() }) else {
+            return ConnexStatus::NullPointer;
+        };
+        let Some(block) = game.0.world().get(row, col) else {
+            return ConnexStatus::OutOfRange;
+        };
+        // block chars are single chars in text format
+        let ch = block.to_string().chars().next().unwrap();
+        // SAFETY: caller guarantees `out` is valid when not null
+        unsafe { write(out, u32::from(ch)) }
+    })
+}
+
+/// Get cursor position.
+///
+/// # Safety
+///
+/// `game` must be null or a valid game, `row` and `col` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_cursor(game: *const ConnexGame, row: *mut usize, col: *mut usize) -> ConnexStatus {
+    guard(|| {
+        // SAFETY: caller guarantees `game` is valid when not null
+        let Some(game) = (unsafe { game.as_ref() }) else {
+            return ConnexStatus::NullPointer;
+        };
+        if row.is_null() || col.is_null() {
+            return ConnexStatus::NullPointer;
+        }
+        let cursor = game.0.cursor();
+        // SAFETY: checked not null, caller guarantees they are valid for writes
+        unsafe {
+            write(row, cursor.row);
+            write(col, cursor.col)
+        }
+    })
+}
+
+/// Check if the game is solved.
+///
+/// # Safety
+///
+/// `game` must be null or a valid game, `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_solved(game: *const ConnexGame, out: *mut bool) -> ConnexStatus {
+    guard(|| {
+        // SAFETY: caller guarantees `game` is valid when not null
+        let Some(game) = (unsafe { game.as_ref() }) else {
+            return ConnexStatus::NullPointer;
+        };
+        // SAFETY: caller guarantees `out` is valid when not null
+        unsafe { write(out, game.0.solved()) }
+    })
+}
+
+/// Write the game world in text format to `buf` as a NUL-terminated string.
+///
+/// `len` is the capacity of `buf` when called, and is set to the needed length including the NUL byte.
+/// [`ConnexStatus::BufferTooSmall`] is returned if the capacity is not enough, `buf` can be null then, so call it
+/// with `*len == 0` first to get the length.
+///
+/// # Safety
+///
+/// `game` must be null or a valid game, `len` must be null or valid for reads and writes, `buf` must be null or
+/// valid for writes of `*len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn connex_game_to_string(
+    game: *const ConnexGame, buf: *mut c_char, len: *mut usize,
+) -> ConnexStatus {
+    guard(|| {
+        // SAFETY: caller guarantees `game` and `len` are valid when not null
+        let (Some(game), Some(len)) = (unsafe { game.as_ref() }, unsafe { len.as_mut() }) else {
+            return ConnexStatus::NullPointer;
+        };
+
+        let s = game.0.world().to_string();
+        let capacity = *len;
+        *len = s.len() + 1;
+        if capacity < *len {
+            return ConnexStatus::BufferTooSmall;
+        }
+        if buf.is_null() {
+            return ConnexStatus::NullPointer;
+        }
+
+        // SAFETY: caller guarantees `buf` is valid for `capacity` bytes, which is enough
+        unsafe {
+            ptr::copy_nonoverlapping(s.as_ptr().cast(), buf, s.len());
+            *buf.add(s.len()) = 0;
+        }
+        ConnexStatus::Ok
+    })
+}
@@ -0,0 +1,53 @@
+/* Smoke test of the C API: load a level, rotate a block until it's solved, and read it back. */
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "connex.h"
+
+#define CHECK(expr)                                                   \
+    do {                                                              \
+        if (!(expr)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #expr); \
+            return 1;                                                 \
+        }                                                             \
+    } while (0)
+
+int main(void) {
+    ConnexGame *game = NULL;
+    size_t height = 0, width = 0, row = 9, col = 9, len = 0;
+    uint32_t block = 0;
+    bool solved = true;
+    int turns = 0;
+
+    CHECK(connex_game_new("not a level\n>", &game) == CONNEX_STATUS_PARSE_ERROR);
+    CHECK(connex_game_new("><\n", NULL) == CONNEX_STATUS_NULL_POINTER);
+
+    CHECK(connex_game_new("^<\n", &game) == CONNEX_STATUS_OK);
+    CHECK(connex_game_size(game, &height, &width) == CONNEX_STATUS_OK);
+    CHECK(height == 1 && width == 2);
+    CHECK(connex_game_cursor(game, &row, &col) == CONNEX_STATUS_OK);
+    CHECK(row == 0 && col == 0);
+    CHECK(connex_game_block_char_at(game, 0, 0, &block) == CONNEX_STATUS_OK);
+    CHECK(block == '^');
+    CHECK(connex_game_block_char_at(game, 1, 0, &block) == CONNEX_STATUS_OUT_OF_RANGE);
+
+    CHECK(connex_game_solved(game, &solved) == CONNEX_STATUS_OK);
+    CHECK(!solved);
+    while (!solved && turns < 4) {
+        CHECK(connex_game_apply(game, CONNEX_COMMAND_ROTATE_CURSOR_BLOCK) == CONNEX_STATUS_OK);
+        CHECK(connex_game_solved(game, &solved) == CONNEX_STATUS_OK);
+        turns++;
+    }
+    CHECK(solved);
+
+    CHECK(connex_game_to_string(game, NULL, &len) == CONNEX_STATUS_BUFFER_TOO_SMALL);
+    char *buf = malloc(len);
+    CHECK(buf != NULL);
+    CHECK(connex_game_to_string(game, buf, &len) == CONNEX_STATUS_OK);
+    printf("%s\n", buf);
+    free(buf);
+
+    CHECK(connex_game_free(game) == CONNEX_STATUS_OK);
+    return 0;
+}
@@ -0,0 +1,53 @@
+//! Checks of the C side: the committed header is up to date, and a C program using it works.
+
+use std::{env, fs, path::Path, process::Command};
+
+const HEADER: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/include/connex.h");
+const GENERATED_HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/connex.h"));
+
+/// Set `CONNEX_UPDATE_SNAPSHOTS=1` to update the committed header after changing the API.
+#[test]
+fn committed_header_is_up_to_date() {
+    if env::var_os("CONNEX_UPDATE_SNAPSHOTS").is_some() {
+        fs::write(HEADER, GENERATED_HEADER).unwrap();
+    }
+    let committed = fs::read_to_string(HEADER).unwrap();
+    assert!(
+        committed == GENERATED_HEADER,
+        "include/connex.h is outdated, run tests with CONNEX_UPDATE_SNAPSHOTS=1 to update it"
+    );
+}
+
+#[test]
+fn c_smoke_program() {
+    // test executable is in `target/<profile>/deps`, static library is in `target/<profile>`
+    let exe = env::current_exe().unwrap();
+    let profile_dir = exe.parent().and_then(Path::parent).unwrap();
+    let library = profile_dir.join("libconnex_ffi.a");
+    assert!(library.is_file(), "{} not found", library.display());
+
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("c_smoke");
+    fs::create_dir_all(&out_dir).unwrap();
+    let program = out_dir.join("main");
+
+    let compiler = cc::Build::new()
+        .cargo_metadata(false)
+        .target(env!("TARGET"))
+        .host(env!("TARGET"))
+        .opt_level(0)
+        .get_compiler();
+    let status = compiler
+        .to_command()
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/c/main.c"))
+        .arg(format!("-I{}", Path::new(HEADER).parent().unwrap().display()))
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "compile C smoke program");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "v1\n1,2\n><\n\n");
+}
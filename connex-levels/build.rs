@@ -2,6 +2,9 @@ use std::{fs, path::PathBuf, str::FromStr};
 
 use connex::World;
 
+#[path = "src/hash.rs"]
+mod hash;
+
 fn main() {
     let mut level_files: Vec<_> = fs::read_dir("levels").unwrap()
         .filter_map(|x| x.ok())
@@ -12,8 +15,10 @@ fn main() {
     level_files.sort_unstable();
 
     let mut src = String::new();
+    let mut meta = String::new();
 
     src.push_str("&[");
+    meta.push_str("&[");
     for path in level_files {
         let mut abs_path = PathBuf::new();
         abs_path.push(env!("CARGO_MANIFEST_DIR"));
@@ -23,15 +28,32 @@ fn main() {
         println!("cargo:rerun-if-changed={}", abs_path.to_str().unwrap());
 
         let content = String::from_utf8(fs::read(&abs_path).unwrap()).unwrap();
-        World::from_str(&content)
+        let world = World::from_str(&content)
             .map_err(|e| format!("{} compile failed: {e}", path.to_str().unwrap()))
             .unwrap();
+        if let Err(errors) = world.validate_puzzle() {
+            let errors: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+            panic!(
+                "{} is not a valid puzzle: {}",
+                path.to_str().unwrap(),
+                errors.join(", ")
+            );
+        }
 
         src.push_str("include_str!(r#\"");
         src.push_str(abs_path.to_str().unwrap());
         src.push_str("\"#),");
+
+        let name = path.file_stem().unwrap().to_str().unwrap();
+        let hash = hash::level_hash(world.to_string().as_bytes());
+        let hints = world.minimum_hints_needed();
+        meta.push_str(&format!(
+            "Level {{ name: {name:?}, content: include_str!(r#\"{}\"#), hash: {hash:#018x}, hints: {hints} }},",
+            abs_path.to_str().unwrap()
+        ));
     }
     src.push(']');
+    meta.push(']');
 
     println!("cargo:rerun-if-changed=levels");
 
@@ -39,5 +61,7 @@ fn main() {
     out_file_path.push(std::env::var("OUT_DIR").unwrap());
     out_file_path.push("levels.rs");
 
-    fs::write(out_file_path.as_path(), src.as_bytes()).unwrap()
+    fs::write(out_file_path.as_path(), src.as_bytes()).unwrap();
+    out_file_path.set_file_name("levels_meta.rs");
+    fs::write(out_file_path.as_path(), meta.as_bytes()).unwrap()
 }
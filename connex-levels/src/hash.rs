@@ -0,0 +1,32 @@
+/// FNV-1a 64 bit hash of bytes, it's stable across platforms and versions.
+pub const fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(PRIME);
+        i += 1;
+    }
+
+    hash
+}
+
+/// FNV-1a hash of a level string, leading version line like `v1` is skipped, so level hashes don't change when
+/// the text format version changes.
+pub const fn level_hash(canonical: &[u8]) -> u64 {
+    let mut start = 0;
+    if !canonical.is_empty() && canonical[0] == b'v' {
+        let mut i = 1;
+        while i < canonical.len() && canonical[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i > 1 && i < canonical.len() && canonical[i] == b'\n' {
+            start = i + 1;
+        }
+    }
+
+    fnv1a(canonical.split_at(start).1)
+}
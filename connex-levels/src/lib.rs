@@ -10,5 +10,125 @@
 //!
 //! Use [`connex::World::from_str`] to compile it to real game world.
 
+mod hash;
+pub mod tutorial;
+
 /// Connex levels.
 pub const LEVELS: &[&str] = include!(concat!(env!("OUT_DIR"), "/levels.rs"));
+
+/// Connex levels with their metadata, in the same order of [`LEVELS`].
+pub const ALL: &[Level] = include!(concat!(env!("OUT_DIR"), "/levels_meta.rs"));
+
+/// A built-in level with its metadata.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Level {
+    /// Level file name without extension.
+    pub name: &'static str,
+    /// Level in string format.
+    pub content: &'static str,
+    /// Hash of the canonical level string, computed at build time, see [`Level::hash_of`].
+    pub hash: u64,
+    /// Blocks need to be fixed to make the level has a unique solution, 0 means it's already unique,
+    /// see `connex::World::minimum_hints_needed`.
+    pub hints: usize,
+}
+
+impl Level {
+    /// Hash a canonical level string, which is the [`Display`] result of the parsed world, so line endings and
+    /// optional size line don't change the hash.
+    ///
+    /// It's the 64 bit FNV-1a hash of the string's UTF-8 bytes without the version line, which is stable, so it can
+    /// be used as a key of saved data, for custom levels too.
+    ///
+    /// [`Display`]: core::fmt::Display
+    pub const fn hash_of(canonical: &str) -> u64 {
+        hash::level_hash(canonical.as_bytes())
+    }
+}
+
+/// Find a built-in level by its name.
+pub fn find(name: &str) -> Option<&'static Level> {
+    ALL.iter().find(|level| level.name == name)
+}
+
+/// Find a built-in level by its hash, see [`Level::hash_of`].
+pub fn by_hash(hash: u64) -> Option<&'static Level> {
+    ALL.iter().find(|level| level.hash == hash)
+}
+
+/// SplitMix64 finalizer, spreads close inputs like consecutive days to unrelated outputs.
+const fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Pick the level of a day, returns level index in [`ALL`] and a seed to shuffle it, e.g. with
+/// `connex::World::shuffle`, so every player gets the same puzzle on the same day.
+///
+/// `date_seed` can be any number identifies a day, like days since epoch or a `yyyymmdd` number.
+///
+/// ## Panics
+///
+/// If there is no level.
+pub fn daily(date_seed: u64) -> (usize, u64) {
+    assert!(!ALL.is_empty(), "no level");
+
+    let index = mix(date_seed) % ALL.len() as u64;
+    let seed = mix(date_seed ^ hash::fnv1a(b"shuffle"));
+    (index as usize, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::ToString;
+
+    use connex::World;
+
+    use super::*;
+
+    #[test]
+    fn build_time_hash_matches_runtime_hash() {
+        for level in ALL {
+            let canonical = level.content.parse::<World>().unwrap().to_string();
+            assert_eq!(level.hash, Level::hash_of(&canonical), "{}", level.name);
+            assert_eq!(by_hash(level.hash), Some(level), "{}", level.name);
+            assert_eq!(find(level.name), Some(level));
+        }
+
+        assert_eq!(find("no such level"), None);
+    }
+
+    #[test]
+    fn hash_ignores_version_line() {
+        assert_eq!(Level::hash_of("v1\n1,2\n><\n"), Level::hash_of("1,2\n><\n"));
+        assert_eq!(Level::hash_of("v1\n1,2\n><\n"), hash::fnv1a(b"1,2\n><\n"));
+        // only a version line followed by other lines is skipped
+        assert_eq!(Level::hash_of("v\n"), hash::fnv1a(b"v\n"));
+        assert_eq!(Level::hash_of("v12"), hash::fnv1a(b"v12"));
+    }
+
+    #[test]
+    fn daily_is_deterministic_and_spreads() {
+        let mut picked = [0usize; ALL.len()];
+        let mut seeds = alloc::collections::BTreeSet::new();
+        for day in 0..ALL.len() as u64 * 50 {
+            let (index, seed) = daily(day);
+            assert_eq!(daily(day), (index, seed));
+            assert!(index < ALL.len());
+            picked[index] += 1;
+            seeds.insert(seed);
+        }
+
+        // every level is picked, and no level takes more than a quarter of the days
+        assert!(picked.iter().all(|&count| count > 0), "{picked:?}");
+        assert!(picked.iter().all(|&count| count < ALL.len() * 50 / 4), "{picked:?}");
+        assert_eq!(seeds.len(), ALL.len() * 50);
+
+        // pinned, so the daily scramble doesn't change between versions, the index changes with the level list
+        assert_eq!(daily(20240229).1, 0x5195_96f8_c94c_b074);
+    }
+}
@@ -0,0 +1,117 @@
+//! Tutorial for first-time players, a sequence of tiny levels with instructions.
+//!
+//! It's only data, players like `connex-tui` decide how to show instructions and check goals against their game.
+
+/// Kind of player input.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Move the cursor.
+    Move,
+    /// Turn the block under cursor.
+    Rotate,
+}
+
+/// Condition to finish a tutorial step, positions are `(row, col)`, 0-based.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Goal {
+    /// Cursor is at the position.
+    CursorAt(usize, usize),
+    /// Block at the position fits all its neighbors and doesn't pass to the boundary.
+    BlockFits(usize, usize),
+    /// The whole level is solved.
+    Solved,
+}
+
+/// A tutorial step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Step {
+    /// Level in string format, `None` to continue with the level of previous step as is, cursor included.
+    pub content: Option<&'static str>,
+    /// Instruction shown to player.
+    pub instruction: &'static str,
+    /// Inputs allowed in this step, others should be ignored.
+    pub actions: &'static [Action],
+    /// Block to point out, as `(row, col)`.
+    pub highlight: Option<(usize, usize)>,
+    /// Step is finished when this is reached.
+    pub goal: Goal,
+}
+
+/// Tutorial steps in order.
+pub const STEPS: &[Step] = &[
+    Step {
+        content: Some("79\n11"),
+        instruction: "Move the cursor to the highlighted block with arrow keys, hjkl or wasd.",
+        actions: &[Action::Move],
+        highlight: Some((1, 1)),
+        goal: Goal::CursorAt(1, 1),
+    },
+    Step {
+        content: None,
+        instruction: "Press space or enter to rotate the highlighted block until both ends connect.",
+        actions: &[Action::Rotate],
+        highlight: Some((1, 1)),
+        goal: Goal::BlockFits(1, 1),
+    },
+    Step {
+        content: Some("1/7\n- -\n9/1"),
+        instruction: "A level is solved when every block fits its neighbors. Rotate blocks to close the loop.",
+        actions: &[Action::Move, Action::Rotate],
+        highlight: None,
+        goal: Goal::Solved,
+    },
+    Step {
+        content: Some("> \n7^"),
+        instruction: "Endpoints have only one end, connect them with the pipes between.",
+        actions: &[Action::Move, Action::Rotate],
+        highlight: None,
+        goal: Goal::Solved,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use connex::{Pos, World};
+
+    use super::*;
+
+    #[test]
+    fn steps_are_playable() {
+        assert!(STEPS[0].content.is_some(), "first step must have a level");
+        assert_eq!(
+            STEPS.last().unwrap().goal,
+            Goal::Solved,
+            "tutorial should end with a solved level"
+        );
+
+        let mut world: Option<World> = None;
+        for (i, step) in STEPS.iter().enumerate() {
+            if let Some(content) = step.content {
+                world = Some(content.parse().unwrap_or_else(|e| panic!("step {i}: {e:?}")));
+            }
+            let world = world.as_ref().unwrap();
+            let in_range = |(row, col): (usize, usize)| world.get(row, col).is_some();
+
+            assert!(!step.actions.is_empty(), "step {i}");
+            assert!(step.highlight.is_none_or(in_range), "step {i}");
+            match step.goal {
+                Goal::CursorAt(row, col) => {
+                    assert!(in_range((row, col)), "step {i}");
+                    assert!(step.actions.contains(&Action::Move), "step {i}");
+                }
+                Goal::BlockFits(row, col) => {
+                    assert!(in_range((row, col)), "step {i}");
+                    assert!(step.actions.contains(&Action::Rotate), "step {i}");
+                    assert!(
+                        !world.solved_cells().contains(&Pos::new(row, col)),
+                        "step {i} is already done"
+                    );
+                }
+                Goal::Solved => {
+                    assert!(world.solve().is_some(), "step {i} is not solvable");
+                    assert!(!world.solved(), "step {i} is already done");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+#![warn(clippy::all)]
+#![warn(missing_debug_implementations)]
+#![deny(warnings)]
+#![forbid(unsafe_code)]
+
+//! Connex multiplayer server, players connected by TCP play on the same world together.
+//!
+//! Usage: `connex-server [address] [level name]`, address defaults to `127.0.0.1:7878` and level to the first
+//! built-in one, it's shuffled when server starts. See [`protocol`] for messages.
+
+mod protocol;
+mod session;
+
+use std::{
+    env::args,
+    error::Error,
+    net::SocketAddr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use connex::World;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, RwLock},
+};
+
+use protocol::{ClientMessage, ServerMessage};
+use session::Session;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+/// Broadcast lines are dropped for players lagging more than this.
+const BROADCAST_CAPACITY: usize = 64;
+
+fn load_world(name: Option<&str>) -> Result<World, Box<dyn Error>> {
+    let level = match name {
+        Some(name) => connex_levels::find(name).ok_or_else(|| format!("no level named {name}"))?,
+        None => connex_levels::ALL.first().ok_or("no level")?,
+    };
+    let mut world: World = level
+        .content
+        .parse()
+        .map_err(|e: connex::WorldParseError| e.to_string())?;
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64;
+    world.shuffle_seeded(seed);
+    Ok(world)
+}
+
+async fn handle(
+    stream: TcpStream, addr: SocketAddr, session: Arc<RwLock<Session>>, tx: broadcast::Sender<String>,
+) -> std::io::Result<()> {
+    let mut rx = tx.subscribe();
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let (id, state) = {
+        let mut session = session.write().await;
+        let id = session.join(addr);
+        (id, session.state())
+    };
+    eprintln!("player {id} joined from {addr}");
+    writer.write_all(state.to_line().as_bytes()).await?;
+
+    let result = loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let line = match line {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break Ok(()),
+                    Err(e) => break Err(e),
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let message = match serde_json::from_str::<ClientMessage>(&line) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        let error = ServerMessage::Error { error: e.to_string() };
+                        writer.write_all(error.to_line().as_bytes()).await?;
+                        continue;
+                    }
+                };
+
+                let mut session = session.write().await;
+                if session.apply(id, message.into()) {
+                    // no receiver is not an error, it's impossible here because we are subscribed
+                    let _ = tx.send(session.state().to_line());
+                    if session.solved() {
+                        let duration = session.duration().unwrap_or_default();
+                        eprintln!("solved in {} moves, {:.1}s", session.moves(), duration.as_secs_f64());
+                    }
+                }
+            }
+            line = rx.recv() => match line {
+                Ok(line) => writer.write_all(line.as_bytes()).await?,
+                // missed some states, the next one contains the whole world anyway
+                Err(broadcast::error::RecvError::Lagged(_)) => (),
+                Err(broadcast::error::RecvError::Closed) => break Ok(()),
+            },
+        }
+    };
+
+    let mut session = session.write().await;
+    if let Some(player) = session.leave(id) {
+        eprintln!(
+            "player {} from {} left, {} remaining",
+            player.id,
+            player.addr,
+            session.players().len()
+        );
+    }
+    result
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let address = args().nth(1).unwrap_or_else(|| DEFAULT_ADDRESS.into());
+    let world = load_world(args().nth(2).as_deref())?;
+
+    let listener = TcpListener::bind(&address).await?;
+    eprintln!("listening on {address}");
+
+    let session = Arc::new(RwLock::new(Session::new(world)));
+    let (tx, _) = broadcast::channel(BROADCAST_CAPACITY);
+
+    loop {
+        let (stream, addr) = listener.accept().await?;
+        let session = Arc::clone(&session);
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, addr, session, tx).await {
+                eprintln!("connection {addr} error: {e}");
+            }
+        });
+    }
+}
@@ -0,0 +1,46 @@
+//! Messages between server and clients, one JSON object per line.
+
+use connex::{Command, Pos};
+use serde::{Deserialize, Serialize};
+
+use crate::session::PlayerId;
+
+/// Message sent by clients, e.g. `{"cmd": "rotate", "row": 2, "col": 3}`, positions are 0-based.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "cmd", rename_all = "lowercase")]
+pub enum ClientMessage {
+    /// Turn block at given position clockwise.
+    Rotate { row: usize, col: usize },
+}
+
+impl From<ClientMessage> for Command {
+    fn from(message: ClientMessage) -> Self {
+        match message {
+            ClientMessage::Rotate { row, col } => Command::RotateBlock(Pos::new(row, col)),
+        }
+    }
+}
+
+/// Message sent by server.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ServerMessage {
+    /// World in string format, broadcast to all players when someone joins or moves, e.g.
+    /// `{"state": "v1\n1,2\n><", "solved": false, "last_mover": 1}`.
+    State {
+        state: String,
+        solved: bool,
+        last_mover: Option<PlayerId>,
+    },
+    /// Sent to the player whose message is invalid, e.g. `{"error": "..."}`.
+    Error { error: String },
+}
+
+impl ServerMessage {
+    /// Serialize to a line, with the line break.
+    pub fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap();
+        line.push('\n');
+        line
+    }
+}
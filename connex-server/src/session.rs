@@ -0,0 +1,98 @@
+use std::{net::SocketAddr, time::Instant};
+
+use connex::{Command, Game, World};
+
+use crate::protocol::ServerMessage;
+
+/// Player id, starts from 1 and never reused in a session.
+pub type PlayerId = usize;
+
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub id: PlayerId,
+    pub addr: SocketAddr,
+}
+
+/// A shared world all players play on.
+///
+/// It holds a [`Game`] instead of a bare world, so commands and solved checking work like in single player games.
+#[derive(Debug)]
+pub struct Session {
+    game: Game,
+    players: Vec<Player>,
+    // only counters are kept, a log of every move grows without bound in a long running session
+    moves: usize,
+    first_move_at: Option<Instant>,
+    last_move: Option<(PlayerId, Instant)>,
+    next_id: PlayerId,
+}
+
+impl Session {
+    pub fn new(world: World) -> Self {
+        let mut game = Game::new(world);
+        game.set_record_history(false);
+        Self {
+            game,
+            players: vec![],
+            moves: 0,
+            first_move_at: None,
+            last_move: None,
+            next_id: 1,
+        }
+    }
+
+    pub fn join(&mut self, addr: SocketAddr) -> PlayerId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.players.push(Player { id, addr });
+        id
+    }
+
+    pub fn leave(&mut self, id: PlayerId) -> Option<Player> {
+        let index = self.players.iter().position(|player| player.id == id)?;
+        Some(self.players.remove(index))
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Apply a command of player, moves after solved are ignored. Returns whether the world changed.
+    pub fn apply(&mut self, id: PlayerId, command: Command) -> bool {
+        if self.game.solved() {
+            return false;
+        }
+        let changed = self.game.apply_with_outcome(command).changed;
+        if changed {
+            let now = Instant::now();
+            self.moves += 1;
+            self.first_move_at.get_or_insert(now);
+            self.last_move = Some((id, now));
+        }
+        changed
+    }
+
+    pub fn solved(&self) -> bool {
+        self.game.solved()
+    }
+
+    /// Count of moves made, by all players.
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Time between the first and the last move.
+    pub fn duration(&self) -> Option<std::time::Duration> {
+        let first = self.first_move_at?;
+        let (_, last) = self.last_move?;
+        Some(last.duration_since(first))
+    }
+
+    pub fn state(&self) -> ServerMessage {
+        ServerMessage::State {
+            state: self.game.world().to_string(),
+            solved: self.game.solved(),
+            last_mover: self.last_move.map(|(id, _)| id),
+        }
+    }
+}
@@ -1,13 +1,39 @@
 use std::num::NonZeroUsize;
 
-use connex::World;
-use crossterm::event::KeyCode;
+use connex::{Block, Command, World};
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use tui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block as TuiBlock, Borders, Paragraph},
+};
 
 use crate::{app::App, widget::Game as GameWidget};
 
+/// Block types can be chosen in palette, placed ones can be rotated as usual.
+const PALETTE: [Block; 6] = [
+    Block::Empty,
+    Block::Endpoint(connex::Direction::Up),
+    Block::Through(connex::Direction::Up),
+    Block::Turn(connex::Direction::Up),
+    Block::Fork(connex::Direction::Up),
+    Block::Cross,
+];
+
 #[derive(Debug, Clone)]
 pub struct Editor {
     game_widget: GameWidget,
+    // problems of the puzzle, shown when quit is requested, quit again to confirm
+    quit_warning: Option<String>,
+    // index of chosen block in palette, Tab cycles it and Enter places it, so block chars needn't be remembered
+    palette: usize,
+    // terminal size in cells, as (width, height)
+    terminal_size: (u16, u16),
+    // `:` is pressed in edit mode, the next key chooses a whole world transform
+    transforming: bool,
+    // result of last transform, shown until next key
+    notice: Option<String>,
 }
 
 impl Editor {
@@ -15,30 +41,360 @@ impl Editor {
         let mut game_widget = GameWidget::default();
         game_widget.reset(World::empty(height, width));
         game_widget.set_edit(true);
-        Self { game_widget }
+        Self {
+            game_widget,
+            quit_warning: None,
+            palette: 0,
+            terminal_size: (u16::MAX, u16::MAX),
+            transforming: false,
+            notice: None,
+        }
+    }
+
+    /// Warn when terminal can't show each block in at least 3x2 cells, pipes become unreadable below that.
+    fn size_warning(&self) -> Option<String> {
+        let (height, width) = self.game_widget.world().size();
+        let need_width = width.get().saturating_mul(3);
+        // palette line and warning box take 4 rows
+        let need_height = height.get().saturating_mul(2).saturating_add(4);
+        let (term_width, term_height) = self.terminal_size;
+
+        if usize::from(term_width) >= need_width && usize::from(term_height) >= need_height {
+            return None;
+        }
+        Some(format!(
+            "Terminal is too small for a {height}x{width} level, enlarge it to at least {need_width}x{need_height}."
+        ))
+    }
+
+    /// Handle palette keys in edit mode, returns `true` if the key is consumed.
+    fn on_palette_key(&mut self, code: KeyCode) -> bool {
+        if !self.game_widget.is_edit() || self.game_widget.is_selecting() || self.game_widget.is_flood_filling() {
+            return false;
+        }
+
+        match code {
+            KeyCode::Tab => self.palette = (self.palette + 1) % PALETTE.len(),
+            KeyCode::BackTab => self.palette = (self.palette + PALETTE.len() - 1) % PALETTE.len(),
+            KeyCode::Enter => {
+                self.game_widget
+                    .apply(Command::ReplaceCursorBlock(PALETTE[self.palette]));
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Handle `:` and the transform key after it in edit mode, returns `true` if the key is consumed.
+    fn on_transform_key(&mut self, code: KeyCode) -> bool {
+        let game_widget = &self.game_widget;
+        if !game_widget.is_edit() || game_widget.is_selecting() || game_widget.is_flood_filling() {
+            return false;
+        }
+
+        if !self.transforming {
+            if code != KeyCode::Char(':') {
+                return false;
+            }
+            self.transforming = true;
+            self.notice = Some("Transform: r/R rotate clockwise/counterclockwise, h/v flip, t transpose".into());
+            return true;
+        }
+
+        self.transforming = false;
+        self.notice = self.game_widget.on_transform_key(code).map(|done| {
+            let (height, width) = self.game_widget.world().size();
+            format!("{done}, level is {height}x{width} now")
+        });
+
+        true
+    }
+
+    fn palette_legend(&self) -> Spans<'static> {
+        let mut spans = vec![Span::raw("Tab/Enter: ")];
+        for (i, block) in PALETTE.iter().enumerate() {
+            let style = if i == self.palette {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(format!(" {} ", block.box_char()), style));
+        }
+        spans.push(Span::raw(format!(" {}", PALETTE[self.palette].label())));
+        Spans::from(spans)
     }
 }
 
 impl App for Editor {
-    type Output = String;
+    type Output = World;
 
     fn on_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        // Esc cancels selection, flood fill or transform first
+        let cancel = key.code == KeyCode::Esc
+            && (self.game_widget.is_selecting() || self.game_widget.is_flood_filling() || self.transforming);
+        if !cancel && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+            if self.quit_warning.is_some() {
+                return false;
+            }
+
+            if self.game_widget.world().is_empty() {
+                self.quit_warning = Some("Canvas is empty. Press q again to quit anyway.".into());
+                return true;
+            }
+
+            let errors = match self.game_widget.world().validate_puzzle() {
+                Ok(()) => return false,
+                Err(errors) => errors,
+            };
+            let errors: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+            self.quit_warning = Some(format!(
+                "Not a valid puzzle: {}. Press q again to quit anyway.",
+                errors.join(", ")
+            ));
+            return true;
+        }
+
+        self.quit_warning = None;
+        self.notice = None;
+        if self.on_transform_key(key.code) {
+            return true;
+        }
+        if self.on_palette_key(key.code) {
+            return true;
+        }
         self.game_widget.on_key(key);
 
         if let KeyCode::Char('p') = key.code {
             self.game_widget.set_edit(!self.game_widget.is_edit());
         }
 
-        !matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+        true
     }
 
     fn on_tick(&mut self) {}
 
+    fn on_resize(&mut self, width: u16, height: u16) {
+        self.terminal_size = (width, height);
+    }
+
+    /// In edit mode, pressing or dragging with left button places palette block on every block it passes.
+    fn on_mouse(&mut self, event: MouseEvent) -> bool {
+        if !self.game_widget.is_edit() {
+            self.game_widget.on_mouse(event);
+            return true;
+        }
+
+        let place = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left) => true,
+            MouseEventKind::Down(_) => false,
+            _ => return true,
+        };
+        if let Some(pos) = self.game_widget.block_at(event.column, event.row) {
+            self.quit_warning = None;
+            self.game_widget.move_cursor_to(pos);
+            if place {
+                self.game_widget
+                    .apply(Command::ReplaceCursorBlock(PALETTE[self.palette]));
+            }
+        }
+
+        true
+    }
+
+    fn has_mouse_handler() -> bool {
+        true
+    }
+
     fn draw<B: tui::backend::Backend>(&self, f: &mut tui::Frame<B>) {
-        f.render_widget(&self.game_widget, f.size())
+        let warning = self.quit_warning.clone().or_else(|| self.size_warning());
+        let message = match (warning, &self.notice) {
+            (Some(warning), _) => Some((warning, Color::Yellow)),
+            (None, Some(notice)) => Some((notice.clone(), Color::Green)),
+            (None, None) => None,
+        };
+        let palette_height = if self.game_widget.is_edit() { 1 } else { 0 };
+        let warning_height = if message.is_some() { 3 } else { 0 };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(0),
+                    Constraint::Length(palette_height),
+                    Constraint::Length(warning_height),
+                ]
+                .as_ref(),
+            )
+            .split(f.size());
+
+        f.render_widget(&self.game_widget, chunks[0]);
+
+        if self.game_widget.is_edit() {
+            let palette_widget = Paragraph::new(self.palette_legend()).alignment(Alignment::Center);
+            f.render_widget(palette_widget, chunks[1]);
+        }
+
+        if let Some((message, color)) = &message {
+            let warning_widget = Paragraph::new(message.as_str())
+                .style(Style::default().fg(*color))
+                .alignment(Alignment::Center)
+                .block(TuiBlock::default().borders(Borders::ALL));
+            f.render_widget(warning_widget, chunks[2]);
+        }
+    }
+
+    fn describe(&self) -> String {
+        let mode = if self.game_widget.is_edit() {
+            format!("edit mode, palette block is {}", PALETTE[self.palette].label())
+        } else {
+            "play mode".to_string()
+        };
+        let description = format!("Editor in {mode}. {}", self.game_widget.describe());
+        match self.quit_warning.as_ref().or(self.notice.as_ref()) {
+            Some(message) => format!("{message}\n{description}"),
+            None => description,
+        }
     }
 
     fn output(self) -> Self::Output {
-        format!("{}", self.game_widget.into_inner().into_inner())
+        self.game_widget.into_inner().into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn editor() -> Editor {
+        let three = NonZeroUsize::new(3).unwrap();
+        Editor::new(three, three)
+    }
+
+    fn press(editor: &mut Editor, keys: &str) {
+        for c in keys.chars() {
+            assert!(editor.on_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)));
+        }
+    }
+
+    fn shift(editor: &mut Editor, code: KeyCode) {
+        assert!(editor.on_key(KeyEvent::new(code, KeyModifiers::SHIFT)));
+    }
+
+    fn world(editor: &Editor) -> &World {
+        editor.game_widget.world()
+    }
+
+    #[test]
+    fn first_key_sets_anchor_and_movement_extends() {
+        let mut editor = editor();
+        press(&mut editor, "jl");
+        assert_eq!(editor.game_widget.selection(), None);
+
+        press(&mut editor, "V");
+        assert!(editor.game_widget.is_selecting());
+        assert_eq!(editor.game_widget.selection(), Some((1, 1, 1, 1)));
+
+        // the anchor stays, the rectangle follows cursor to any side of it
+        press(&mut editor, "jl");
+        assert_eq!(editor.game_widget.selection(), Some((1, 1, 2, 2)));
+        press(&mut editor, "kkhh");
+        assert_eq!(editor.game_widget.selection(), Some((0, 0, 2, 2)));
+        assert!(world(&editor).is_empty());
+    }
+
+    #[test]
+    fn shift_movement_starts_and_extends_selection() {
+        let mut editor = editor();
+        shift(&mut editor, KeyCode::Right);
+        assert_eq!(editor.game_widget.selection(), Some((0, 0, 1, 2)));
+        shift(&mut editor, KeyCode::Down);
+        shift(&mut editor, KeyCode::Down);
+        assert_eq!(editor.game_widget.selection(), Some((0, 0, 3, 2)));
+        shift(&mut editor, KeyCode::Up);
+        assert_eq!(editor.game_widget.selection(), Some((0, 0, 2, 2)));
+
+        // plain movement keeps extending it
+        press(&mut editor, "l");
+        assert_eq!(editor.game_widget.selection(), Some((0, 0, 2, 3)));
+    }
+
+    #[test]
+    fn esc_clears_selection_without_quitting() {
+        let mut editor = editor();
+        press(&mut editor, "Vjl");
+        assert!(editor.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+        assert!(!editor.game_widget.is_selecting());
+        assert_eq!(editor.game_widget.selection(), None);
+        assert_eq!(editor.quit_warning, None);
+
+        // selection keys are block chars again
+        press(&mut editor, "5");
+        assert_eq!(world(&editor)[(1, 1)], Block::Cross);
+        assert_eq!(
+            world(&editor)
+                .iter()
+                .filter(|(_, _, block)| **block == Block::Cross)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn bulk_operations_only_touch_selected_cells() {
+        let mut editor = editor();
+        press(&mut editor, "Vjl5f");
+        assert!(!editor.game_widget.is_selecting());
+        let crosses = |editor: &Editor| {
+            world(editor)
+                .iter()
+                .filter(|(_, _, block)| **block == Block::Cross)
+                .map(|(row, col, _)| (row, col))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(crosses(&editor), [(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+        // cursor is at (1, 1), clear the bottom right 2x2
+        shift(&mut editor, KeyCode::Right);
+        shift(&mut editor, KeyCode::Down);
+        press(&mut editor, "x");
+        assert!(!editor.game_widget.is_selecting());
+        assert_eq!(crosses(&editor), [(0, 0), (0, 1), (1, 0)]);
+
+        // yank the top row and paste it to the bottom one
+        press(&mut editor, "kkhhVllyhhjjP");
+        assert_eq!(crosses(&editor), [(0, 0), (0, 1), (1, 0), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn palette_cycles_through_every_block_and_wraps() {
+        let mut editor = editor();
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        for round in 0..2 {
+            for (i, block) in PALETTE.iter().enumerate() {
+                assert_eq!(editor.palette, i, "round {round}");
+                assert!(editor.on_key(key(KeyCode::Enter)));
+                assert_eq!(world(&editor)[(0, 0)], *block);
+                assert!(editor.on_key(key(KeyCode::Tab)));
+            }
+        }
+        assert_eq!(editor.palette, 0);
+
+        // backwards from the first one is the last one
+        assert!(editor.on_key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert_eq!(editor.palette, PALETTE.len() - 1);
+        assert!(editor.on_key(key(KeyCode::Enter)));
+        assert_eq!(world(&editor)[(0, 0)], Block::Cross);
+
+        // palette keys don't work while selecting
+        press(&mut editor, "V");
+        assert!(editor.on_key(key(KeyCode::Tab)));
+        assert_eq!(editor.palette, PALETTE.len() - 1);
     }
 }
@@ -1,6 +1,11 @@
-use std::borrow::Cow;
+use std::{
+    collections::HashMap,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use once_cell::sync::Lazy;
 use rand::thread_rng;
 use tui::{
@@ -13,14 +18,27 @@ use tui::{
 };
 use tui_markup::generator::TuiTextGenerator;
 
-use connex::World;
-use connex_levels::LEVELS;
+use connex::{Pos, SolveResult, World};
 
 static HELP_TEXT: Lazy<Text<'static>> = Lazy::new(compile_help_text);
 
-use crate::{app::App, widget::Game as GameWidget};
+use crate::{
+    app::App,
+    daily::{self, Date},
+    levels,
+    progress::{self, Progress},
+    text,
+    widget::Game as GameWidget,
+};
+
+/// Level names longer than this are truncated in level list, so the list doesn't squeeze the game.
+const LEVEL_NAME_MAX_WIDTH: usize = 16;
 
 fn compile_help_text() -> Text<'static> {
+    compile_markup(include_str!("game_help.txt"))
+}
+
+fn compile_markup(source: &'static str) -> Text<'static> {
     let gen = TuiTextGenerator::new(|tag: &str| {
         Some(match tag {
             "h1" => Style::default()
@@ -36,45 +54,187 @@ fn compile_help_text() -> Text<'static> {
             _ => return None,
         })
     });
-    tui_markup::compile_with(include_str!("game_help.txt"), gen).unwrap()
+    // a stray `<` in help file should not crash the game, show the error and the raw text instead
+    tui_markup::compile_with(source, gen).unwrap_or_else(|e| {
+        let mut text = Text::styled(format!("Invalid help text: {e}\n\n"), Style::default().fg(Color::Red));
+        text.extend(Text::raw(source));
+        text
+    })
 }
 
+#[derive(Clone, Copy)]
 enum Page {
     Gaming,
+    DailyChallenge,
     Help,
 }
 
+/// Key of computed solutions, a level index or a daily challenge date code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SolutionKey {
+    Level(usize),
+    Daily(u64),
+}
+
+/// Solver gives up after this long by default, see [`Game::new`].
+pub const DEFAULT_SOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct Game {
     page: Page,
+    // page to go back when leaving help page
+    last_page: Page,
     level: Option<usize>,
     game_widget: GameWidget,
+    daily_date: Date,
+    daily_widget: GameWidget,
+    progress: Progress,
+    started: Instant,
+    // show solution under the board
+    peek: bool,
+    // solutions computed for peeking
+    solutions: HashMap<SolutionKey, SolveResult>,
+    // solver running in background for peeking, its result is received in `on_tick`
+    solving: Option<(SolutionKey, Receiver<SolveResult>)>,
+    solve_timeout: Duration,
+    // levels generation of current level, restart it when levels are reloaded
+    levels_generation: u64,
 }
 
 impl Default for Game {
     fn default() -> Self {
+        Self::new(DEFAULT_SOLVE_TIMEOUT)
+    }
+}
+
+impl Game {
+    /// Create the game app, solver for peeking gives up after `solve_timeout`.
+    pub fn new(solve_timeout: Duration) -> Self {
         let mut state = Game {
             page: Page::Gaming,
+            last_page: Page::Gaming,
             level: None,
             game_widget: GameWidget::default(),
+            daily_date: Date::today(),
+            daily_widget: GameWidget::default(),
+            progress: progress::load(),
+            started: Instant::now(),
+            peek: false,
+            solutions: HashMap::new(),
+            solving: None,
+            solve_timeout,
+            levels_generation: levels::generation(),
         };
 
-        if !LEVELS.is_empty() {
+        if levels::len() > 0 {
             state.start_level(0);
         }
 
         state
     }
-}
 
-impl Game {
     fn start_level(&mut self, level: usize) {
-        assert!(level < LEVELS.len());
+        // levels may be reloaded and become less in dev mode
+        let Some(content) = levels::content(level) else {
+            return;
+        };
 
-        let mut world: World = connex_levels::LEVELS[level].parse().unwrap();
+        let mut world: World = content.parse().unwrap();
         world.shuffle(thread_rng());
 
         self.game_widget.reset(world);
         self.level.replace(level);
+        self.page = Page::Gaming;
+        self.started = Instant::now();
+        self.update_peek();
+    }
+
+    fn start_daily(&mut self) {
+        self.daily_date = Date::today();
+        self.daily_widget.reset(daily::level(&self.daily_date));
+        self.page = Page::DailyChallenge;
+        self.started = Instant::now();
+        self.update_peek();
+    }
+
+    fn solution_key(&self) -> Option<SolutionKey> {
+        match self.page {
+            Page::DailyChallenge => Some(SolutionKey::Daily(self.daily_date.code())),
+            _ => self.level.map(SolutionKey::Level),
+        }
+    }
+
+    fn widget_mut(&mut self, key: SolutionKey) -> &mut GameWidget {
+        match key {
+            SolutionKey::Level(_) => &mut self.game_widget,
+            SolutionKey::Daily(_) => &mut self.daily_widget,
+        }
+    }
+
+    /// Set or clear the solution overlay of current page, solution is computed in background when first needed.
+    fn update_peek(&mut self) {
+        if !self.peek {
+            self.game_widget.set_overlay(None);
+            self.daily_widget.set_overlay(None);
+            return;
+        }
+
+        let Some(key) = self.solution_key() else {
+            return;
+        };
+        let overlay = match self.solutions.get(&key) {
+            Some(SolveResult::Solved(solution)) => Some(solution.clone()),
+            Some(_) => None,
+            None => {
+                self.start_solving(key);
+                None
+            }
+        };
+        self.widget_mut(key).set_overlay(overlay);
+    }
+
+    /// Solve world of `key` in another thread, so large worlds don't block the event loop.
+    fn start_solving(&mut self, key: SolutionKey) {
+        if self.solving.as_ref().is_some_and(|(solving, _)| *solving == key) {
+            return;
+        }
+
+        let world = self.widget_mut(key).world().clone();
+        let timeout = self.solve_timeout;
+        let (tx, rx) = mpsc::channel();
+        // a replaced solving thread fails to send and stops silently
+        thread::spawn(move || tx.send(world.solve_with_timeout(timeout)));
+        self.solving = Some((key, rx));
+    }
+
+    fn receive_solution(&mut self) {
+        let received = self.solving.as_ref().map(|(key, rx)| (*key, rx.try_recv()));
+        match received {
+            Some((key, Ok(result))) => {
+                self.solutions.insert(key, result);
+                self.solving = None;
+                self.update_peek();
+            }
+            Some((_, Err(TryRecvError::Disconnected))) => self.solving = None,
+            _ => (),
+        }
+    }
+
+    /// Describe solver state of current page when peeking and there is no solution to show.
+    fn peek_status(&self) -> Option<&'static str> {
+        if !self.peek {
+            return None;
+        }
+        let key = self.solution_key()?;
+        match self.solutions.get(&key) {
+            Some(SolveResult::Solved(_)) => None,
+            Some(SolveResult::Unsolvable) => Some("No solution"),
+            Some(SolveResult::TimedOut) => Some("Solver timed out"),
+            None => Some("Solving..."),
+        }
+    }
+
+    fn streak(&self) -> usize {
+        daily::streak(&Date::today(), |date| self.progress.daily.contains_key(&date))
     }
 }
 
@@ -82,10 +242,17 @@ impl Game {
     fn on_key_common(&mut self, key: KeyEvent) -> bool {
         match key.code {
             KeyCode::Char('?') => match self.page {
-                Page::Gaming => self.page = Page::Help,
-                Page::Help => self.page = Page::Gaming,
+                Page::Help => self.page = self.last_page,
+                page => {
+                    self.last_page = page;
+                    self.page = Page::Help;
+                }
             },
             KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('p') if !matches!(self.page, Page::Help) => {
+                self.peek = !self.peek;
+                self.update_peek();
+            }
             _ => (),
         };
 
@@ -94,8 +261,13 @@ impl Game {
 
     fn on_key_gaming(&mut self, key: KeyEvent) -> bool {
         if let Some(level) = self.level {
-            if !self.game_widget.solved() {
-                self.game_widget.on_key(key);
+            // solved world ignores keys, and progress is recorded exactly once when it becomes solved
+            if !self.game_widget.solved() && self.game_widget.on_key(key) {
+                if let Some(hash) = levels::hash(level) {
+                    self.progress.complete(hash, self.started.elapsed());
+                }
+                // nowhere to report error in the middle of the game, progress will be saved again at exit
+                let _ = progress::save(&self.progress);
             }
 
             if let KeyCode::Char('r') = key.code {
@@ -104,12 +276,30 @@ impl Game {
         }
 
         match key.code {
-            KeyCode::Char(']') if !LEVELS.is_empty() => {
-                self.start_level((self.level.map(|x| x + 1).unwrap_or_default()) % LEVELS.len())
+            KeyCode::Char(']') if levels::len() > 0 => {
+                self.start_level((self.level.map(|x| x + 1).unwrap_or_default()) % levels::len())
             }
-            KeyCode::Char('[') if self.level.is_some() => {
-                self.start_level((self.level.map(|x| x + LEVELS.len() - 1)).unwrap_or_default() % LEVELS.len())
+            KeyCode::Char('[') if self.level.is_some() && levels::len() > 0 => {
+                self.start_level((self.level.map(|x| x + levels::len() - 1)).unwrap_or_default() % levels::len())
             }
+            KeyCode::Char('t') => self.start_daily(),
+            _ => (),
+        }
+
+        true
+    }
+
+    fn on_key_daily(&mut self, key: KeyEvent) -> bool {
+        if !self.daily_widget.solved() && self.daily_widget.on_key(key) {
+            self.progress
+                .complete_daily(self.daily_date.code(), self.started.elapsed());
+            let _ = progress::save(&self.progress);
+        }
+
+        match key.code {
+            KeyCode::Char('r') => self.start_daily(),
+            KeyCode::Char(']') if levels::len() > 0 => self.start_level(0),
+            KeyCode::Char('[') if levels::len() > 0 => self.start_level(levels::len() - 1),
             _ => (),
         }
 
@@ -120,7 +310,30 @@ impl Game {
         true
     }
 
+    fn on_mouse_gaming(&mut self, event: MouseEvent) {
+        let Some(level) = self.level else {
+            return;
+        };
+        if !self.game_widget.solved() && self.game_widget.on_mouse(event) {
+            if let Some(hash) = levels::hash(level) {
+                self.progress.complete(hash, self.started.elapsed());
+            }
+            let _ = progress::save(&self.progress);
+        }
+    }
+
+    fn on_mouse_daily(&mut self, event: MouseEvent) {
+        if !self.daily_widget.solved() && self.daily_widget.on_mouse(event) {
+            self.progress
+                .complete_daily(self.daily_date.code(), self.started.elapsed());
+            let _ = progress::save(&self.progress);
+        }
+    }
+
     fn draw_gaming<B: Backend>(&self, f: &mut Frame<B>) {
+        let daily = matches!(self.page, Page::DailyChallenge);
+        let widget = if daily { &self.daily_widget } else { &self.game_widget };
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)].as_ref())
@@ -128,31 +341,69 @@ impl Game {
 
         let title_rect = chunks[0];
         let mut title_color = Style::default();
-        if self.game_widget.solved() {
+        if widget.solved() {
             title_color = title_color.fg(Color::Green);
         }
-        let title = if let Some(level) = self.level {
-            Cow::Owned(format!("Connex TUI - Level {level:03}"))
+        let mut title = if daily {
+            let Date { year, month, day, .. } = self.daily_date;
+            format!("Connex TUI - Daily Challenge {year}-{month:02}-{day:02}")
+        } else if let Some(level) = self.level {
+            let name = levels::name(level).unwrap_or_else(|| format!("{level:03}"));
+            match levels::size(level) {
+                Some((height, width)) => format!("Connex TUI - Level {name} ({height}×{width})"),
+                None => format!("Connex TUI - Level {name}"),
+            }
         } else {
-            Cow::Borrowed("Connex TUI")
+            "Connex TUI".into()
         };
+        let streak = self.streak();
+        if streak > 0 {
+            title += &format!("  🔥 {streak} days");
+        }
         let title_widget = Paragraph::new(Span::styled(title, title_color))
             .alignment(Alignment::Center)
             .block(TuiBlock::default().borders(Borders::ALL));
         f.render_widget(title_widget, title_rect);
 
+        let daily_item = if self.progress.daily.contains_key(&Date::today().code()) {
+            " Day ✓".to_string()
+        } else {
+            " Day".to_string()
+        };
+        let level_items: Vec<_> = std::iter::once(daily_item)
+            .chain((0..levels::len()).map(|n| {
+                let name = levels::name(n).unwrap_or_else(|| format!("{n:03}"));
+                let name = text::truncate(&name, LEVEL_NAME_MAX_WIDTH);
+                let badge = levels::size(n)
+                    .map(|(height, width)| format!(" {height}×{width}"))
+                    .unwrap_or_default();
+                let mark = if levels::hash(n).is_some_and(|hash| self.progress.completed.contains(&hash)) {
+                    " ✓"
+                } else {
+                    ""
+                };
+                format!(" {name}{badge}{mark}")
+            }))
+            .collect();
+        // grow to fit the longest item plus borders, names are truncated so it has an upper bound
+        let level_list_width = level_items.iter().map(|item| text::width(item)).max().unwrap_or(0) + 2;
+        let level_list_width = level_list_width.max("Levels".len() + 2) as u16;
+
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(8), Constraint::Min(0)])
+            .constraints([Constraint::Length(level_list_width), Constraint::Min(0)])
             .split(chunks[1]);
 
         let level_rect = main_chunks[0];
-        let mut level_list: Vec<_> = (0..LEVELS.len())
-            .map(|n| format!(" {n:03}"))
-            .map(ListItem::new)
-            .collect();
-        if let Some(level) = self.level {
-            let selected = level_list.get_mut(level).unwrap();
+        let mut level_list: Vec<_> = level_items.into_iter().map(ListItem::new).collect();
+        // first item is daily challenge
+        let selected = if daily {
+            Some(0)
+        } else {
+            self.level.map(|level| level + 1)
+        };
+        if let Some(selected) = selected {
+            let selected = level_list.get_mut(selected).unwrap();
             *selected = selected.clone().style(Style::default().fg(Color::Green));
         }
         let level_widget = List::new(level_list)
@@ -161,12 +412,24 @@ impl Game {
         f.render_widget(level_widget, level_rect);
 
         let game_widget_rect = main_chunks[1];
-        if self.level.is_some() && game_widget_rect.area() > 0 {
-            f.render_widget(&self.game_widget, game_widget_rect);
+        if (daily || self.level.is_some()) && game_widget_rect.area() > 0 {
+            f.render_widget(widget, game_widget_rect);
         }
 
         let status_bar_rect = chunks[2];
-        let status_bar_widget = Paragraph::new("Press ? to see help page")
+        let Pos { row, col } = widget.cursor();
+        let mut status = match widget.world().get(row, col) {
+            Some(block) if daily || self.level.is_some() => format!(
+                "{} | {:.0}% fit | Press ? to see help page",
+                block.label(),
+                widget.world().progress() * 100.0
+            ),
+            _ => "Press ? to see help page".into(),
+        };
+        if let Some(peek_status) = self.peek_status() {
+            status = format!("{peek_status} | {status}");
+        }
+        let status_bar_widget = Paragraph::new(status)
             .alignment(Alignment::Center)
             .block(TuiBlock::default().borders(Borders::ALL));
         f.render_widget(status_bar_widget, status_bar_rect);
@@ -191,18 +454,155 @@ impl App for Game {
 
         match self.page {
             Page::Gaming => self.on_key_gaming(key),
+            Page::DailyChallenge => self.on_key_daily(key),
             Page::Help => self.on_key_help(key),
         }
     }
 
-    fn on_tick(&mut self) {}
+    fn on_mouse(&mut self, event: MouseEvent) -> bool {
+        match self.page {
+            Page::Gaming => self.on_mouse_gaming(event),
+            Page::DailyChallenge => self.on_mouse_daily(event),
+            Page::Help => (),
+        }
+        true
+    }
+
+    fn has_mouse_handler() -> bool {
+        true
+    }
+
+    fn on_tick(&mut self) {
+        self.receive_solution();
+
+        let generation = levels::generation();
+        if generation != self.levels_generation {
+            self.levels_generation = generation;
+            self.solutions.retain(|key, _| matches!(key, SolutionKey::Daily(_)));
+            if matches!(self.solving, Some((SolutionKey::Level(_), _))) {
+                self.solving = None;
+            }
+            if let (Page::Gaming, Some(level)) = (self.page, self.level) {
+                self.start_level(level.min(levels::len().saturating_sub(1)));
+            }
+        }
+    }
 
     fn draw<B: Backend>(&self, f: &mut Frame<B>) {
         match self.page {
-            Page::Gaming => self.draw_gaming(f),
+            Page::Gaming | Page::DailyChallenge => self.draw_gaming(f),
             Page::Help => self.draw_help(f),
         }
     }
 
-    fn output(self) -> Self::Output {}
+    fn describe(&self) -> String {
+        if let Page::DailyChallenge = self.page {
+            return format!("Daily challenge. {}", self.daily_widget.describe());
+        }
+
+        match self.level {
+            Some(level) => format!("Level {level:03}. {}", self.game_widget.describe()),
+            None => "No level".into(),
+        }
+    }
+
+    fn output(self) -> Self::Output {
+        let _ = progress::save(&self.progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::testing::render;
+
+    fn plain(text: &Text<'_>) -> String {
+        text.lines
+            .iter()
+            .map(|line| line.0.iter().map(|span| span.content.as_ref()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn bundled_help_text_compiles() {
+        let text = compile_help_text();
+        assert!(!plain(&text).starts_with("Invalid help text"));
+        assert!(!text.lines.is_empty());
+    }
+
+    #[test]
+    fn malformed_markup_does_not_panic() {
+        for source in [
+            "<h1 unclosed",
+            "a stray < here",
+            "<kbd <action nested>",
+            "closing > only>",
+            "<>",
+            "<",
+        ] {
+            let text = compile_markup(source);
+            let plain = plain(&text);
+            assert!(plain.starts_with("Invalid help text: "), "{source:?}: {plain}");
+            // raw text is still shown after the error
+            assert!(plain.ends_with(source), "{source:?}: {plain}");
+        }
+
+        assert_eq!(plain(&compile_markup("<kbd q> to quit")), "q to quit");
+    }
+
+    fn dimmed_cells(widget: &GameWidget) -> usize {
+        let buffer = render(widget, 40, 20);
+        buffer
+            .content()
+            .iter()
+            .filter(|cell| cell.symbol.trim() != "" && cell.fg == Color::DarkGray)
+            .count()
+    }
+
+    fn toggle_peek_and_wait(game: &mut Game) {
+        assert!(game.on_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE)));
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while game.peek_status() == Some("Solving...") {
+            assert!(Instant::now() < deadline, "solver doesn't finish");
+            thread::sleep(Duration::from_millis(10));
+            game.on_tick();
+        }
+    }
+
+    #[test]
+    fn peek_does_not_change_world_or_solved_state() {
+        let mut game = Game::new(Duration::from_secs(5));
+        assert!(game.level.is_some());
+        let world = game.game_widget.world().clone();
+        let solved = game.game_widget.solved();
+
+        toggle_peek_and_wait(&mut game);
+        assert!(game.peek);
+        assert_eq!(game.peek_status(), None);
+        assert!(dimmed_cells(&game.game_widget) > 0);
+        assert_eq!(game.game_widget.world(), &world);
+        assert_eq!(game.game_widget.solved(), solved);
+
+        toggle_peek_and_wait(&mut game);
+        assert!(!game.peek);
+        assert_eq!(dimmed_cells(&game.game_widget), 0);
+        assert_eq!(game.game_widget.world(), &world);
+        assert_eq!(game.game_widget.solved(), solved);
+
+        // a solved board stays solved with its own orientation, under the cached solution
+        let Some(SolveResult::Solved(solution)) = game.solutions.get(&SolutionKey::Level(0)).cloned() else {
+            panic!("solution is not cached");
+        };
+        game.game_widget.reset(solution.clone());
+        assert!(game.game_widget.solved());
+        for peek in [true, false] {
+            toggle_peek_and_wait(&mut game);
+            assert_eq!(game.peek, peek);
+            assert!(game.game_widget.solved());
+            assert_eq!(game.game_widget.world(), &solution);
+        }
+    }
 }
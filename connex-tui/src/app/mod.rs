@@ -1,16 +1,25 @@
 mod editor;
 mod game;
+mod multiplayer;
+mod replay;
+mod tutorial;
 
 use std::{
     error::Error,
     time::{Duration, Instant},
 };
 
-use crossterm::event::{Event, KeyEvent};
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture, Event, KeyEvent, MouseEvent},
+    ExecutableCommand,
+};
 use tui::{backend::Backend, Frame, Terminal};
 
 pub use editor::Editor;
-pub use game::Game;
+pub use game::{Game, DEFAULT_SOLVE_TIMEOUT};
+pub use multiplayer::Multiplayer;
+pub use replay::Replay;
+pub use tutorial::Tutorial;
 
 pub trait App {
     type Output;
@@ -18,25 +27,62 @@ pub trait App {
     fn on_key(&mut self, key: KeyEvent) -> bool;
     fn on_tick(&mut self);
     fn draw<B: Backend>(&self, f: &mut Frame<B>);
+    fn describe(&self) -> String;
     fn output(self) -> Self::Output;
 
-    fn run<B: Backend>(
+    /// Called with terminal size in cells when run starts and every time terminal is resized.
+    fn on_resize(&mut self, _width: u16, _height: u16) {}
+
+    /// Handle a mouse event, return `false` to quit like [`App::on_key`].
+    fn on_mouse(&mut self, _event: MouseEvent) -> bool {
+        true
+    }
+
+    /// Whether [`App::on_mouse`] is implemented, mouse is only captured then, because capturing it disables text
+    /// selection of terminal.
+    fn has_mouse_handler() -> bool {
+        false
+    }
+
+    fn run<B: Backend>(self, terminal: &mut Terminal<B>, tick_rate: Duration) -> Result<Self::Output, Box<dyn Error>>
+    where
+        Self: Sized,
+    {
+        self.run_with_events(terminal, tick_rate, |_, timeout| {
+            Ok(if crossterm::event::poll(timeout)? {
+                Some(crossterm::event::read()?)
+            } else {
+                None
+            })
+        })
+    }
+
+    /// Like [`App::run`], but events come from `next_event`, which waits at most the given timeout for one.
+    fn run_with_events<B: Backend>(
         mut self, terminal: &mut Terminal<B>, tick_rate: Duration,
+        mut next_event: impl FnMut(&mut Terminal<B>, Duration) -> std::io::Result<Option<Event>>,
     ) -> Result<Self::Output, Box<dyn Error>>
     where
         Self: Sized,
     {
+        let size = terminal.size()?;
+        self.on_resize(size.width, size.height);
+
+        let mouse = Self::has_mouse_handler();
+        if mouse {
+            std::io::stdout().execute(EnableMouseCapture)?;
+        }
+
         let mut last_tick = Instant::now();
         loop {
             terminal.draw(|f| self.draw(f))?;
 
             let timeout = tick_rate.checked_sub(last_tick.elapsed()).unwrap_or(Duration::ZERO);
-            if crossterm::event::poll(timeout)? {
-                if let Event::Key(key) = crossterm::event::read()? {
-                    if !self.on_key(key) {
-                        break;
-                    }
-                }
+            match next_event(terminal, timeout)? {
+                Some(Event::Key(key)) if !self.on_key(key) => break,
+                Some(Event::Mouse(event)) if !self.on_mouse(event) => break,
+                Some(Event::Resize(width, height)) => self.on_resize(width, height),
+                _ => (),
             }
 
             if last_tick.elapsed() >= tick_rate {
@@ -45,6 +91,105 @@ pub trait App {
             }
         }
 
+        if mouse {
+            std::io::stdout().execute(DisableMouseCapture)?;
+        }
+
         Ok(self.output())
     }
+
+    /// Run without drawing, print [`App::describe`] to stderr at start and after each key press.
+    fn run_accessible(mut self) -> Result<Self::Output, Box<dyn Error>>
+    where
+        Self: Sized,
+    {
+        // terminal is in raw mode, so line break need a carriage return
+        let print = |text: String| eprint!("{}\r\n", text.trim_end().replace('\n', "\r\n"));
+
+        print(self.describe());
+        loop {
+            if let Event::Key(key) = crossterm::event::read()? {
+                if !self.on_key(key) {
+                    break;
+                }
+                print(self.describe());
+            }
+        }
+
+        Ok(self.output())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use tui::{backend::TestBackend, layout::Rect};
+
+    use super::*;
+
+    /// Records sizes it's told and drawn in.
+    #[derive(Default)]
+    struct Recorder {
+        resized: Vec<(u16, u16)>,
+        drawn: std::cell::RefCell<Vec<Rect>>,
+    }
+
+    impl App for Recorder {
+        type Output = Self;
+
+        fn on_key(&mut self, key: KeyEvent) -> bool {
+            key.code != KeyCode::Char('q')
+        }
+
+        fn on_tick(&mut self) {}
+
+        fn draw<B: Backend>(&self, f: &mut Frame<B>) {
+            self.drawn.borrow_mut().push(f.size());
+        }
+
+        fn describe(&self) -> String {
+            String::new()
+        }
+
+        fn output(self) -> Self::Output {
+            self
+        }
+
+        fn on_resize(&mut self, width: u16, height: u16) {
+            self.resized.push((width, height));
+        }
+    }
+
+    #[test]
+    fn resize_event_reaches_app_and_next_draw() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+        let mut events = VecDeque::from([
+            Event::Resize(30, 12),
+            Event::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)),
+            Event::Key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)),
+        ]);
+
+        let recorder = Recorder::default()
+            .run_with_events(&mut terminal, Duration::from_secs(60), |terminal, _| {
+                let event = events.pop_front();
+                // a real terminal is resized before it reports so
+                if let Some(Event::Resize(width, height)) = event {
+                    terminal.backend_mut().resize(width, height);
+                }
+                Ok(event)
+            })
+            .unwrap();
+
+        assert_eq!(recorder.resized, [(20, 10), (30, 12)]);
+        assert_eq!(
+            *recorder.drawn.borrow(),
+            [
+                Rect::new(0, 0, 20, 10),
+                Rect::new(0, 0, 30, 12),
+                Rect::new(0, 0, 30, 12)
+            ]
+        );
+    }
 }
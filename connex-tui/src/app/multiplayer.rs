@@ -0,0 +1,192 @@
+use connex::{generator, Command, Direction, World};
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::thread_rng;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction as LayoutDirection, Layout},
+    style::{Color, Style},
+    text::Span,
+    widgets::{Block as TuiBlock, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{app::App, widget::Game as GameWidget};
+
+const WORLD_SIZE: usize = 6;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    fn index(&self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Two => 1,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::One => "Player 1",
+            Self::Two => "Player 2",
+        }
+    }
+
+    /// Map key to player and command, player 1 uses WASD and Space, player 2 uses arrows and Enter.
+    fn command(key: KeyEvent) -> Option<(Self, Command)> {
+        Some(match key.code {
+            KeyCode::Char('w') => (Self::One, Command::MoveCursor(Direction::Up)),
+            KeyCode::Char('d') => (Self::One, Command::MoveCursor(Direction::Right)),
+            KeyCode::Char('s') => (Self::One, Command::MoveCursor(Direction::Down)),
+            KeyCode::Char('a') => (Self::One, Command::MoveCursor(Direction::Left)),
+            KeyCode::Char(' ') => (Self::One, Command::RotateCursorBlock),
+            KeyCode::Up => (Self::Two, Command::MoveCursor(Direction::Up)),
+            KeyCode::Right => (Self::Two, Command::MoveCursor(Direction::Right)),
+            KeyCode::Down => (Self::Two, Command::MoveCursor(Direction::Down)),
+            KeyCode::Left => (Self::Two, Command::MoveCursor(Direction::Left)),
+            KeyCode::Enter => (Self::Two, Command::RotateCursorBlock),
+            _ => return None,
+        })
+    }
+}
+
+/// Two players solve the same world, each with their own cursor, who makes the world solved wins the round.
+///
+/// Each player has a game of their own to keep the cursor, rotations are applied to both games so they always
+/// show the same world.
+#[derive(Debug, Clone)]
+pub struct Multiplayer {
+    games: [GameWidget; 2],
+    scores: [usize; 2],
+    winner: Option<Player>,
+}
+
+impl Default for Multiplayer {
+    fn default() -> Self {
+        let mut state = Self {
+            games: Default::default(),
+            scores: [0; 2],
+            winner: None,
+        };
+        state.new_round();
+        state
+    }
+}
+
+impl Multiplayer {
+    fn new_round(&mut self) {
+        let size = WORLD_SIZE.try_into().unwrap();
+        let world: World = generator::generate(size, size, &mut thread_rng());
+        for game in &mut self.games {
+            game.reset(world.clone());
+        }
+        self.winner = None;
+    }
+
+    fn apply(&mut self, player: Player, command: Command) {
+        let command = match command {
+            Command::RotateCursorBlock => Command::RotateBlock(self.games[player.index()].cursor()),
+            Command::MoveCursor(_) => {
+                self.games[player.index()].apply(command);
+                return;
+            }
+            _ => command,
+        };
+
+        let mut solved_now = false;
+        for game in &mut self.games {
+            solved_now = game.apply(command.clone());
+        }
+
+        if solved_now {
+            self.winner = Some(player);
+            self.scores[player.index()] += 1;
+        }
+    }
+
+    fn title(&self) -> String {
+        let scores = format!("Player 1: {}  Player 2: {}", self.scores[0], self.scores[1]);
+        match self.winner {
+            Some(winner) => format!("{} wins! {scores}", winner.name()),
+            None => scores,
+        }
+    }
+}
+
+impl App for Multiplayer {
+    type Output = ();
+
+    fn on_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('n') if self.winner.is_some() => self.new_round(),
+            _ => {
+                if self.winner.is_none() {
+                    if let Some((player, command)) = Player::command(key) {
+                        self.apply(player, command);
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn on_tick(&mut self) {}
+
+    fn draw<B: Backend>(&self, f: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)].as_ref())
+            .split(f.size());
+
+        let mut title_color = Style::default();
+        if self.winner.is_some() {
+            title_color = title_color.fg(Color::Green);
+        }
+        let title_widget = Paragraph::new(Span::styled(self.title(), title_color))
+            .alignment(Alignment::Center)
+            .block(TuiBlock::default().borders(Borders::ALL));
+        f.render_widget(title_widget, chunks[0]);
+
+        let panels = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .split(chunks[1]);
+        for (player, game) in [Player::One, Player::Two].iter().zip(&self.games) {
+            let panel = panels[player.index()];
+            let border = TuiBlock::default().borders(Borders::ALL).title(player.name());
+            let inner = border.inner(panel);
+            f.render_widget(border, panel);
+            if inner.area() > 0 {
+                f.render_widget(game, inner);
+            }
+        }
+
+        let help = if self.winner.is_some() {
+            "Press n for next round, q to quit"
+        } else {
+            "Player 1: WASD + Space, Player 2: Arrows + Enter, q to quit"
+        };
+        let status_bar_widget = Paragraph::new(help)
+            .alignment(Alignment::Center)
+            .block(TuiBlock::default().borders(Borders::ALL));
+        f.render_widget(status_bar_widget, chunks[2]);
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{}\n{}: {}\n{}: {}",
+            self.title(),
+            Player::One.name(),
+            self.games[0].describe(),
+            Player::Two.name(),
+            self.games[1].describe()
+        )
+    }
+
+    fn output(self) -> Self::Output {}
+}
@@ -0,0 +1,103 @@
+use connex::{Command, World};
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    widgets::{Block as TuiBlock, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{app::App, widget::Game as GameWidget};
+
+/// Ticks between two commands when playing.
+const STEP_TICKS: usize = 25;
+
+#[derive(Debug, Clone)]
+pub struct Replay {
+    game_widget: GameWidget,
+    commands: Vec<Command>,
+    step: usize,
+    ticks: usize,
+    paused: bool,
+}
+
+impl Replay {
+    pub fn new(world: World, commands: Vec<Command>) -> Self {
+        let mut game_widget = GameWidget::default();
+        game_widget.reset(world);
+        Self {
+            game_widget,
+            commands,
+            step: 0,
+            ticks: 0,
+            paused: false,
+        }
+    }
+
+    fn next(&mut self) {
+        if let Some(command) = self.commands.get(self.step) {
+            self.game_widget.apply(command.clone());
+            self.step += 1;
+        }
+    }
+
+    fn status(&self) -> String {
+        let state = if self.step == self.commands.len() {
+            "finished"
+        } else if self.paused {
+            "paused"
+        } else {
+            "playing"
+        };
+        format!("Step {}/{}, {state}", self.step, self.commands.len())
+    }
+}
+
+impl App for Replay {
+    type Output = ();
+
+    fn on_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char(' ') | KeyCode::Enter => self.paused = !self.paused,
+            KeyCode::Char('n') => self.next(),
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            _ => (),
+        }
+
+        true
+    }
+
+    fn on_tick(&mut self) {
+        if self.paused {
+            return;
+        }
+
+        self.ticks += 1;
+        if self.ticks >= STEP_TICKS {
+            self.ticks = 0;
+            self.next();
+        }
+    }
+
+    fn draw<B: Backend>(&self, f: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
+            .split(f.size());
+
+        if chunks[0].area() > 0 {
+            f.render_widget(&self.game_widget, chunks[0]);
+        }
+
+        let status_bar_widget = Paragraph::new(format!("{}, space to pause, n to step, q to quit", self.status()))
+            .alignment(Alignment::Center)
+            .block(TuiBlock::default().borders(Borders::ALL));
+        f.render_widget(status_bar_widget, chunks[1]);
+    }
+
+    fn describe(&self) -> String {
+        format!("Replay step {}. {}", self.status(), self.game_widget.describe())
+    }
+
+    fn output(self) -> Self::Output {}
+}
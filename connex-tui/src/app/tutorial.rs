@@ -0,0 +1,264 @@
+use connex::{Game, Pos, World};
+use connex_levels::tutorial::{Action, Goal, Step, STEPS};
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::Span,
+    widgets::{Block as TuiBlock, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{app::App, widget::Game as GameWidget};
+
+/// Input kind of a key, keys of no kind are not passed to game.
+fn action_of(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('k' | 'w' | 'l' | 'd' | 'j' | 's' | 'h' | 'a')
+        | KeyCode::Up
+        | KeyCode::Right
+        | KeyCode::Down
+        | KeyCode::Left => Some(Action::Move),
+        KeyCode::Char(' ') | KeyCode::Enter => Some(Action::Rotate),
+        _ => None,
+    }
+}
+
+/// Arrow keys are not handled by game widget, turn them into the letter keys.
+fn normalize(key: KeyEvent) -> KeyEvent {
+    let code = match key.code {
+        KeyCode::Up => KeyCode::Char('k'),
+        KeyCode::Right => KeyCode::Char('l'),
+        KeyCode::Down => KeyCode::Char('j'),
+        KeyCode::Left => KeyCode::Char('h'),
+        code => code,
+    };
+    KeyEvent::new(code, key.modifiers)
+}
+
+fn reached(goal: Goal, game: &Game) -> bool {
+    match goal {
+        Goal::CursorAt(row, col) => game.cursor() == Pos::new(row, col),
+        Goal::BlockFits(row, col) => game.world().solved_cells().contains(&Pos::new(row, col)),
+        Goal::Solved => game.solved(),
+    }
+}
+
+/// Guide players through [`STEPS`], only keys of the step's actions work, and next step starts after the goal is
+/// reached and enter is pressed.
+#[derive(Debug, Clone, Default)]
+pub struct Tutorial {
+    game_widget: GameWidget,
+    step: usize,
+    // goal of current step is reached, waiting for enter
+    done: bool,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        let mut tutorial = Self::default();
+        tutorial.start(0);
+        tutorial
+    }
+
+    fn current(&self) -> &'static Step {
+        &STEPS[self.step]
+    }
+
+    fn start(&mut self, step: usize) {
+        self.step = step;
+        self.done = false;
+        let step = self.current();
+        if let Some(content) = step.content {
+            let world: World = content.parse().unwrap();
+            self.game_widget.reset(world);
+        }
+        self.game_widget
+            .set_marked(step.highlight.map(|(row, col)| Pos::new(row, col)));
+        // goal may already be reached by previous step
+        self.check();
+    }
+
+    fn check(&mut self) {
+        self.done = reached(self.current().goal, self.game_widget.game());
+    }
+
+    fn is_last(&self) -> bool {
+        self.step + 1 == STEPS.len()
+    }
+
+    fn status(&self) -> &'static str {
+        match (self.done, self.is_last()) {
+            (true, true) => "Tutorial finished, press enter to start playing",
+            (true, false) => "Well done! Press enter to continue",
+            (false, _) => "Press q to leave the tutorial",
+        }
+    }
+}
+
+impl App for Tutorial {
+    type Output = ();
+
+    fn on_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Enter if self.done && self.is_last() => return false,
+            KeyCode::Enter if self.done => self.start(self.step + 1),
+            _ if self.done => (),
+            _ => {
+                if action_of(key).is_some_and(|action| self.current().actions.contains(&action)) {
+                    self.game_widget.on_key(normalize(key));
+                    self.check();
+                }
+            }
+        }
+
+        true
+    }
+
+    fn on_tick(&mut self) {}
+
+    fn draw<B: Backend>(&self, f: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(4), Constraint::Min(0), Constraint::Length(3)].as_ref())
+            .split(f.size());
+
+        let title = format!("Tutorial {}/{}", self.step + 1, STEPS.len());
+        let instruction_widget = Paragraph::new(self.current().instruction)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(TuiBlock::default().borders(Borders::ALL).title(title));
+        f.render_widget(instruction_widget, chunks[0]);
+
+        if chunks[1].area() > 0 {
+            f.render_widget(&self.game_widget, chunks[1]);
+        }
+
+        let mut status_style = Style::default();
+        if self.done {
+            status_style = status_style.fg(Color::Green);
+        }
+        let status_bar_widget = Paragraph::new(Span::styled(self.status(), status_style))
+            .alignment(Alignment::Center)
+            .block(TuiBlock::default().borders(Borders::ALL));
+        f.render_widget(status_bar_widget, chunks[2]);
+    }
+
+    fn describe(&self) -> String {
+        let highlight = match self.current().highlight {
+            Some((row, col)) => format!(" Highlighted block is at row {}, column {}.", row + 1, col + 1),
+            None => String::new(),
+        };
+        format!(
+            "Tutorial step {} of {}. {}{highlight} {}.\n{}",
+            self.step + 1,
+            STEPS.len(),
+            self.current().instruction,
+            self.status(),
+            self.game_widget.describe()
+        )
+    }
+
+    fn output(self) -> Self::Output {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn press(tutorial: &mut Tutorial, code: KeyCode) -> bool {
+        tutorial.on_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn world(tutorial: &Tutorial) -> &World {
+        tutorial.game_widget.game().world()
+    }
+
+    /// Move cursor to `(row, col)` with keys, the tutorial level is small enough to go straight.
+    fn move_to(tutorial: &mut Tutorial, row: usize, col: usize) {
+        let Pos { row: r, col: c } = tutorial.game_widget.cursor();
+        let keys = std::iter::repeat_n(if row < r { 'k' } else { 'j' }, row.abs_diff(r))
+            .chain(std::iter::repeat_n(if col < c { 'h' } else { 'l' }, col.abs_diff(c)));
+        for key in keys {
+            assert!(!tutorial.done);
+            press(tutorial, KeyCode::Char(key));
+        }
+        assert_eq!(tutorial.game_widget.cursor(), Pos::new(row, col));
+    }
+
+    #[test]
+    fn steps_advance_only_after_goal_is_reached() {
+        let mut tutorial = Tutorial::new();
+        assert_eq!(tutorial.step, 0);
+        assert!(!tutorial.done);
+
+        // step 1: move only, rotate and enter are ignored
+        let before = world(&tutorial).clone();
+        assert!(press(&mut tutorial, KeyCode::Char(' ')));
+        assert!(press(&mut tutorial, KeyCode::Enter));
+        assert_eq!(world(&tutorial), &before);
+        assert_eq!(tutorial.step, 0);
+
+        press(&mut tutorial, KeyCode::Down);
+        assert!(!tutorial.done);
+        press(&mut tutorial, KeyCode::Right);
+        assert!(tutorial.done);
+        assert_eq!(tutorial.game_widget.cursor(), Pos::new(1, 1));
+
+        // keys other than enter do nothing once the goal is reached
+        press(&mut tutorial, KeyCode::Left);
+        assert_eq!(tutorial.game_widget.cursor(), Pos::new(1, 1));
+        assert_eq!(tutorial.step, 0);
+
+        // step 2: same level and cursor, rotate only
+        press(&mut tutorial, KeyCode::Enter);
+        assert_eq!(tutorial.step, 1);
+        assert!(!tutorial.done);
+        press(&mut tutorial, KeyCode::Char('h'));
+        assert_eq!(tutorial.game_widget.cursor(), Pos::new(1, 1));
+
+        let mut turns = 0;
+        while !tutorial.done {
+            assert!(turns < 4, "block never fits");
+            assert_eq!(tutorial.step, 1);
+            press(&mut tutorial, KeyCode::Char(' '));
+            turns += 1;
+        }
+        assert!(world(&tutorial).solved_cells().contains(&Pos::new(1, 1)));
+
+        // remaining steps: solve the level by keys, enter is rotate inside a step
+        for step in 2..STEPS.len() {
+            press(&mut tutorial, KeyCode::Enter);
+            assert_eq!(tutorial.step, step);
+            assert!(!tutorial.done);
+
+            let target = world(&tutorial).solve().unwrap();
+            for (row, col, block) in target.iter() {
+                move_to(&mut tutorial, row, col);
+                while world(&tutorial)[(row, col)] != *block {
+                    assert!(!tutorial.done);
+                    press(&mut tutorial, KeyCode::Enter);
+                }
+            }
+            assert!(tutorial.done);
+            assert!(tutorial.game_widget.game().solved());
+        }
+        assert!(tutorial.is_last());
+        assert_eq!(tutorial.status(), "Tutorial finished, press enter to start playing");
+
+        // enter on the finished last step leaves the tutorial
+        assert!(!press(&mut tutorial, KeyCode::Enter));
+    }
+
+    #[test]
+    fn quit_keys_leave_at_any_step() {
+        for code in [KeyCode::Char('q'), KeyCode::Esc] {
+            let mut tutorial = Tutorial::new();
+            assert!(!press(&mut tutorial, code));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    num::NonZeroUsize,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use connex::{generator, World};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A calendar day in UTC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Date {
+    days: i64,
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Create from days since 1970-01-01.
+    fn from_days(days: i64) -> Self {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + (month <= 2) as i64;
+
+        Self { days, year, month, day }
+    }
+
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self::from_days((seconds / SECONDS_PER_DAY) as i64)
+    }
+
+    pub fn previous(&self) -> Self {
+        Self::from_days(self.days - 1)
+    }
+
+    /// Date as a `yyyymmdd` number, used as seed and progress key.
+    pub fn code(&self) -> u64 {
+        self.year as u64 * 10000 + self.month as u64 * 100 + self.day as u64
+    }
+}
+
+/// Generate the daily challenge level of a day, it grows from 4x4 at the start of a year to 8x8 at the end.
+pub fn level(date: &Date) -> World {
+    let day_of_year = (date.month - 1) * 31 + date.day - 1;
+    let size = NonZeroUsize::new(4 + day_of_year as usize * 5 / 372).unwrap();
+
+    // seeded generation is portable, so every player gets the same level
+    generator::generate_seeded(size, size, date.code())
+}
+
+/// Count consecutive solved days, end at today, or yesterday if today is not solved yet.
+pub fn streak<F>(today: &Date, solved: F) -> usize
+where
+    F: Fn(u64) -> bool,
+{
+    let mut date = if solved(today.code()) { *today } else { today.previous() };
+    let mut count = 0;
+
+    while solved(date.code()) {
+        count += 1;
+        date = date.previous();
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-02-29
+    const LEAP_DAY: i64 = 19782;
+
+    #[test]
+    fn date_from_days() {
+        let date = Date::from_days(LEAP_DAY);
+        assert_eq!((date.year, date.month, date.day), (2024, 2, 29));
+        assert_eq!(date.code(), 20240229);
+        assert_eq!(date.previous().code(), 20240228);
+        assert_eq!(Date::from_days(0).code(), 19700101);
+    }
+
+    #[test]
+    fn same_day_same_level() {
+        let date = Date::from_days(LEAP_DAY);
+        assert_eq!(level(&date), level(&date));
+        assert_ne!(level(&date), level(&date.previous()));
+    }
+
+    #[test]
+    fn level_grows_over_the_year() {
+        let size = |days| level(&Date::from_days(days)).size().0.get();
+        // 2024-01-01 and 2024-12-31
+        assert_eq!(size(19723), 4);
+        assert_eq!(size(20088), 8);
+    }
+
+    #[test]
+    fn streak_counts_consecutive_days() {
+        let today = Date::from_days(LEAP_DAY);
+        let solved = [20240229, 20240228, 20240227, 20240225];
+        assert_eq!(streak(&today, |code| solved.contains(&code)), 3);
+        // today not solved yet, streak ends yesterday
+        assert_eq!(streak(&today, |code| solved[1..].contains(&code)), 2);
+        assert_eq!(streak(&today, |_| false), 0);
+    }
+}
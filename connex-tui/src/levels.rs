@@ -0,0 +1,188 @@
+//! Built-in levels.
+//!
+//! With `dev-levels` feature in debug builds, levels are loaded from `connex-levels/levels` directory at runtime
+//! and reloaded when files change, so level authors don't need to recompile. Otherwise they are the ones baked
+//! into [`connex_levels::ALL`].
+
+#[cfg(not(all(feature = "dev-levels", debug_assertions)))]
+mod imp {
+    use connex::World;
+    use once_cell::sync::Lazy;
+
+    // parsing is not free, so do it once instead of every frame
+    static SIZES: Lazy<Vec<(usize, usize)>> = Lazy::new(|| {
+        connex_levels::ALL
+            .iter()
+            .map(|level| {
+                let world: World = level.content.parse().unwrap();
+                (world.height().get(), world.width().get())
+            })
+            .collect()
+    });
+
+    pub fn len() -> usize {
+        connex_levels::ALL.len()
+    }
+
+    pub fn name(index: usize) -> Option<String> {
+        connex_levels::ALL.get(index).map(|level| level.name.to_string())
+    }
+
+    pub fn size(index: usize) -> Option<(usize, usize)> {
+        SIZES.get(index).copied()
+    }
+
+    pub fn content(index: usize) -> Option<String> {
+        connex_levels::ALL.get(index).map(|level| level.content.to_string())
+    }
+
+    pub fn hash(index: usize) -> Option<u64> {
+        connex_levels::ALL.get(index).map(|level| level.hash)
+    }
+
+    pub fn generation() -> u64 {
+        0
+    }
+}
+
+#[cfg(all(feature = "dev-levels", debug_assertions))]
+mod imp {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::{mpsc, Mutex},
+        thread,
+    };
+
+    use connex::World;
+    use connex_levels::Level;
+    use notify::{RecursiveMode, Watcher};
+    use once_cell::sync::Lazy;
+
+    struct Entry {
+        // file name without extension
+        name: String,
+        // canonical level string
+        content: String,
+        hash: u64,
+        // height and width
+        size: (usize, usize),
+    }
+
+    struct Levels {
+        levels: Vec<Entry>,
+        // increased on every reload
+        generation: u64,
+    }
+
+    static LEVELS: Lazy<Mutex<Levels>> = Lazy::new(|| {
+        thread::spawn(watch);
+        Mutex::new(Levels {
+            levels: load(),
+            generation: 0,
+        })
+    });
+
+    fn dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../connex-levels/levels")
+    }
+
+    /// Load levels in file name order, files can't be parsed are skipped, they may be in the middle of editing.
+    fn load() -> Vec<Entry> {
+        let mut paths: Vec<_> = fs::read_dir(dir())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+            .map(|entry| entry.path())
+            .collect();
+        paths.sort_unstable();
+
+        paths
+            .iter()
+            .filter_map(|path| {
+                let world = fs::read_to_string(path).ok()?.parse::<World>().ok()?;
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                Some((name, world))
+            })
+            .map(|(name, world)| {
+                let content = world.to_string();
+                let hash = Level::hash_of(&content);
+                let size = (world.height().get(), world.width().get());
+                Entry {
+                    name,
+                    content,
+                    hash,
+                    size,
+                }
+            })
+            .collect()
+    }
+
+    fn watch() {
+        let (tx, rx) = mpsc::channel();
+        let Ok(mut watcher) = notify::recommended_watcher(tx) else {
+            return;
+        };
+        if watcher.watch(&dir(), RecursiveMode::NonRecursive).is_err() {
+            return;
+        }
+
+        for event in rx {
+            // reading files while loading may cause access events, ignore them to avoid reloading forever
+            if event.is_ok_and(|event| !event.kind.is_access()) {
+                let levels = load();
+                let mut state = LEVELS.lock().unwrap();
+                state.levels = levels;
+                state.generation += 1;
+            }
+        }
+    }
+
+    pub fn len() -> usize {
+        LEVELS.lock().unwrap().levels.len()
+    }
+
+    pub fn name(index: usize) -> Option<String> {
+        LEVELS.lock().unwrap().levels.get(index).map(|entry| entry.name.clone())
+    }
+
+    pub fn content(index: usize) -> Option<String> {
+        LEVELS
+            .lock()
+            .unwrap()
+            .levels
+            .get(index)
+            .map(|entry| entry.content.clone())
+    }
+
+    pub fn hash(index: usize) -> Option<u64> {
+        LEVELS.lock().unwrap().levels.get(index).map(|entry| entry.hash)
+    }
+
+    pub fn size(index: usize) -> Option<(usize, usize)> {
+        LEVELS.lock().unwrap().levels.get(index).map(|entry| entry.size)
+    }
+
+    pub fn generation() -> u64 {
+        LEVELS.lock().unwrap().generation
+    }
+}
+
+/// Count of levels.
+pub use imp::len;
+
+/// Name of level at index, which is its file name without extension.
+pub use imp::name;
+
+/// Level at index in string format.
+pub use imp::content;
+
+/// Height and width of level at index.
+pub use imp::size;
+
+/// Hash of level at index, used as key of progress, see [`connex_levels::Level::hash_of`].
+pub use imp::hash;
+
+/// Changes every time levels are reloaded, levels at same index may be different after that.
+pub use imp::generation;
@@ -4,6 +4,12 @@
 #![forbid(unsafe_code)]
 
 mod app;
+mod daily;
+mod levels;
+mod progress;
+#[cfg(test)]
+mod testing;
+mod text;
 mod widget;
 
 use std::{env::args, error::Error, num::NonZeroUsize, time::Duration};
@@ -15,12 +21,13 @@ use crossterm::{
 use tui::{backend::CrosstermBackend, Terminal};
 
 use app::App;
+use connex::World;
 
 const TICK_RATE: Duration = std::time::Duration::from_millis(20);
 
 fn editor_world_size() -> Option<(NonZeroUsize, NonZeroUsize)> {
     let editor_args: Vec<_> = args().skip(1).take(3).collect();
-    let is_editor_mode = editor_args.get(0).map(|s| s == "editor").unwrap_or_default();
+    let is_editor_mode = editor_args.first().map(|s| s == "editor").unwrap_or_default();
 
     if !is_editor_mode {
         return None;
@@ -40,7 +47,76 @@ fn editor_world_size() -> Option<(NonZeroUsize, NonZeroUsize)> {
     Some((height.try_into().unwrap(), width.try_into().unwrap()))
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+/// Get solver budget given by `--solve-timeout-ms <n>` arguments.
+fn solve_timeout() -> Duration {
+    let args: Vec<_> = args().collect();
+    args.iter()
+        .position(|arg| arg == "--solve-timeout-ms")
+        .and_then(|i| args.get(i + 1)?.parse().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(app::DEFAULT_SOLVE_TIMEOUT)
+}
+
+fn is_multiplayer() -> bool {
+    args().nth(1).map(|s| s == "multiplayer").unwrap_or_default()
+}
+
+fn is_tutorial() -> bool {
+    args().nth(1).map(|s| s == "tutorial").unwrap_or_default()
+}
+
+/// Tutorial is shown before the game on first run, progress is saved after it so it's only shown once.
+fn is_first_run() -> bool {
+    !progress::exists()
+}
+
+fn finish_first_run() {
+    let _ = progress::save(&progress::load());
+}
+
+/// Load replay file given by `replay <file>` arguments.
+///
+/// Replay file is a world in it's string representation, an empty line, then the command notation,
+/// see [`connex::Game::from_notation`].
+fn load_replay() -> Result<Option<app::Replay>, Box<dyn Error>> {
+    let replay_args: Vec<_> = args().skip(1).take(2).collect();
+    if replay_args.first().map(|s| s != "replay").unwrap_or(true) {
+        return Ok(None);
+    }
+
+    let path = replay_args.get(1).ok_or("missing replay file")?;
+    let content = std::fs::read_to_string(path)?;
+    let (world, notation) = content
+        .split_once("\n\n")
+        .ok_or("replay file should contains a world and notation, separated by an empty line")?;
+
+    let world = World::parse_all_errors(world)
+        .map_err(|errors| errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))?;
+    let game = connex::Game::from_notation(world.clone(), notation).map_err(|e| e.to_string())?;
+
+    Ok(Some(app::Replay::new(world, game.history().to_vec())))
+}
+
+/// Run `progress export <file>` or `progress import <file>`, return None if not given.
+fn progress_command() -> Option<Result<(), Box<dyn Error>>> {
+    let progress_args: Vec<_> = args().skip(1).take(3).collect();
+    if progress_args.first().map(|s| s != "progress").unwrap_or(true) {
+        return None;
+    }
+
+    let result = match (progress_args.get(1).map(String::as_str), progress_args.get(2)) {
+        (Some("export"), Some(path)) => {
+            progress::export(path.as_ref()).map(|()| eprintln!("Progress exported to {path}"))
+        }
+        (Some("import"), Some(path)) => {
+            progress::import(path.as_ref()).map(|()| eprintln!("Progress merged from {path}"))
+        }
+        _ => Err("usage: connex-tui progress export|import <file>".into()),
+    };
+    Some(result)
+}
+
+fn run_tui(replay: Option<app::Replay>) -> Result<Option<World>, Box<dyn Error>> {
     crossterm::terminal::enable_raw_mode()?;
     let mut stdout = std::io::stdout();
     stdout.execute(EnterAlternateScreen)?;
@@ -48,10 +124,23 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut terminal = Terminal::new(backend)?;
     terminal.hide_cursor()?;
 
-    let output = if let Some((height, width)) = editor_world_size() {
+    let output = if let Some(replay) = replay {
+        replay.run(&mut terminal, TICK_RATE)?;
+        None
+    } else if is_multiplayer() {
+        app::Multiplayer::default().run(&mut terminal, TICK_RATE)?;
+        None
+    } else if let Some((height, width)) = editor_world_size() {
         Some(app::Editor::new(height, width).run(&mut terminal, TICK_RATE)?)
+    } else if is_tutorial() {
+        app::Tutorial::new().run(&mut terminal, TICK_RATE)?;
+        None
     } else {
-        app::Game::default().run(&mut terminal, TICK_RATE)?;
+        if is_first_run() {
+            app::Tutorial::new().run(&mut terminal, TICK_RATE)?;
+            finish_first_run();
+        }
+        app::Game::new(solve_timeout()).run(&mut terminal, TICK_RATE)?;
         None
     };
 
@@ -59,8 +148,56 @@ fn main() -> Result<(), Box<dyn Error>> {
     crossterm::terminal::disable_raw_mode()?;
     terminal.show_cursor()?;
 
+    Ok(output)
+}
+
+fn run_accessible(replay: Option<app::Replay>) -> Result<Option<World>, Box<dyn Error>> {
+    crossterm::terminal::enable_raw_mode()?;
+
+    let output = if let Some(replay) = replay {
+        replay.run_accessible()?;
+        None
+    } else if is_multiplayer() {
+        app::Multiplayer::default().run_accessible()?;
+        None
+    } else if let Some((height, width)) = editor_world_size() {
+        Some(app::Editor::new(height, width).run_accessible()?)
+    } else if is_tutorial() {
+        app::Tutorial::new().run_accessible()?;
+        None
+    } else {
+        if is_first_run() {
+            app::Tutorial::new().run_accessible()?;
+            finish_first_run();
+        }
+        app::Game::new(solve_timeout()).run_accessible()?;
+        None
+    };
+
+    crossterm::terminal::disable_raw_mode()?;
+
+    Ok(output)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    if let Some(result) = progress_command() {
+        return result;
+    }
+
+    let replay = load_replay()?;
+
+    let output = if args().skip(1).any(|arg| arg == "--accessible") {
+        run_accessible(replay)?
+    } else {
+        run_tui(replay)?
+    };
+
     if let Some(output) = output {
-        print!("{}", output)
+        if args().skip(1).any(|arg| arg == "--no-size") {
+            print!("{:#}", output)
+        } else {
+            print!("{}", output)
+        }
     }
 
     Ok(())
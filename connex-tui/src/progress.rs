@@ -0,0 +1,300 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Version of progress file, files without version key levels by index.
+const VERSION: u32 = 1;
+
+/// Error of reading a progress file written by a newer version, which may have data this version can't keep.
+#[derive(Debug)]
+struct NewerVersion(u64);
+
+impl Display for NewerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "progress file version {} is newer than supported version {VERSION}, upgrade connex-tui first",
+            self.0
+        )
+    }
+}
+
+impl Error for NewerVersion {}
+
+/// Player progress of built-in levels, keyed by level hash, see [`connex_levels::Level::hash_of`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+    #[serde(default)]
+    version: u32,
+    /// Saved file is written by a newer version, so it's not overwritten, see [`save`].
+    #[serde(skip)]
+    read_only: bool,
+    pub completed: HashSet<u64>,
+    pub best_times: HashMap<u64, Duration>,
+    /// Solve time of daily challenges, keyed by `yyyymmdd` date.
+    #[serde(default)]
+    pub daily: HashMap<u64, Duration>,
+}
+
+impl Progress {
+    fn new() -> Self {
+        Self {
+            version: VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Mark a level as completed, keep the shorter time as best time.
+    pub fn complete(&mut self, level: u64, time: Duration) {
+        self.completed.insert(level);
+        let best = self.best_times.entry(level).or_insert(time);
+        *best = (*best).min(time);
+    }
+
+    /// Re-key levels by hash if progress is saved by an old version which keys them by index.
+    fn migrate(&mut self) {
+        if self.version == 0 {
+            let hash = |index: u64| connex_levels::ALL.get(index as usize).map(|level| level.hash);
+            self.completed = self.completed.iter().filter_map(|&index| hash(index)).collect();
+            self.best_times = self
+                .best_times
+                .iter()
+                .filter_map(|(&index, &time)| Some((hash(index)?, time)))
+                .collect();
+        }
+        self.version = VERSION;
+    }
+
+    /// Record a solved daily challenge, keep the shorter time.
+    pub fn complete_daily(&mut self, date: u64, time: Duration) {
+        let best = self.daily.entry(date).or_insert(time);
+        *best = (*best).min(time);
+    }
+
+    /// Merge progress from another machine, completed levels are combined and shorter times are kept, so merging
+    /// is idempotent and order doesn't matter.
+    pub fn merge(&mut self, other: &Progress) {
+        self.completed.extend(&other.completed);
+        for (&level, &time) in &other.best_times {
+            let best = self.best_times.entry(level).or_insert(time);
+            *best = (*best).min(time);
+        }
+        for (&date, &time) in &other.daily {
+            self.complete_daily(date, time);
+        }
+    }
+
+    /// Parse progress file content, old versions are migrated, newer versions are rejected.
+    fn from_json(content: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let value: serde_json::Value = serde_json::from_slice(content)?;
+        let version = value.get("version").and_then(serde_json::Value::as_u64).unwrap_or(0);
+        if version > u64::from(VERSION) {
+            return Err(NewerVersion(version).into());
+        }
+
+        let mut progress: Progress = serde_json::from_value(value)?;
+        progress.migrate();
+        Ok(progress)
+    }
+}
+
+#[cfg(windows)]
+fn data_dir() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(PathBuf::from)
+}
+
+#[cfg(not(windows))]
+fn data_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+}
+
+fn path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("connex").join("progress.json"))
+}
+
+/// Check if progress is saved, it's not on first run.
+pub fn exists() -> bool {
+    path().is_some_and(|path| path.exists())
+}
+
+fn no_data_dir() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::NotFound, "no data directory")
+}
+
+/// Read progress file at `path`, return empty progress if it doesn't exist.
+fn read_from(path: &Path) -> Result<Progress, Box<dyn Error>> {
+    match fs::read(path) {
+        Ok(content) => Progress::from_json(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Progress::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Read progress file at `path`, return empty progress if it can't be read.
+///
+/// If the file is written by a newer version, the empty progress is marked read only, so it doesn't overwrite the
+/// file when saved.
+fn load_from(path: &Path) -> Progress {
+    read_from(path).unwrap_or_else(|e| Progress {
+        read_only: e.is::<NewerVersion>(),
+        ..Progress::new()
+    })
+}
+
+/// Load saved progress, return empty progress if not saved yet or file is broken, see [`load_from`].
+pub fn load() -> Progress {
+    path().map_or_else(Progress::new, |path| load_from(&path))
+}
+
+/// Write saved progress to `path` as JSON, it can be imported on another machine, see [`import`].
+pub fn export(path: &Path) -> Result<(), Box<dyn Error>> {
+    let progress = read_from(&self::path().ok_or_else(no_data_dir)?)?;
+    fs::write(path, serde_json::to_vec_pretty(&progress)?)?;
+    Ok(())
+}
+
+/// Merge progress exported by [`export`] into saved progress, see [`Progress::merge`].
+pub fn import(path: &Path) -> Result<(), Box<dyn Error>> {
+    let other = Progress::from_json(&fs::read(path)?)?;
+    let saved = self::path().ok_or_else(no_data_dir)?;
+    let mut progress = read_from(&saved)?;
+    progress.merge(&other);
+    save_to(&saved, &progress)?;
+    Ok(())
+}
+
+fn save_to(path: &Path, p: &Progress) -> std::io::Result<()> {
+    if p.read_only {
+        return Err(std::io::Error::other(
+            "progress file is written by a newer version, not overwritten",
+        ));
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_vec(p)?)
+}
+
+/// Save progress, data directory is created if not exists.
+///
+/// Fails without writing if the saved file is written by a newer version and can't be loaded.
+pub fn save(p: &Progress) -> std::io::Result<()> {
+    save_to(&path().ok_or_else(no_data_dir)?, p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    fn progress(levels: &[(u64, u64)], daily: &[(u64, u64)]) -> Progress {
+        let mut progress = Progress::new();
+        for &(level, time) in levels {
+            progress.complete(level, secs(time));
+        }
+        for &(date, time) in daily {
+            progress.complete_daily(date, secs(time));
+        }
+        progress
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("connex-tui-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn merge_is_idempotent() {
+        let a = progress(&[(1, 30), (2, 40)], &[(20240229, 50)]);
+        let mut merged = a.clone();
+        merged.merge(&a);
+        assert_eq!(merged, a);
+    }
+
+    #[test]
+    fn merge_is_commutative_and_keeps_shorter_time() {
+        let a = progress(&[(1, 30), (2, 40)], &[(20240229, 50), (20240301, 10)]);
+        let b = progress(&[(2, 35), (3, 60)], &[(20240229, 70), (20240302, 20)]);
+
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+
+        assert_eq!(ab.completed, ba.completed);
+        assert_eq!(ab.best_times, ba.best_times);
+        assert_eq!(ab.daily, ba.daily);
+
+        assert_eq!(ab.completed, HashSet::from([1, 2, 3]));
+        assert_eq!(
+            ab.best_times,
+            HashMap::from([(1, secs(30)), (2, secs(35)), (3, secs(60))])
+        );
+        assert_eq!(
+            ab.daily,
+            HashMap::from([(20240229, secs(50)), (20240301, secs(10)), (20240302, secs(20))])
+        );
+    }
+
+    #[test]
+    fn v0_file_is_migrated_to_hashes() {
+        let content = br#"{"completed":[0,1,999],"best_times":{"1":{"secs":12,"nanos":0},"999":{"secs":1,"nanos":0}}}"#;
+        let progress = Progress::from_json(content).unwrap();
+
+        let hash = |index: usize| connex_levels::ALL[index].hash;
+        assert_eq!(progress.version, VERSION);
+        // indexes without a level are dropped
+        assert_eq!(progress.completed, HashSet::from([hash(0), hash(1)]));
+        assert_eq!(progress.best_times, HashMap::from([(hash(1), secs(12))]));
+        assert!(progress.daily.is_empty());
+    }
+
+    #[test]
+    fn newer_file_is_rejected_and_not_overwritten() {
+        let content = format!(
+            r#"{{"version":{},"completed":[],"best_times":{{}},"future":1}}"#,
+            VERSION + 1
+        );
+        let error = Progress::from_json(content.as_bytes()).unwrap_err();
+        assert!(error.is::<NewerVersion>());
+
+        let path = temp_file("newer.json");
+        fs::write(&path, &content).unwrap();
+        let mut progress = load_from(&path);
+        assert!(progress.read_only);
+        assert!(progress.completed.is_empty());
+
+        progress.complete(1, secs(10));
+        assert!(save_to(&path, &progress).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn saved_progress_loads_back() {
+        let path = temp_file("round_trip.json");
+        assert_eq!(load_from(&path), Progress::new());
+
+        let progress = progress(&[(1, 30)], &[(20240229, 50)]);
+        save_to(&path, &progress).unwrap();
+        assert_eq!(load_from(&path), progress);
+
+        // a broken file is replaced
+        fs::write(&path, "{").unwrap();
+        let broken = load_from(&path);
+        assert!(!broken.read_only);
+        save_to(&path, &broken).unwrap();
+    }
+}
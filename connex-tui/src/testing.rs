@@ -0,0 +1,88 @@
+//! Helpers to render widgets without a live terminal in tests.
+
+use std::{env, fs, path::PathBuf};
+
+use tui::{backend::TestBackend, buffer::Buffer, style::Color, widgets::Widget, Terminal};
+
+/// Render a widget to a `width` x `height` in-memory terminal, return the drawn buffer.
+pub fn render<W: Widget>(widget: W, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| f.render_widget(widget, f.size())).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Count cells which draw something.
+pub fn drawn_cells(buffer: &Buffer) -> usize {
+    buffer.content().iter().filter(|cell| cell.symbol.trim() != "").count()
+}
+
+fn color_char(color: Color) -> char {
+    match color {
+        Color::Reset => '.',
+        Color::Green => 'g',
+        Color::Yellow => 'y',
+        Color::Red => 'r',
+        Color::DarkGray => 'd',
+        _ => '?',
+    }
+}
+
+/// Text form of a buffer for snapshots, symbols of each row, then a line `--`, then foreground color of each drawn
+/// cell as a letter, `.` for default color, `g`reen, `y`ellow, `r`ed and `d`ark gray.
+pub fn buffer_text(buffer: &Buffer) -> String {
+    let width = usize::from(buffer.area.width).max(1);
+    let mut symbols = String::new();
+    let mut colors = String::new();
+
+    for row in buffer.content().chunks(width) {
+        for cell in row {
+            let drawn = cell.symbol.trim() != "";
+            symbols.push_str(if drawn { &cell.symbol } else { " " });
+            colors.push(if drawn { color_char(cell.fg) } else { ' ' });
+        }
+        symbols.push('\n');
+        colors.push('\n');
+    }
+
+    format!("{symbols}--\n{colors}")
+}
+
+/// Compare `actual` with snapshot file `testdata/snapshots/<name>.txt`.
+///
+/// Set `CONNEX_UPDATE_SNAPSHOTS=1` to write the snapshots after an intended rendering change, then review the diff.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "testdata",
+        "snapshots",
+        &format!("{name}.txt"),
+    ]
+    .iter()
+    .collect();
+
+    if env::var_os("CONNEX_UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+    if let Some((line, (expected, actual))) = expected
+        .lines()
+        .chain(std::iter::repeat(""))
+        .zip(actual.lines())
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)
+    {
+        panic!(
+            "snapshot {name} differs at line {}:\nexpected: {expected:?}\n  actual: {actual:?}\n\
+             run tests with CONNEX_UPDATE_SNAPSHOTS=1 to update {}",
+            line + 1,
+            path.display()
+        );
+    }
+    assert!(
+        expected.lines().count() == actual.lines().count(),
+        "snapshot {name} has extra lines, run tests with CONNEX_UPDATE_SNAPSHOTS=1 to update {}",
+        path.display()
+    );
+}
@@ -0,0 +1,68 @@
+//! Text helpers aware of terminal display width.
+
+use std::borrow::Cow;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Display width of `s` in terminal columns, wide chars like CJK and emoji take 2 columns.
+pub fn width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Truncate `s` to at most `max` columns, an ellipsis is appended if anything is cut.
+pub fn truncate(s: &str, max: usize) -> Cow<'_, str> {
+    if width(s) <= max {
+        return Cow::Borrowed(s);
+    }
+    if max == 0 {
+        return Cow::Borrowed("");
+    }
+
+    // leave one column for the ellipsis
+    let mut result = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > max - 1 {
+            break;
+        }
+        used += w;
+        result.push(c);
+    }
+    result.push('…');
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fitting_strings_are_borrowed() {
+        assert!(matches!(truncate("loops", 5), Cow::Borrowed("loops")));
+        assert!(matches!(truncate("水管", 4), Cow::Borrowed("水管")));
+        assert_eq!(truncate("", 0), "");
+        assert_eq!(truncate("a", 0), "");
+    }
+
+    #[test]
+    fn wide_char_straddling_cut_is_dropped() {
+        // the ellipsis takes column 4, `管` would take columns 3 and 4
+        let cut = truncate("水管工人", 4);
+        assert_eq!(cut, "水…");
+        assert!(width(&cut) <= 4);
+
+        for max in 1..8 {
+            let cut = truncate("a水管b工人", max);
+            assert!(width(&cut) <= max, "{max}: {cut}");
+        }
+    }
+
+    #[test]
+    fn combining_mark_stays_with_its_base() {
+        let name = "cafe\u{301} loops";
+        assert_eq!(width(name), 10);
+        assert_eq!(truncate(name, 5), "cafe\u{301}…");
+        assert_eq!(truncate(name, 4), "caf…");
+    }
+}
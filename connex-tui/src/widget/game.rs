@@ -1,7 +1,13 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use tui::widgets::{canvas::Canvas, Block, Borders, Widget};
+use std::{cell::Cell, collections::HashSet};
 
-use connex::{Command, Direction, World};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use tui::{
+    layout::Rect,
+    style::Color,
+    widgets::{canvas::Canvas, Block, Borders, Widget},
+};
+
+use connex::{Block as WorldBlock, Command, Direction, GameEvent, Pos, SolvedState, World};
 
 use super::painter::WorldPainter;
 
@@ -9,11 +15,35 @@ use super::painter::WorldPainter;
 pub struct Game {
     game: connex::Game,
     edit: bool,
+    // anchor of visual selection in edit mode, the selection is the rectangle between it and cursor
+    selection: Option<Pos>,
+    // block to fill the selection with, typed in visual selection mode
+    fill_block: Option<WorldBlock>,
+    // world drawn dimmed under the game world, e.g. a solution to peek
+    overlay: Option<World>,
+    // blocks yanked from a selection, pasted at cursor
+    clipboard: Option<World>,
+    // `F` is pressed in edit mode, the next block char flood fills from cursor
+    flood_filling: bool,
+    // block to point out, e.g. in tutorial
+    marked: Option<Pos>,
+    // area drawn last time, to find the block under mouse
+    area: Cell<Rect>,
 }
 
 impl Game {
     pub fn new(game: connex::Game) -> Self {
-        Self { game, edit: false }
+        Self {
+            game,
+            edit: false,
+            selection: None,
+            fill_block: None,
+            overlay: None,
+            clipboard: None,
+            flood_filling: false,
+            marked: None,
+            area: Cell::default(),
+        }
     }
 
     pub fn is_edit(&self) -> bool {
@@ -22,22 +52,211 @@ impl Game {
 
     pub fn set_edit(&mut self, enable: bool) {
         self.edit = enable;
+        self.selection = None;
+        self.flood_filling = false;
+    }
+
+    /// Get visual selection as `(top, left, height, width)`, it's always inside the world.
+    pub fn selection(&self) -> Option<(usize, usize, usize, usize)> {
+        let Pos {
+            row: anchor_row,
+            col: anchor_col,
+        } = self.selection?;
+        let (height, width) = self.game.world().size();
+        let anchor_row = anchor_row.min(height.get() - 1);
+        let anchor_col = anchor_col.min(width.get() - 1);
+        let Pos { row, col } = self.game.cursor();
+
+        Some((
+            row.min(anchor_row),
+            col.min(anchor_col),
+            row.abs_diff(anchor_row) + 1,
+            col.abs_diff(anchor_col) + 1,
+        ))
+    }
+
+    fn in_selection(&self, i: usize, j: usize) -> bool {
+        self.selection().is_some_and(|(top, left, height, width)| {
+            (top..top + height).contains(&i) && (left..left + width).contains(&j)
+        })
+    }
+
+    pub fn is_selecting(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    /// `F` is pressed in edit mode, waiting for the block char to flood fill with.
+    pub fn is_flood_filling(&self) -> bool {
+        self.flood_filling
+    }
+
+    /// Handle the block char typed after `F`, any other char cancels the flood fill. Returns `true` if the world
+    /// becomes solved by it.
+    fn on_flood_fill_key(&mut self, c: char) -> bool {
+        self.flood_filling = false;
+        let command = match c.to_string().parse() {
+            Ok(block) => Command::FloodFill(self.game.cursor(), block),
+            Err(_) => Command::Noop,
+        };
+        self.game.apply_with_outcome(command).solved_now
+    }
+
+    /// Handle keys in visual selection mode, typed block char is remembered and `f` fills the selection with it,
+    /// `y` yanks the selection to clipboard and `x` clears it.
+    fn on_selection_key(&mut self, c: char) -> Command {
+        match c {
+            'V' => {
+                self.selection = None;
+                Command::Noop
+            }
+            'y' => {
+                if let Some((top, left, height, width)) = self.selection() {
+                    self.clipboard = self.game.world().crop(top, left, height, width).ok();
+                }
+                self.selection = None;
+                Command::Noop
+            }
+            'x' => match self.selection() {
+                Some((top, left, height, width)) => {
+                    self.selection = None;
+                    Command::FillRegion(top, left, height, width, WorldBlock::Empty)
+                }
+                None => Command::Noop,
+            },
+            'f' => match (self.selection(), self.fill_block) {
+                (Some((top, left, height, width)), Some(block)) => {
+                    self.selection = None;
+                    Command::FillRegion(top, left, height, width, block)
+                }
+                _ => Command::Noop,
+            },
+            _ => {
+                if let Ok(block) = c.to_string().parse() {
+                    self.fill_block = Some(block);
+                }
+                Command::Noop
+            }
+        }
+    }
+
+    /// Transform the whole world by key, `r`/`R` rotate it clockwise/counterclockwise, `h`/`v` flip it
+    /// horizontally/vertically and `t` transposes it. Returns what is done, or None if the key is not a transform.
+    ///
+    /// Transforms are game commands, so they are recorded like other edits and cursor follows its block.
+    pub fn on_transform_key(&mut self, code: KeyCode) -> Option<&'static str> {
+        let (commands, done) = match code {
+            KeyCode::Char('r') => (vec![Command::RotateWholeWorld(true)], "Rotated clockwise"),
+            // three clockwise rotations, there is no counterclockwise command
+            KeyCode::Char('R') => (vec![Command::RotateWholeWorld(true); 3], "Rotated counterclockwise"),
+            KeyCode::Char('h') => (vec![Command::FlipHorizontally], "Flipped horizontally"),
+            KeyCode::Char('v') => (vec![Command::FlipVertically], "Flipped vertically"),
+            KeyCode::Char('t') => (vec![Command::Transpose], "Transposed"),
+            _ => return None,
+        };
+        self.game.apply_batch(commands);
+        Some(done)
+    }
+
+    /// Replace all empty blocks with crosses, for quick difficulty testing during level design.
+    fn fill_empty_with_cross(&mut self) {
+        let mut world = self.game.world().clone();
+        world.replace_empty_with(WorldBlock::Cross);
+        let commands: Vec<_> = world
+            .iter()
+            .zip(self.game.world())
+            .filter(|((_, _, new), (_, _, old))| new != old)
+            .map(|((row, col, block), _)| Command::ReplaceBlock(Pos::new(row, col), *block))
+            .collect();
+        self.game.apply_batch(commands);
+    }
+
+    /// Set a world to draw dimmed under the game world, it's ignored if size not match.
+    pub fn set_overlay(&mut self, overlay: Option<World>) {
+        self.overlay = overlay;
+    }
+
+    /// Point out a block by drawing it in yellow.
+    pub fn set_marked(&mut self, marked: Option<Pos>) {
+        self.marked = marked;
     }
 
     pub fn reset(&mut self, world: World) {
+        self.selection = None;
+        self.flood_filling = false;
         self.game.apply(Command::Reset(world));
     }
 
-    pub fn on_key(&mut self, key: KeyEvent) {
+    /// Apply a command, returns `true` if the world becomes solved by it.
+    pub fn apply(&mut self, command: Command) -> bool {
+        self.game.apply_with_outcome(command).solved_now
+    }
+
+    /// Apply commands in order, returns `true` if the world becomes solved by them.
+    pub fn apply_batch(&mut self, commands: impl IntoIterator<Item = Command>) -> bool {
+        self.game.apply_batch(commands).contains(&GameEvent::Solved)
+    }
+
+    /// Handle a key, returns `true` if the world becomes solved by it.
+    pub fn on_key(&mut self, key: KeyEvent) -> bool {
+        let mut solved_now = false;
+
+        if key.code == KeyCode::Esc && self.selection.is_some() {
+            self.selection = None;
+            return false;
+        }
+
+        if self.edit && self.flood_filling {
+            // block char may also be a move or rotate key, like space for empty block
+            return match key.code {
+                KeyCode::Char(c) => self.on_flood_fill_key(c),
+                _ => {
+                    self.flood_filling = false;
+                    false
+                }
+            };
+        }
+
+        if self.edit && key.modifiers.contains(KeyModifiers::SHIFT) {
+            // shift with arrow keys starts a selection at cursor, or extends the current one
+            let direction = match key.code {
+                KeyCode::Up => Some(Direction::Up),
+                KeyCode::Right => Some(Direction::Right),
+                KeyCode::Down => Some(Direction::Down),
+                KeyCode::Left => Some(Direction::Left),
+                _ => None,
+            };
+            if let Some(direction) = direction {
+                self.selection.get_or_insert(self.game.cursor());
+                return self.game.apply_with_outcome(Command::MoveCursor(direction)).solved_now;
+            }
+        }
+
         if self.edit {
             if let KeyCode::Char(c) = key.code {
                 let command = match c {
+                    _ if self.selection.is_some() && !"NODAIX".contains(c) => self.on_selection_key(c),
+                    'V' => {
+                        self.selection = Some(self.game.cursor());
+                        Command::Noop
+                    }
+                    'F' => {
+                        self.flood_filling = true;
+                        Command::Noop
+                    }
                     'N' => Command::InsertRow(self.game.row() + 1),
                     'O' => Command::InsertRow(self.game.row()),
                     'D' => Command::RemoveRow(self.game.row()),
                     'A' => Command::InsertColumn(self.game.col() + 1),
                     'I' => Command::InsertColumn(self.game.col()),
                     'X' => Command::RemoveColumn(self.game.col()),
+                    'C' => {
+                        self.fill_empty_with_cross();
+                        Command::Noop
+                    }
+                    'P' => match &self.clipboard {
+                        Some(blocks) => Command::ReplaceWorldRegion(self.game.row(), self.game.col(), blocks.clone()),
+                        None => Command::Noop,
+                    },
                     _ => {
                         if let Ok(block) = c.to_string().parse() {
                             Command::ReplaceCursorBlock(block)
@@ -47,7 +266,15 @@ impl Game {
                     }
                 };
 
-                self.game.apply(command);
+                // world size changes, selection is not meaningful anymore
+                if matches!(
+                    command,
+                    Command::InsertRow(_) | Command::InsertColumn(_) | Command::RemoveRow(_) | Command::RemoveColumn(_)
+                ) {
+                    self.selection = None;
+                }
+
+                solved_now |= self.game.apply_with_outcome(command).solved_now;
             }
         }
 
@@ -56,17 +283,97 @@ impl Game {
             KeyCode::Char('l' | 'd') => Command::MoveCursor(Direction::Right),
             KeyCode::Char('j' | 's') => Command::MoveCursor(Direction::Down),
             KeyCode::Char('h' | 'a') => Command::MoveCursor(Direction::Left),
-            KeyCode::Char(' ') | KeyCode::Enter => Command::RotateCursorBlock,
+            // space is the empty block char when choosing block to fill selection
+            KeyCode::Char(' ') | KeyCode::Enter if self.selection.is_none() => Command::RotateCursorBlock,
             _ => Command::Noop,
         };
 
-        self.game.apply(command);
+        solved_now |= self.game.apply_with_outcome(command).solved_now;
+
+        solved_now
+    }
+
+    /// Get position of the block drawn in terminal cell at `(x, y)`, in last drawn layout.
+    pub fn block_at(&self, x: u16, y: u16) -> Option<Pos> {
+        WorldPainter::new(self.game.world(), &self.area.get()).block_at(x, y)
+    }
+
+    /// Move cursor to `pos` step by step, so the moves are recorded like key presses.
+    pub fn move_cursor_to(&mut self, pos: Pos) {
+        let Pos { row, col } = self.game.cursor();
+        let vertical = if pos.row < row { Direction::Up } else { Direction::Down };
+        let horizontal = if pos.col < col {
+            Direction::Left
+        } else {
+            Direction::Right
+        };
+        let moves = std::iter::repeat_n(vertical, pos.row.abs_diff(row))
+            .chain(std::iter::repeat_n(horizontal, pos.col.abs_diff(col)))
+            .map(Command::MoveCursor);
+        self.game.apply_batch(moves);
+    }
+
+    /// Handle a mouse event, left click moves cursor to the clicked block and turns it, right click only moves
+    /// cursor. Returns `true` if the world becomes solved by it.
+    pub fn on_mouse(&mut self, event: MouseEvent) -> bool {
+        let MouseEventKind::Down(button) = event.kind else {
+            return false;
+        };
+        let Some(pos) = self.block_at(event.column, event.row) else {
+            return false;
+        };
+
+        self.move_cursor_to(pos);
+        button == MouseButton::Left && self.apply(Command::RotateCursorBlock)
+    }
+
+    pub fn cursor(&self) -> Pos {
+        self.game.cursor()
+    }
+
+    pub fn game(&self) -> &connex::Game {
+        &self.game
+    }
+
+    pub fn world(&self) -> &World {
+        self.game.world()
     }
 
     pub fn solved(&self) -> bool {
         self.game.solved()
     }
 
+    /// Describe cursor, solved state and world in text, see [`World::to_accessible_string`].
+    pub fn describe(&self) -> String {
+        let Pos { row, col } = self.game.cursor();
+        let state = match self.game.world().solved_state() {
+            SolvedState::Solved => "solved",
+            SolvedState::Empty => "empty",
+            SolvedState::Misfit => "not solved",
+        };
+        let selection = match self.selection() {
+            Some((top, left, height, width)) => format!(
+                ", selecting rows {} to {}, columns {} to {}",
+                top + 1,
+                top + height,
+                left + 1,
+                left + width
+            ),
+            None => String::new(),
+        };
+        let flood_filling = if self.flood_filling {
+            ", type a block char to flood fill"
+        } else {
+            ""
+        };
+        format!(
+            "Cursor at row {}, column {}, {state}{selection}{flood_filling}\n{}",
+            row + 1,
+            col + 1,
+            self.game.world().to_accessible_string()
+        )
+    }
+
     pub fn into_inner(self) -> connex::Game {
         self.game
     }
@@ -79,7 +386,7 @@ impl Game {
 
         // else only highlight selected block
 
-        let (row, col) = self.game.cursor();
+        let Pos { row, col } = self.game.cursor();
 
         i == row && j == col
     }
@@ -92,7 +399,7 @@ impl Game {
 
         // normal mode, only selected block has boundary
 
-        let (row, col) = self.game.cursor();
+        let Pos { row, col } = self.game.cursor();
 
         i == row && j == col
     }
@@ -100,12 +407,185 @@ impl Game {
 
 impl Widget for &Game {
     fn render(self, area: tui::layout::Rect, buf: &mut tui::buffer::Buffer) {
+        self.area.set(area);
+        // canvas panics on empty area, e.g. when terminal is too small
+        if area.area() == 0 {
+            return;
+        }
         let painter = WorldPainter::new(self.game.world(), &area);
+        // locally correct blocks glow before the whole puzzle is solved
+        let solved_cells: HashSet<_> = if self.edit {
+            HashSet::new()
+        } else {
+            self.game.world().solved_cells().into_iter().collect()
+        };
+        // editor marks blocks pass to the outside, they make the level unsolvable
+        let border_violations: HashSet<_> = if self.edit {
+            self.game
+                .world()
+                .border_passability_violations()
+                .into_iter()
+                .map(|(pos, _)| pos)
+                .collect()
+        } else {
+            HashSet::new()
+        };
         let canvas = Canvas::default()
             .block(Block::default().borders(Borders::NONE))
-            .paint(|ctx| painter.draw(ctx, |i, j| self.need_highlight(i, j), |i, j| self.need_boundary(i, j)))
+            .paint(|ctx| {
+                if let Some(overlay) = &self.overlay {
+                    if overlay.size() == self.game.world().size() {
+                        painter.draw_world(ctx, overlay, Color::DarkGray);
+                    }
+                }
+                painter.draw_colored(
+                    ctx,
+                    |i, j| {
+                        if self.in_selection(i, j) || self.marked == Some(Pos::new(i, j)) {
+                            Some(Color::Yellow)
+                        } else if border_violations.contains(&Pos::new(i, j)) && !self.need_highlight(i, j) {
+                            Some(Color::Red)
+                        } else {
+                            (self.need_highlight(i, j) || solved_cells.contains(&Pos::new(i, j)))
+                                .then_some(Color::Green)
+                        }
+                    },
+                    |i, j| self.need_boundary(i, j),
+                )
+            })
             .x_bounds(painter.x_bound())
             .y_bounds(painter.y_bound());
         canvas.render(area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tui::{buffer::Buffer, style::Color};
+
+    use super::*;
+    use crate::testing::{assert_snapshot, buffer_text, drawn_cells, render};
+
+    fn game(s: &str) -> Game {
+        Game::new(connex::Game::new(s.parse().unwrap()))
+    }
+
+    fn colored_cells(buffer: &Buffer, color: Color) -> usize {
+        buffer
+            .content()
+            .iter()
+            .filter(|cell| cell.symbol.trim() != "" && cell.fg == color)
+            .count()
+    }
+
+    #[test]
+    fn solved_world_is_all_green() {
+        let game = game("2,2\n79\n13\n");
+        let buffer = render(&game, 20, 10);
+
+        assert!(drawn_cells(&buffer) > 0);
+        assert_eq!(colored_cells(&buffer, Color::Green), drawn_cells(&buffer));
+    }
+
+    #[test]
+    fn unsolved_world_only_highlights_cursor_and_fitting_blocks() {
+        // cursor at top-left, nothing fits
+        let game = game("2,2\n91\n37\n");
+        let buffer = render(&game, 20, 10);
+
+        let green = colored_cells(&buffer, Color::Green);
+        assert!(green > 0);
+        assert!(green < drawn_cells(&buffer));
+        // highlight is in the top-left quarter, where the cursor block is drawn
+        for (i, cell) in buffer.content().iter().enumerate() {
+            let (x, y) = (i as u16 % 20, i as u16 / 20);
+            if cell.symbol.trim() != "" && cell.fg == Color::Green {
+                assert!(x < 10 && y < 5, "unexpected highlight at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn empty_area_draws_nothing() {
+        let game = game("2,2\n79\n13\n");
+        let buffer = render(&game, 0, 0);
+        assert_eq!(drawn_cells(&buffer), 0);
+    }
+
+    // square, wide and narrow terminals
+    const SIZES: [(u16, u16); 3] = [(20, 10), (41, 9), (13, 12)];
+
+    fn assert_game_snapshot(name: &str, game: &Game) {
+        let text: String = SIZES
+            .iter()
+            .map(|&(width, height)| format!("# {width}x{height}\n{}", buffer_text(&render(game, width, height))))
+            .collect();
+        assert_snapshot(name, &text);
+    }
+
+    #[test]
+    fn snapshots() {
+        let worlds = [
+            ("loop", "v1\n2,2\n79\n13\n"),
+            ("loop_unsolved", "v1\n2,2\n91\n37\n"),
+            ("mixed", "v1\n3,4\n>-89\n 75/\n^13<\n"),
+        ];
+
+        for (name, world) in worlds {
+            let mut game = game(world);
+            assert_game_snapshot(&format!("{name}_top_left"), &game);
+
+            let (height, width) = game.world().size();
+            game.move_cursor_to(Pos::new(height.get() - 1, width.get() - 1));
+            assert_game_snapshot(&format!("{name}_bottom_right"), &game);
+        }
+    }
+
+    /// Size of the smallest rectangle containing all drawn cells, as `(width, height)`.
+    fn drawn_size(buffer: &Buffer) -> (u16, u16) {
+        let width = buffer.area.width;
+        let drawn: Vec<_> = (0..)
+            .zip(buffer.content())
+            .filter(|(_, cell)| cell.symbol.trim() != "")
+            .map(|(i, _)| (i % width, i / width))
+            .collect();
+        let span = |values: Vec<u16>| values.iter().max().unwrap() - values.iter().min().unwrap() + 1;
+        (
+            span(drawn.iter().map(|&(x, _)| x).collect()),
+            span(drawn.iter().map(|&(_, y)| y).collect()),
+        )
+    }
+
+    #[test]
+    fn rotate_key_changes_drawn_dimensions() {
+        let mut game = game("v1\n2,4\n>-89\n^13<\n");
+        game.set_edit(true);
+        // a terminal cell is about twice as tall as wide, so a square block takes twice as many columns as rows
+        let (width, height) = drawn_size(&render(&game, 40, 40));
+        assert!(width > 3 * height, "{width}x{height}");
+
+        assert_eq!(game.on_transform_key(KeyCode::Char('r')), Some("Rotated clockwise"));
+        assert_eq!(game.world().size(), (4.try_into().unwrap(), 2.try_into().unwrap()));
+        let rotated = drawn_size(&render(&game, 40, 40));
+        assert!(rotated.0 <= rotated.1 && rotated.1 > height, "{rotated:?}");
+
+        // rotating back draws the same as before
+        assert_eq!(
+            game.on_transform_key(KeyCode::Char('R')),
+            Some("Rotated counterclockwise")
+        );
+        assert_eq!(drawn_size(&render(&game, 40, 40)), (width, height));
+
+        // other keys change nothing
+        assert_eq!(game.on_transform_key(KeyCode::Char('x')), None);
+        assert_eq!(drawn_size(&render(&game, 40, 40)), (width, height));
+    }
+
+    #[test]
+    fn edit_mode_snapshot() {
+        let mut game = game("v1\n3,4\n>-89\n 75/\n^13<\n");
+        game.set_edit(true);
+        game.move_cursor_to(Pos::new(1, 1));
+        assert_game_snapshot("mixed_edit", &game);
+    }
+}
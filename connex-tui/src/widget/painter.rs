@@ -4,7 +4,10 @@ use tui::{
     widgets::canvas::{Context, Line},
 };
 
-use connex::{Block, Direction, World};
+use connex::{
+    shape::{self, BlockLine},
+    Pos, World,
+};
 
 #[derive(Default, Debug, Clone)]
 struct LayoutInfo {
@@ -60,80 +63,6 @@ fn layout(rect: &Rect, world: &World) -> LayoutInfo {
     info
 }
 
-type BlockLine = ((u8, u8), (u8, u8));
-
-const BL_EP_UP: BlockLine = ((0, 2), (1, 2));
-const BL_EP_RIGHT: BlockLine = ((2, 3), (2, 4));
-const BL_EP_DOWN: BlockLine = ((3, 2), (4, 2));
-const BL_EP_LEFT: BlockLine = ((2, 0), (2, 1));
-const BL_TURN_LEFT_UP: BlockLine = ((1, 2), (2, 1));
-const BL_TURN_RIGHT_UP: BlockLine = ((2, 3), (1, 2));
-const BL_TURN_RIGHT_DOWN: BlockLine = ((3, 2), (2, 3));
-const BL_TURN_LEFT_DOWN: BlockLine = ((2, 1), (3, 2));
-
-const BL_TURN_ALL: &[BlockLine] = &[BL_TURN_LEFT_UP, BL_TURN_RIGHT_UP, BL_TURN_RIGHT_DOWN, BL_TURN_LEFT_DOWN];
-const BL_EP_ALL: &[BlockLine] = &[BL_EP_UP, BL_EP_RIGHT, BL_EP_DOWN, BL_EP_LEFT];
-
-const BL_THROUGH_UP_DOWN: BlockLine = ((0, 2), (4, 2));
-const BL_THROUGH_LEFT_RIGHT: BlockLine = ((2, 0), (2, 4));
-
-const BL_LEFT_UP_ARC: &[BlockLine] = &[BL_EP_LEFT, BL_EP_UP, BL_TURN_LEFT_UP];
-const BL_RIGHT_UP_ARC: &[BlockLine] = &[BL_EP_RIGHT, BL_EP_UP, BL_TURN_RIGHT_UP];
-const BL_RIGHT_DOWN_ARC: &[BlockLine] = &[BL_EP_RIGHT, BL_EP_DOWN, BL_TURN_RIGHT_DOWN];
-const BL_LEFT_DOWN_ARC: &[BlockLine] = &[BL_EP_LEFT, BL_EP_DOWN, BL_TURN_LEFT_DOWN];
-
-const BL_UP_FORK: &[BlockLine] = &[
-    BL_EP_RIGHT,
-    BL_TURN_RIGHT_DOWN,
-    BL_EP_DOWN,
-    BL_TURN_LEFT_DOWN,
-    BL_EP_LEFT,
-];
-const BL_RIGHT_FORK: &[BlockLine] = &[BL_EP_UP, BL_TURN_LEFT_UP, BL_EP_LEFT, BL_TURN_LEFT_DOWN, BL_EP_DOWN];
-const BL_DOWN_FORK: &[BlockLine] = &[BL_EP_LEFT, BL_TURN_LEFT_UP, BL_EP_UP, BL_TURN_RIGHT_UP, BL_EP_RIGHT];
-const BL_LEFT_FORK: &[BlockLine] = &[BL_EP_UP, BL_TURN_RIGHT_UP, BL_EP_RIGHT, BL_TURN_RIGHT_DOWN, BL_EP_DOWN];
-
-const BL_BOUNDARY_UP: BlockLine = ((0, 0), (0, 4));
-const BL_BOUNDARY_RIGHT: BlockLine = ((0, 4), (4, 4));
-const BL_BOUNDARY_DOWN: BlockLine = ((4, 0), (4, 4));
-const BL_BOUNDARY_LEFT: BlockLine = ((0, 0), (4, 0));
-const BL_BOUNDARY: &[BlockLine] = &[BL_BOUNDARY_UP, BL_BOUNDARY_RIGHT, BL_BOUNDARY_DOWN, BL_BOUNDARY_LEFT];
-
-fn common_lines(block: &Block) -> &[&[BlockLine]] {
-    match block {
-        Block::Endpoint(_) => &[BL_TURN_ALL],
-        Block::Cross => &[BL_TURN_ALL, BL_EP_ALL],
-        _ => &[],
-    }
-}
-
-fn side_lines(block: &Block) -> &[BlockLine] {
-    match block {
-        Block::Empty => &[],
-        Block::Endpoint(s) => match s {
-            Direction::Up => &[BL_EP_UP],
-            Direction::Right => &[BL_EP_RIGHT],
-            Direction::Down => &[BL_EP_DOWN],
-            Direction::Left => &[BL_EP_LEFT],
-        },
-        Block::Through(Direction::Up | Direction::Down) => &[BL_THROUGH_UP_DOWN],
-        Block::Through(Direction::Left | Direction::Right) => &[BL_THROUGH_LEFT_RIGHT],
-        Block::Turn(s) => match s {
-            Direction::Up => BL_RIGHT_UP_ARC,
-            Direction::Right => BL_RIGHT_DOWN_ARC,
-            Direction::Down => BL_LEFT_DOWN_ARC,
-            Direction::Left => BL_LEFT_UP_ARC,
-        },
-        Block::Fork(s) => match s {
-            Direction::Up => BL_UP_FORK,
-            Direction::Right => BL_RIGHT_FORK,
-            Direction::Down => BL_DOWN_FORK,
-            Direction::Left => BL_LEFT_FORK,
-        },
-        Block::Cross => &[],
-    }
-}
-
 #[derive(Debug)]
 struct BlockPainter<'a, 'b> {
     canvas: &'a connex::World,
@@ -153,44 +82,65 @@ impl<'a, 'b> BlockPainter<'a, 'b> {
     }
 
     fn draw<'i, I: IntoIterator<Item = &'i BlockLine>>(
-        &self, ctx: &mut Context, row: usize, col: usize, lines: I, highlight: bool,
+        &self, ctx: &mut Context, row: usize, col: usize, lines: I, highlight: Option<Color>,
     ) {
         let x_offset = self.layout.x_offset + self.layout.block_size * col as u64;
         let y_offset = self.layout.y_offset + self.layout.block_size * row as u64;
 
-        let color = if highlight { Color::Green } else { Color::Reset };
+        let color = highlight.unwrap_or(Color::Reset);
 
         for point in lines {
             ctx.draw(&self.create_line(x_offset, y_offset, point, color))
         }
     }
 
-    pub fn draw_block(&self, ctx: &mut Context, row: usize, col: usize, highlight: bool) {
-        let block = self.canvas.get(row, col).unwrap();
-
-        let lines = common_lines(block)
-            .iter()
-            .flat_map(|a| a.iter())
-            .chain(side_lines(block).iter());
-
-        self.draw(ctx, row, col, lines, highlight)
+    pub fn draw_block(&self, ctx: &mut Context, row: usize, col: usize, highlight: Option<Color>) {
+        self.draw(ctx, row, col, shape::block_lines(&self.canvas[(row, col)]), highlight)
     }
 
-    pub fn draw_boundary(&self, ctx: &mut Context, row: usize, col: usize, highlight: bool) {
-        self.draw(ctx, row, col, BL_BOUNDARY, highlight)
+    pub fn draw_boundary(&self, ctx: &mut Context, row: usize, col: usize, highlight: Option<Color>) {
+        self.draw(ctx, row, col, shape::BOUNDARY, highlight)
     }
 }
 
 #[derive(Debug)]
 pub struct WorldPainter<'a> {
     world: &'a connex::World,
+    rect: Rect,
     layout: LayoutInfo,
 }
 
 impl<'a> WorldPainter<'a> {
     pub fn new(world: &'a connex::World, rect: &Rect) -> Self {
         let layout = layout(rect, world);
-        Self { world, layout }
+        Self {
+            world,
+            rect: *rect,
+            layout,
+        }
+    }
+
+    /// Get position of the block drawn in terminal cell at `(x, y)`, return None if it's outside of the world.
+    pub fn block_at(&self, x: u16, y: u16) -> Option<Pos> {
+        if self.rect.area() == 0 || !(self.rect.left()..self.rect.right()).contains(&x) {
+            return None;
+        }
+        if !(self.rect.top()..self.rect.bottom()).contains(&y) {
+            return None;
+        }
+
+        // use center of the cell, canvas coordinates are scaled to fill the rect
+        let canvas_x = (f64::from(x - self.rect.x) + 0.5) / f64::from(self.rect.width) * self.layout.x_bound as f64;
+        let canvas_y = (f64::from(y - self.rect.y) + 0.5) / f64::from(self.rect.height) * self.layout.y_bound as f64;
+        let block_size = self.layout.block_size as f64;
+        let col = (canvas_x - self.layout.x_offset as f64) / block_size;
+        let row = (canvas_y - self.layout.y_offset as f64) / block_size;
+        if col < 0.0 || row < 0.0 {
+            return None;
+        }
+
+        let pos = Pos::new(row as usize, col as usize);
+        self.world.get(pos.row, pos.col).map(|_| pos)
     }
 
     pub fn x_bound(&self) -> [f64; 2] {
@@ -201,9 +151,24 @@ impl<'a> WorldPainter<'a> {
         [0.0, self.layout.y_bound as f64]
     }
 
-    pub fn draw<F1, F2>(&self, ctx: &mut Context, mut highlight_pred: F1, mut boundary_pred: F2)
+    /// Draw another world of the same size in given color, with the layout of this world.
+    pub fn draw_world(&self, ctx: &mut Context, world: &World, color: Color) {
+        let painter = BlockPainter {
+            canvas: world,
+            layout: &self.layout,
+        };
+
+        for i in 0..world.height().get() {
+            for j in 0..world.width().get() {
+                painter.draw_block(ctx, i, j, Some(color));
+            }
+        }
+    }
+
+    /// Draw world, each block is drawn in the color `highlight_pred` returns, or default color if None.
+    pub fn draw_colored<F1, F2>(&self, ctx: &mut Context, mut highlight_pred: F1, mut boundary_pred: F2)
     where
-        F1: FnMut(usize, usize) -> bool,
+        F1: FnMut(usize, usize) -> Option<Color>,
         F2: FnMut(usize, usize) -> bool,
     {
         let painter = BlockPainter {
@@ -220,28 +185,22 @@ impl<'a> WorldPainter<'a> {
 
                 painter.draw_block(ctx, i, j, highlight);
                 if boundary_pred(i, j) {
-                    if highlight {
-                        &mut highlight_boundaries
-                    } else {
-                        &mut normal_boundaries
+                    match highlight {
+                        Some(color) => highlight_boundaries.push((i, j, color)),
+                        None => normal_boundaries.push((i, j)),
                     }
-                    .push((i, j));
                 }
             }
         }
 
         // draw highlight boundary after normal boundary to make sure highlight color is on top.
 
-        if !normal_boundaries.is_empty() {
-            for (row, col) in normal_boundaries {
-                painter.draw_boundary(ctx, row, col, false);
-            }
+        for (row, col) in normal_boundaries {
+            painter.draw_boundary(ctx, row, col, None);
         }
 
-        if !highlight_boundaries.is_empty() {
-            for (row, col) in highlight_boundaries {
-                painter.draw_boundary(ctx, row, col, true);
-            }
+        for (row, col, color) in highlight_boundaries {
+            painter.draw_boundary(ctx, row, col, Some(color));
         }
     }
 }
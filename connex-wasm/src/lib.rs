@@ -0,0 +1,278 @@
+#![warn(clippy::all)]
+#![warn(missing_docs, missing_debug_implementations)]
+#![deny(warnings)]
+#![forbid(unsafe_code)]
+
+//! # Connex WASM
+//!
+//! Expose connex game logic to JavaScript, build it with `wasm-pack build --target web`.
+
+use connex::{Block, Command, Direction, Game, Pos, World};
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+fn direction_name(dir: Direction) -> &'static str {
+    match dir {
+        Direction::Up => "up",
+        Direction::Right => "right",
+        Direction::Down => "down",
+        Direction::Left => "left",
+    }
+}
+
+fn parse_direction(s: &str) -> Result<Direction, JsError> {
+    match s {
+        "up" => Ok(Direction::Up),
+        "right" => Ok(Direction::Right),
+        "down" => Ok(Direction::Down),
+        "left" => Ok(Direction::Left),
+        _ => Err(JsError::new(&format!("invalid direction: {s}"))),
+    }
+}
+
+/// A block of world.
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone)]
+pub struct JsBlock(Block);
+
+#[wasm_bindgen]
+impl JsBlock {
+    /// Block kind, one of `empty`, `endpoint`, `through`, `turn`, `fork` and `cross`.
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        match self.0 {
+            Block::Empty => "empty",
+            Block::Endpoint(_) => "endpoint",
+            Block::Through(_) => "through",
+            Block::Turn(_) => "turn",
+            Block::Fork(_) => "fork",
+            Block::Cross => "cross",
+        }
+        .into()
+    }
+
+    /// Direction of block, see [`Block`] for the meaning of it, `undefined` for empty and cross.
+    #[wasm_bindgen(getter)]
+    pub fn direction(&self) -> Option<String> {
+        match self.0 {
+            Block::Empty | Block::Cross => None,
+            Block::Endpoint(dir) | Block::Through(dir) | Block::Turn(dir) | Block::Fork(dir) => {
+                Some(direction_name(dir).into())
+            }
+        }
+    }
+
+    /// Character of block in text format.
+    #[wasm_bindgen(getter)]
+    pub fn text(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Box drawing character of block.
+    #[wasm_bindgen(getter, js_name = boxChar)]
+    pub fn box_char(&self) -> String {
+        self.0.box_char().into()
+    }
+}
+
+/// A game world.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct JsWorld(World);
+
+#[wasm_bindgen]
+impl JsWorld {
+    /// Parse world from its text format.
+    #[wasm_bindgen(constructor)]
+    pub fn new(text: &str) -> Result<JsWorld, JsError> {
+        text.parse().map(Self).map_err(JsError::from)
+    }
+
+    /// Height of world.
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> usize {
+        self.0.height().get()
+    }
+
+    /// Width of world.
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> usize {
+        self.0.width().get()
+    }
+
+    /// Get block at given position, `undefined` if out of range.
+    #[wasm_bindgen(js_name = getBlock)]
+    pub fn get_block(&self, row: usize, col: usize) -> Option<JsBlock> {
+        self.0.get(row, col).copied().map(JsBlock)
+    }
+
+    /// Check if world is solved.
+    pub fn solved(&self) -> bool {
+        self.0.solved()
+    }
+
+    /// Shuffle all blocks, same seed gives same result.
+    pub fn shuffle(&mut self, seed: u64) {
+        self.0.shuffle_seeded(seed);
+    }
+
+    /// Turn block at given position clockwise.
+    pub fn rotate(&mut self, row: usize, col: usize) -> Result<(), JsError> {
+        self.0
+            .get_mut(row, col)
+            .map(Block::rotate)
+            .ok_or_else(|| JsError::new(&format!("block index ({row}, {col}) out of range")))
+    }
+
+    /// Text format of world as UTF-8 bytes.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_string().into_bytes()
+    }
+
+    /// Text format of world.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_text(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// JSON format of world, `{"height": H, "width": W, "rows": ["...", ...]}`, rows use text format characters.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        let rows: Vec<_> = self
+            .0
+            .rows()
+            .map(|row| row.iter().map(ToString::to_string).collect::<String>())
+            .collect();
+        json!({
+            "height": self.height(),
+            "width": self.width(),
+            "rows": rows,
+        })
+        .to_string()
+    }
+}
+
+/// A game control command.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct JsCommand(Command);
+
+#[wasm_bindgen]
+impl JsCommand {
+    /// Parse command from its notation, e.g. `mr`, `r`, `r2c3`, rows and columns are 1-based.
+    pub fn parse(notation: &str) -> Result<JsCommand, JsError> {
+        notation
+            .parse()
+            .map(Self)
+            .map_err(|()| JsError::new(&format!("invalid notation: {notation}")))
+    }
+
+    /// Reset game world.
+    pub fn reset(world: &JsWorld) -> JsCommand {
+        Self(Command::Reset(world.0.clone()))
+    }
+
+    /// Move cursor one block towards given direction, one of `up`, `right`, `down` and `left`.
+    #[wasm_bindgen(js_name = moveCursor)]
+    pub fn move_cursor(direction: &str) -> Result<JsCommand, JsError> {
+        parse_direction(direction).map(|dir| Self(Command::MoveCursor(dir)))
+    }
+
+    /// Turn block under cursor clockwise.
+    #[wasm_bindgen(js_name = rotateCursorBlock)]
+    pub fn rotate_cursor_block() -> JsCommand {
+        Self(Command::RotateCursorBlock)
+    }
+
+    /// Turn block at given position clockwise.
+    #[wasm_bindgen(js_name = rotateBlock)]
+    pub fn rotate_block(row: usize, col: usize) -> JsCommand {
+        Self(Command::RotateBlock(Pos::new(row, col)))
+    }
+
+    /// Notation of command.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_notation(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A playable game.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct JsGame(Game);
+
+#[wasm_bindgen]
+impl JsGame {
+    /// Create a new game.
+    #[wasm_bindgen(constructor)]
+    pub fn new(world: &JsWorld) -> JsGame {
+        Self(Game::new(world.0.clone()))
+    }
+
+    /// Apply a command in this game.
+    #[wasm_bindgen(js_name = applyCommand)]
+    pub fn apply_command(&mut self, command: &JsCommand) {
+        self.0.apply(command.0.clone());
+    }
+
+    /// Check if game world is solved.
+    pub fn solved(&self) -> bool {
+        self.0.solved()
+    }
+
+    /// Row of cursor.
+    #[wasm_bindgen(getter)]
+    pub fn row(&self) -> usize {
+        self.0.row()
+    }
+
+    /// Column of cursor.
+    #[wasm_bindgen(getter)]
+    pub fn col(&self) -> usize {
+        self.0.col()
+    }
+
+    /// Get a copy of current game world.
+    pub fn world(&self) -> JsWorld {
+        JsWorld(self.0.world().clone())
+    }
+
+    /// Applied commands in notation, separated by spaces.
+    pub fn notation(&self) -> String {
+        self.0.to_notation_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_bindings() {
+        let mut world = JsWorld::new("v1\n1,2\n^<\n").unwrap();
+        assert_eq!((world.height(), world.width()), (1, 2));
+        assert!(!world.solved());
+
+        world.rotate(0, 0).unwrap();
+        assert!(world.solved());
+        assert_eq!(world.to_bytes(), b"v1\n1,2\n><\n");
+
+        let mut a = JsWorld::new("v1\n2,2\n79\n13\n").unwrap();
+        let mut b = a.clone();
+        a.shuffle(42);
+        b.shuffle(42);
+        assert_eq!(a.to_bytes(), b.to_bytes());
+    }
+
+    #[test]
+    fn game_bindings() {
+        let world = JsWorld::new("v1\n1,2\n^<\n").unwrap();
+        let mut game = JsGame::new(&world);
+        game.apply_command(&JsCommand::rotate_cursor_block());
+        assert!(game.solved());
+        assert_eq!(game.world().to_text(), "v1\n1,2\n><\n");
+        assert_eq!(game.notation(), "r");
+    }
+}
@@ -0,0 +1,38 @@
+// Build the package first: `wasm-pack build --target web connex-wasm`,
+// then serve the `connex-wasm` directory with any static file server and open `www/index.html`.
+import init, { JsCommand, JsGame, JsWorld } from "../pkg/connex_wasm.js";
+
+const LEVEL = `3,3
+789
+456
+123
+`;
+
+await init();
+
+const game = new JsGame(new JsWorld(LEVEL));
+const board = document.getElementById("board");
+const status = document.getElementById("status");
+
+function render() {
+    const world = game.world();
+    board.replaceChildren();
+    board.style.gridTemplateColumns = `repeat(${world.width}, auto)`;
+
+    for (let row = 0; row < world.height; row++) {
+        for (let col = 0; col < world.width; col++) {
+            const button = document.createElement("button");
+            button.textContent = world.getBlock(row, col).boxChar;
+            button.onclick = () => {
+                game.applyCommand(JsCommand.rotateBlock(row, col));
+                render();
+            };
+            board.appendChild(button);
+        }
+    }
+
+    board.classList.toggle("solved", game.solved());
+    status.textContent = game.solved() ? `Solved! Moves: ${game.notation()}` : "Click blocks to rotate them.";
+}
+
+render();
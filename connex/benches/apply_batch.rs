@@ -0,0 +1,40 @@
+//! Applying commands one by one vs in a batch, which checks solved state only once.
+//!
+//! Rotations update solved state incrementally either way, so the batch mainly helps commands which need a full
+//! check, like replacing blocks.
+
+mod util;
+
+use connex::{generator::generate_seeded, Command, Game, Pos};
+
+const COMMANDS: usize = 100;
+
+fn main() {
+    for size in [10, 30, 100] {
+        let n = size.try_into().unwrap();
+        let world = generate_seeded(n, n, 42);
+        // spread over the world, a block may be rotated more than once
+        let positions: Vec<Pos> = (0..COMMANDS).map(|i| Pos::new(i * 7 % size, i * 13 % size)).collect();
+
+        for (kind, command) in [
+            ("rotate", Command::RotateBlock as fn(Pos) -> Command),
+            ("replace", |pos| Command::ReplaceBlock(pos, connex::Block::Cross)),
+        ] {
+            let commands: Vec<Command> = positions.iter().map(|&pos| command(pos)).collect();
+
+            let one_by_one = util::bench(&format!("apply/{kind}/{size}x{size}/{COMMANDS}"), || {
+                let mut game = Game::new(world.clone());
+                for command in commands.iter().cloned() {
+                    game.apply(command);
+                }
+                game
+            });
+            let batch = util::bench(&format!("apply_batch/{kind}/{size}x{size}/{COMMANDS}"), || {
+                let mut game = Game::new(world.clone());
+                game.apply_batch(commands.iter().cloned());
+                game
+            });
+            util::speedup("speedup", one_by_one, batch);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Random bot vs greedy bot on the built-in levels.
+
+mod util;
+
+use connex::{
+    bot::{simulate, Bot, GreedyBot, RandomBot},
+    World,
+};
+
+const MAX_STEPS: usize = 10_000;
+
+/// Bench a bot on a level, bots are created for each run so random ones play the same every time.
+fn bench_bot<B: Bot>(bot_name: &str, level: &str, world: &World, bot: impl Fn() -> B) {
+    let (game, steps) = simulate(world.clone(), &bot(), MAX_STEPS);
+    let name = format!("simulate/{bot_name}/{level} ({steps} steps, solved: {})", game.solved());
+    util::bench(&name, || simulate(world.clone(), &bot(), MAX_STEPS));
+}
+
+fn main() {
+    for level in connex_levels::ALL {
+        let mut world: World = level.content.parse().unwrap();
+        world.shuffle_seeded(42);
+
+        bench_bot("random", level.name, &world, || RandomBot::new(42));
+        bench_bot("greedy", level.name, &world, || GreedyBot);
+    }
+}
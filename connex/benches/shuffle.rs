@@ -0,0 +1,26 @@
+//! Sequential vs parallel shuffle.
+
+mod util;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use connex::generator::generate_seeded;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn main() {
+    for size in [50, 100] {
+        let n = size.try_into().unwrap();
+        let mut world = generate_seeded(n, n, 42);
+
+        let sequential = util::bench(&format!("shuffle/{size}x{size}"), || {
+            world.shuffle(ChaCha8Rng::seed_from_u64(42));
+        });
+        // RNGs of batches must have different seeds
+        let seed = AtomicU64::new(0);
+        let parallel = util::bench(&format!("shuffle_par/{size}x{size}"), || {
+            world.shuffle_par(|| ChaCha8Rng::seed_from_u64(seed.fetch_add(1, Ordering::Relaxed)));
+        });
+        util::speedup("speedup", sequential, parallel);
+    }
+}
@@ -0,0 +1,60 @@
+//! Solved state tracked incrementally by game vs checking the whole world after each rotation.
+//!
+//! [`World::solved`] stops at the first misfit block, so it's cheap for a scrambled world, the nearly solved world
+//! with only its last block rotated is the worst case of it. [`World::count_correct_edges`] always scans the whole
+//! world, it's what game did before tracking edges incrementally.
+
+mod util;
+
+use connex::{generator::generate_solved, Command, Game, Pos, World};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const ROTATIONS: usize = 1000;
+
+fn bench_case(name: &str, world: &World, positions: &[Pos]) {
+    let mut full_world = world.clone();
+    let full = util::bench(&format!("world_solved/{name}"), || {
+        positions.iter().fold(false, |solved, &pos| {
+            full_world[pos].rotate();
+            solved ^ full_world.solved()
+        })
+    });
+
+    let mut count_world = world.clone();
+    let count = util::bench(&format!("count_correct_edges/{name}"), || {
+        positions
+            .iter()
+            .map(|&pos| {
+                count_world[pos].rotate();
+                count_world.count_correct_edges()
+            })
+            .sum::<usize>()
+    });
+
+    let mut game = Game::new(world.clone());
+    let incremental = util::bench(&format!("game_solved/{name}"), || {
+        positions.iter().fold(false, |solved, &pos| {
+            game.apply(Command::RotateBlock(pos));
+            solved ^ game.solved()
+        })
+    });
+
+    util::speedup("speedup over world_solved", full, incremental);
+    util::speedup("speedup over count_correct_edges", count, incremental);
+}
+
+fn main() {
+    for size in [10, 50, 100] {
+        let n = size.try_into().unwrap();
+        let solved = generate_solved(n, n, &mut ChaCha8Rng::seed_from_u64(42));
+
+        let mut scrambled = solved.clone();
+        scrambled.shuffle_seeded(42);
+        let spread: Vec<Pos> = (0..ROTATIONS).map(|i| Pos::new(i * 7 % size, i * 13 % size)).collect();
+        bench_case(&format!("scrambled/{size}x{size}/{ROTATIONS}"), &scrambled, &spread);
+
+        let last = vec![Pos::new(size - 1, size - 1); ROTATIONS];
+        bench_case(&format!("nearly_solved/{size}x{size}/{ROTATIONS}"), &solved, &last);
+    }
+}
@@ -0,0 +1,45 @@
+//! Timing helpers shared by benchmarks, run them with `cargo bench -p connex --all-features`.
+//!
+//! No benchmark framework is used so benchmarks build offline and for `no_std` users too. They only run once as a
+//! smoke test without `--bench`, like `cargo test --benches` does.
+
+// every benchmark includes this module, but only uses some of it
+#![allow(dead_code)]
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Time to repeat each benchmark for.
+const TARGET: Duration = Duration::from_secs(1);
+
+/// Run `f` repeatedly, print and return average time of one run.
+pub fn bench<T>(name: &str, mut f: impl FnMut() -> T) -> Duration {
+    let measuring = std::env::args().any(|arg| arg == "--bench");
+
+    // warm up, and the only run of a smoke test
+    black_box(f());
+    if !measuring {
+        return Duration::ZERO;
+    }
+
+    let start = Instant::now();
+    let mut runs = 0;
+    while start.elapsed() < TARGET {
+        black_box(f());
+        runs += 1;
+    }
+
+    let average = start.elapsed() / runs;
+    println!("{name:<48} {average:>12.2?}");
+    average
+}
+
+/// Print how many times `faster` is faster than `baseline` under `name`, nothing is printed for a smoke test.
+pub fn speedup(name: &str, baseline: Duration, faster: Duration) {
+    if !faster.is_zero() {
+        let times = baseline.as_secs_f64() / faster.as_secs_f64();
+        println!("  {name:<46} {times:>11.2}x");
+    }
+}
@@ -0,0 +1,17 @@
+//! Shuffle a level with seeded helpers only, which need no OS randomness, so it works the same on
+//! `wasm32-unknown-unknown` without `getrandom` wiring.
+//!
+//! Run with `cargo run -p connex --features random --example wasm_seed -- <seed>`.
+
+use connex::{Block, World};
+
+fn main() {
+    let seed = std::env::args().nth(1).and_then(|s| s.parse().ok()).unwrap_or(42);
+
+    let mut world: World = "79\n13".parse().unwrap();
+    world.shuffle_seeded(seed);
+    print!("{world}");
+
+    let block = Block::random_seeded(seed, false);
+    println!("random block of seed {seed}: {block}");
+}
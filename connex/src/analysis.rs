@@ -0,0 +1,728 @@
+//! Analysis of game worlds.
+
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{Block, Direction, Pos, Solutions, SolveStep, World};
+
+/// Rough difficulty level of a world, see [`World::estimate_difficulty`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(missing_docs)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Easy => "easy",
+            Self::Normal => "normal",
+            Self::Hard => "hard",
+        })
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Self::Easy),
+            "normal" => Ok(Self::Normal),
+            "hard" => Ok(Self::Hard),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Max solver nodes [`difficulty`] will visit.
+pub const DIFFICULTY_NODE_LIMIT: u64 = 1_000_000;
+
+/// Estimate difficulty of a world.
+///
+/// The score is the number of nodes the solver visits to find the solution and prove it's unique, capped by
+/// [`DIFFICULTY_NODE_LIMIT`]. Search stops at the second solution, so multi-solution worlds score lower.
+///
+/// For worlds with a unique solution, the whole search tree is visited, so the score does not depend on current
+/// block orientations.
+pub fn difficulty(world: &World) -> u32 {
+    let mut solutions = world.solutions();
+    let mut found = 0;
+
+    while found < 2 {
+        match solutions.resume(DIFFICULTY_NODE_LIMIT - solutions.nodes()) {
+            SolveStep::Found(_) => found += 1,
+            SolveStep::Paused | SolveStep::Exhausted => break,
+        }
+    }
+
+    solutions.nodes() as u32
+}
+
+/// Factors of a world's [`Difficulty`] rating, see [`World::difficulty_score`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DifficultyScore {
+    /// The rating.
+    pub difficulty: Difficulty,
+    /// Points the rating is made from, see [`World::estimate_difficulty`].
+    pub points: u8,
+    /// Count of non-empty blocks.
+    pub blocks: usize,
+    /// Count of [`Block::Fork`] and [`Block::Cross`] blocks.
+    pub forks_and_crosses: usize,
+    /// Solver nodes visited, see [`difficulty`].
+    pub solver_nodes: usize,
+    /// Blocks which need a decision of rotation, see [`World::count_rotation_classes`]. It's not rated, because
+    /// solver nodes already tell how hard those decisions are.
+    pub rotation_classes: usize,
+}
+
+impl DifficultyScore {
+    /// Explain the rating in one line, with points given by each factor, e.g.
+    /// `normal, 2 points: 30 blocks +1, 6 forks and crosses +0, 95 solver nodes +1, 22 rotation classes`.
+    pub fn explanation(&self) -> String {
+        let Self {
+            difficulty,
+            points,
+            blocks,
+            forks_and_crosses,
+            solver_nodes,
+            rotation_classes,
+        } = *self;
+
+        format!(
+            "{difficulty}, {points} points: {blocks} blocks +{}, {forks_and_crosses} forks and crosses +{}, \
+             {solver_nodes} solver nodes +{}, {rotation_classes} rotation classes",
+            self.block_points(),
+            self.fork_and_cross_points(),
+            self.solver_points(),
+        )
+    }
+
+    fn block_points(&self) -> u8 {
+        (self.blocks >= 25) as u8 + (self.blocks >= 64) as u8
+    }
+
+    fn fork_and_cross_points(&self) -> u8 {
+        (self.forks_and_crosses * 4 >= self.blocks) as u8
+    }
+
+    fn solver_points(&self) -> u8 {
+        (self.solver_nodes >= 3 * self.blocks) as u8 + (self.solver_nodes >= 6 * self.blocks) as u8
+    }
+}
+
+/// Symmetries a world has, see [`World::symmetries`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Symmetries {
+    /// Same after mirrored left to right, see [`World::flipped_horizontally`].
+    pub horizontal_mirror: bool,
+    /// Same after mirrored top to bottom, see [`World::flipped_vertically`].
+    pub vertical_mirror: bool,
+    /// Same after rotated 180 degrees.
+    pub rotation_180: bool,
+    /// Same after rotated 90 degrees, always false for non-square worlds.
+    pub rotation_90: bool,
+}
+
+/// Connected networks of a world, see [`World::components`].
+///
+/// Networks are numbered from 0 in row-major order of their first block, so the same world always gives the
+/// same numbers, renderers can use them to pick colors. It's a snapshot, build a new one after world changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentMap {
+    width: usize,
+    ids: Vec<Option<usize>>,
+    count: usize,
+}
+
+impl ComponentMap {
+    /// Get network index of block at `(row, col)`.
+    ///
+    /// Return None for empty blocks, blocks not connected to any other block, and out of range index.
+    pub fn component_of(&self, row: usize, col: usize) -> Option<usize> {
+        if col >= self.width {
+            return None;
+        }
+        self.ids.get(row * self.width + col).copied().flatten()
+    }
+
+    /// Count of networks.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Check if there is no network at all.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Iterate `(row, col, component)` of all blocks in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, Option<usize>)> + '_ {
+        self.ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (i / self.width, i % self.width, *id))
+    }
+}
+
+/// Reason a world is not a valid puzzle, see [`World::validate_puzzle`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PuzzleValidationError {
+    /// Less than two non-empty blocks.
+    TooFewBlocks,
+    /// Count of endpoints plus forks is odd, so some link can't have two ends.
+    ///
+    /// Endpoints have one end and forks have three, other blocks have an even count, so the total count of ends
+    /// is odd exactly when this count is.
+    OddEndpointAndForkCount,
+    /// Endpoint at the position has no non-empty neighbor to connect.
+    IsolatedEndpoint(Pos),
+    /// Endpoint at the position connects to no other block in the solution, it points to an open border.
+    UnreachableEndpoint(Pos),
+    /// No rotation of blocks makes the world solved.
+    Unsolvable,
+}
+
+impl Display for PuzzleValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewBlocks => f.write_str("less than two non-empty blocks"),
+            Self::OddEndpointAndForkCount => f.write_str("odd number of endpoints and forks"),
+            Self::IsolatedEndpoint(pos) => {
+                write!(f, "isolated endpoint at row {}, column {}", pos.row + 1, pos.col + 1)
+            }
+            Self::UnreachableEndpoint(pos) => {
+                write!(
+                    f,
+                    "endpoint at row {}, column {} connects to nothing",
+                    pos.row + 1,
+                    pos.col + 1
+                )
+            }
+            Self::Unsolvable => f.write_str("no solution"),
+        }
+    }
+}
+
+/// Max solutions [`World::validate_puzzle`] will check for one with all endpoints reachable.
+pub const REACHABLE_SOLUTION_LIMIT: usize = 16;
+
+/// Max solutions [`World::minimum_hints_needed`] will compare when choosing a block to fix.
+pub const HINTS_SOLUTION_LIMIT: usize = 16;
+
+/// Max solutions [`World::minimum_rotations`] will compare.
+pub const MINIMUM_ROTATIONS_SOLUTION_LIMIT: usize = 16;
+
+/// Get how many clockwise turns each block of `world` needs to become the block of `solved` in the same position,
+/// in row-major order.
+///
+/// Return None if size not match or any block can't be rotated into the target block.
+pub fn block_rotations(world: &World, solved: &World) -> Option<Vec<u8>> {
+    if world.size() != solved.size() {
+        return None;
+    }
+
+    world
+        .rows()
+        .zip(solved.rows())
+        .flat_map(|(from, to)| from.iter().zip(to))
+        .map(|(from, to)| from.turns_to(to))
+        .collect()
+}
+
+/// Get minimal total clockwise turns to rotate `world` into `solved`, see [`block_rotations`].
+///
+/// Only clockwise rotation is supported, so one counterclockwise turn counts as 3.
+pub fn minimum_rotations(world: &World, solved: &World) -> Option<u32> {
+    block_rotations(world, solved).map(|turns| turns.into_iter().map(u32::from).sum())
+}
+
+impl World {
+    /// Get minimal total clockwise turns to solve this world, return None if it's not solvable.
+    ///
+    /// When this world has more than one solution, only the first [`MINIMUM_ROTATIONS_SOLUTION_LIMIT`] ones the
+    /// solver finds are compared, so the result may not be the global minimum.
+    pub fn minimum_rotations(&self) -> Option<u32> {
+        self.solutions()
+            .take(MINIMUM_ROTATIONS_SOLUTION_LIMIT)
+            .filter_map(|solved| minimum_rotations(self, &solved))
+            .min()
+    }
+
+    /// Get how many blocks need to be fixed in their solved orientation to make this world has a unique solution,
+    /// return 0 if this world is not solvable.
+    ///
+    /// It's a greedy estimation: while there are more than one solution, compare the first solution with up to
+    /// [`HINTS_SOLUTION_LIMIT`] following ones, fix the block differs in most of them, so the result may be
+    /// larger than the real minimum. It runs the solver many times, so it's slow for big worlds.
+    pub fn minimum_hints_needed(&self) -> usize {
+        let Some(first) = self.solve() else {
+            return 0;
+        };
+
+        let width = self.width().get();
+        let mut fixed = vec![false; self.block_count()];
+        let mut hints = 0;
+
+        loop {
+            let mut differs = vec![0_usize; fixed.len()];
+            let others = Solutions::with_fixed(&first, fixed.clone())
+                .skip(1)
+                .take(HINTS_SOLUTION_LIMIT);
+            for other in others {
+                for pos in first.diff(&other) {
+                    differs[pos.row * width + pos.col] += 1;
+                }
+            }
+
+            // fixed blocks never differ, so a count above zero always picks a free block
+            match differs.iter().enumerate().max_by_key(|(_, count)| **count) {
+                Some((index, count)) if *count > 0 => {
+                    fixed[index] = true;
+                    hints += 1;
+                }
+                _ => return hints,
+            }
+        }
+    }
+
+    /// Check if this world is a valid puzzle, return all problems found.
+    ///
+    /// Solvability is only checked when no other problem is found, because the solver may take a long time and
+    /// those problems already make the world unsolvable. Then endpoints are checked to be connected to other
+    /// blocks in one of the first [`REACHABLE_SOLUTION_LIMIT`] solutions, see
+    /// [`World::check_all_endpoints_reachable`].
+    pub fn validate_puzzle(&self) -> Result<(), Vec<PuzzleValidationError>> {
+        const NEIGHBORS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+        let mut errors = Vec::new();
+        let counts = self.block_counts();
+
+        if counts.endpoint + counts.through + counts.turn + counts.fork + counts.cross < 2 {
+            errors.push(PuzzleValidationError::TooFewBlocks);
+        }
+
+        // every link has two ends, endpoints have one and forks have three
+        if (counts.endpoint + counts.fork) % 2 == 1 {
+            errors.push(PuzzleValidationError::OddEndpointAndForkCount);
+        }
+
+        for (row, col, block) in self {
+            if !matches!(block, Block::Endpoint(_)) {
+                continue;
+            }
+
+            let connectable = NEIGHBORS.iter().any(|(dr, dc)| {
+                let neighbor = row
+                    .checked_add_signed(*dr)
+                    .zip(col.checked_add_signed(*dc))
+                    .and_then(|(row, col)| self.get(row, col));
+                // all non-empty blocks can be rotated to face any side
+                neighbor.is_some_and(|b| b != &Block::Empty)
+            });
+
+            if !connectable {
+                errors.push(PuzzleValidationError::IsolatedEndpoint(Pos::new(row, col)));
+            }
+        }
+
+        if errors.is_empty() {
+            let mut solutions = self.solutions().take(REACHABLE_SOLUTION_LIMIT);
+            match solutions.next() {
+                Some(first) => {
+                    if !first.check_all_endpoints_reachable()
+                        && !solutions.any(|solved| solved.check_all_endpoints_reachable())
+                    {
+                        errors.extend(
+                            first
+                                .unreachable_endpoints()
+                                .map(PuzzleValidationError::UnreachableEndpoint),
+                        );
+                    }
+                }
+                None => errors.push(PuzzleValidationError::Unsolvable),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Get positions of blocks connected to block at `(row, col)` through pipes in current orientation, including
+    /// itself, in row-major order. Blocks are connected only when both of them pass to each other.
+    ///
+    /// ## Panics
+    ///
+    /// If index out of range.
+    pub fn reachable_from(&self, row: usize, col: usize) -> Vec<Pos> {
+        assert!(self.get(row, col).is_some(), "block index out of range");
+
+        let width = self.width().get();
+        let mut visited = vec![false; self.block_count()];
+        self.flood(row, col, &mut visited, |_| {});
+
+        visited
+            .into_iter()
+            .enumerate()
+            .filter(|(_, visited)| *visited)
+            .map(|(i, _)| Pos::new(i / width, i % width))
+            .collect()
+    }
+
+    /// Mark blocks connected to `(row, col)` in `visited`, `f` is called with row-major index of each newly
+    /// marked block, including the start one.
+    fn flood(&self, row: usize, col: usize, visited: &mut [bool], mut f: impl FnMut(usize)) {
+        const SIDES: [(Direction, isize, isize); 4] = [
+            (Direction::Up, -1, 0),
+            (Direction::Right, 0, 1),
+            (Direction::Down, 1, 0),
+            (Direction::Left, 0, -1),
+        ];
+
+        let width = self.width().get();
+        let mut stack = vec![(row, col)];
+        visited[row * width + col] = true;
+        f(row * width + col);
+
+        while let Some((row, col)) = stack.pop() {
+            let block = &self[(row, col)];
+            for (side, dr, dc) in &SIDES {
+                let Some((r, c)) = row.checked_add_signed(*dr).zip(col.checked_add_signed(*dc)) else {
+                    continue;
+                };
+                let Some(neighbor) = self.get(r, c) else {
+                    continue;
+                };
+                if block.passable(*side) && neighbor.passable(side.opposite()) && !visited[r * width + c] {
+                    visited[r * width + c] = true;
+                    f(r * width + c);
+                    stack.push((r, c));
+                }
+            }
+        }
+    }
+
+    /// Split blocks into networks connected in current orientation, see [`ComponentMap`].
+    pub fn components(&self) -> ComponentMap {
+        let width = self.width().get();
+        let mut visited = vec![false; self.block_count()];
+        let mut ids = vec![None; self.block_count()];
+        let mut count = 0;
+        let mut members = Vec::new();
+
+        for (row, col, _) in self.iter().filter(|(_, _, block)| **block != Block::Empty) {
+            if visited[row * width + col] {
+                continue;
+            }
+            members.clear();
+            self.flood(row, col, &mut visited, |i| members.push(i));
+            if members.len() < 2 {
+                continue;
+            }
+            for i in &members {
+                ids[*i] = Some(count);
+            }
+            count += 1;
+        }
+
+        ComponentMap { width, ids, count }
+    }
+
+    fn unreachable_endpoints(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.iter()
+            .filter(|(_, _, block)| matches!(block, Block::Endpoint(_)))
+            .filter(|(row, col, _)| self.reachable_from(*row, *col).len() < 2)
+            .map(|(row, col, _)| Pos::new(row, col))
+    }
+
+    /// Check if every endpoint is connected to at least one other block in current orientation.
+    ///
+    /// It's always true for a solved world with closed borders, but an endpoint may point to an open border, see
+    /// [`World::set_open_borders`].
+    pub fn check_all_endpoints_reachable(&self) -> bool {
+        self.unreachable_endpoints().next().is_none()
+    }
+
+    /// Get position and direction of every border block passing to the outside of the world, in row-major order.
+    ///
+    /// Always empty when borders are open, see [`World::set_open_borders`].
+    pub fn border_passability_violations(&self) -> Vec<(Pos, Direction)> {
+        if self.open_borders() {
+            return Vec::new();
+        }
+
+        let (height, width) = (self.height().get(), self.width().get());
+        self.iter()
+            .flat_map(|(row, col, block)| {
+                [
+                    (Direction::Up, row == 0),
+                    (Direction::Right, col == width - 1),
+                    (Direction::Down, row == height - 1),
+                    (Direction::Left, col == 0),
+                ]
+                .into_iter()
+                .filter(move |(dir, border)| *border && block.passable(*dir))
+                .map(move |(dir, _)| (Pos::new(row, col), dir))
+            })
+            .collect()
+    }
+
+    /// Check which symmetries map this world onto itself, blocks connect the same sides are treated as equal.
+    pub fn symmetries(&self) -> Symmetries {
+        let same = |other: &World| self.size() == other.size() && self.diff(other).is_empty();
+        let rotated = self.rotated();
+
+        Symmetries {
+            horizontal_mirror: same(&self.flipped_horizontally()),
+            vertical_mirror: same(&self.flipped_vertically()),
+            rotation_180: same(&rotated.rotated()),
+            rotation_90: same(&rotated),
+        }
+    }
+
+    /// Count non-empty blocks which need a decision of rotation, they have 4 distinct orientations.
+    ///
+    /// [`Block::Through`] and [`Block::Cross`] look the same after 2 or 1 rotations, they are not counted.
+    /// So a world of all crosses has 0 rotation classes, and a world of all forks has one for every block.
+    ///
+    /// [`Block::Through`]: crate::Block::Through
+    /// [`Block::Cross`]: crate::Block::Cross
+    pub fn count_rotation_classes(&self) -> usize {
+        let counts = self.block_counts();
+        counts.endpoint + counts.turn + counts.fork
+    }
+
+    /// Estimate difficulty level of this world.
+    ///
+    /// It's a heuristic rating, one point is given for each of:
+    ///
+    /// - at least 25 non-empty blocks, and another point for at least 64.
+    /// - at least 1/4 of non-empty blocks are [`Block::Fork`] or [`Block::Cross`].
+    /// - solver visits at least 3 nodes per non-empty block (see [`difficulty`]), and another point for at least 6.
+    ///
+    /// 0-1 points is [`Difficulty::Easy`], 2-3 points is [`Difficulty::Normal`], others are [`Difficulty::Hard`].
+    ///
+    /// [`Block::Fork`]: crate::Block::Fork
+    /// [`Block::Cross`]: crate::Block::Cross
+    pub fn estimate_difficulty(&self) -> Difficulty {
+        self.difficulty_score().difficulty
+    }
+
+    /// Rate difficulty like [`World::estimate_difficulty`], and tell the factors of the rating.
+    pub fn difficulty_score(&self) -> DifficultyScore {
+        let counts = self.block_counts();
+        let mut score = DifficultyScore {
+            difficulty: Difficulty::Easy,
+            points: 0,
+            blocks: counts.endpoint + counts.through + counts.turn + counts.fork + counts.cross,
+            forks_and_crosses: counts.fork + counts.cross,
+            solver_nodes: difficulty(self) as usize,
+            rotation_classes: self.count_rotation_classes(),
+        };
+
+        score.points = score.block_points() + score.fork_and_cross_points() + score.solver_points();
+        score.difficulty = match score.points {
+            0..=1 => Difficulty::Easy,
+            2..=3 => Difficulty::Normal,
+            _ => Difficulty::Hard,
+        };
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world(s: &str) -> World {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn rotations_against_reference() {
+        let solved = world("79\n13");
+        assert_eq!(block_rotations(&world("79\n31"), &solved), Some(vec![0, 0, 1, 3]));
+        assert_eq!(minimum_rotations(&world("79\n31"), &solved), Some(4));
+        assert_eq!(minimum_rotations(&world("13\n79"), &solved), Some(8));
+        assert_eq!(minimum_rotations(&solved, &solved), Some(0));
+
+        // a through block looks the same after two turns, an endpoint needs three to turn left once
+        assert_eq!(block_rotations(&world("-/^"), &world("/-<")), Some(vec![1, 1, 3]));
+
+        assert_eq!(block_rotations(&world("><"), &solved), None);
+        assert_eq!(block_rotations(&world("79\n35"), &solved), None);
+    }
+
+    #[test]
+    fn rotations_take_cheapest_solution() {
+        // solved as `><` rows with 2 turns, or as `vv` over `^^` with 6
+        assert_eq!(world("^<\n^<").minimum_rotations(), Some(2));
+        assert_eq!(world("79\n31").minimum_rotations(), Some(4));
+        assert_eq!(world("^").minimum_rotations(), None);
+    }
+
+    #[test]
+    fn symmetries() {
+        let all = Symmetries {
+            horizontal_mirror: true,
+            vertical_mirror: true,
+            rotation_180: true,
+            rotation_90: true,
+        };
+        assert_eq!(world("79\n13").symmetries(), all);
+        // through blocks stored in the other direction are still the same
+        assert_eq!(world("v1\n3,3\n7-9\n/5/\n1-3").symmetries(), all);
+
+        let mirror_only = Symmetries {
+            horizontal_mirror: true,
+            ..Symmetries::default()
+        };
+        assert_eq!(world("v1\n3,3\n>-<\n   \n   ").symmetries(), mirror_only);
+
+        assert_eq!(world("v1\n2,2\n>9\n 1").symmetries(), Symmetries::default());
+    }
+
+    #[test]
+    fn non_square_worlds_skip_90_degree_rotation() {
+        let symmetries = world("v1\n2,3\n7-9\n1-3").symmetries();
+        assert!(symmetries.horizontal_mirror && symmetries.vertical_mirror && symmetries.rotation_180);
+        assert!(!symmetries.rotation_90);
+
+        // a row of crosses looks the same turned, but not in another shape
+        assert!(!world("555").symmetries().rotation_90);
+        assert!(world("555").symmetries().rotation_180);
+    }
+
+    #[test]
+    fn tiny_through_board_is_easy() {
+        let world: World = "v1\n2,2\n--\n--\n".parse().unwrap();
+        assert_eq!(world.estimate_difficulty(), Difficulty::Easy);
+    }
+
+    #[test]
+    fn rotation_classes_skip_symmetric_blocks() {
+        let row = |c: &str| c.repeat(5);
+        let grid = |c: &str| world(&vec![row(c); 5].join("\n"));
+        assert_eq!(grid("5").count_rotation_classes(), 0);
+        assert_eq!(grid("/").count_rotation_classes(), 0);
+        assert_eq!(grid("8").count_rotation_classes(), 25);
+        assert_eq!(world("v1\n2,3\n>8<\n /5\n").count_rotation_classes(), 3);
+    }
+
+    #[test]
+    fn difficulty_score_explains_rating() {
+        let world = world("v1\n2,3\n>8<\n ^ \n");
+        let score = world.difficulty_score();
+        assert_eq!(
+            score,
+            DifficultyScore {
+                difficulty: Difficulty::Normal,
+                points: 2,
+                blocks: 4,
+                forks_and_crosses: 1,
+                solver_nodes: 22,
+                rotation_classes: 4,
+            }
+        );
+        assert_eq!(world.estimate_difficulty(), score.difficulty);
+        assert_eq!(
+            score.explanation(),
+            "normal, 2 points: 4 blocks +0, 1 forks and crosses +1, 22 solver nodes +1, 4 rotation classes"
+        );
+    }
+
+    #[test]
+    fn dense_fork_board_is_hard() {
+        // a shuffled generated 8x8 level, 9 forks and 5 crosses
+        let world: World = "v1\n8,8\n^v<7<^^^\n-/11-/--\n72583853\nv921883v\n723><3/1\n^/>^1-1^\n22555<99\n3^<^7/v^\n"
+            .parse()
+            .unwrap();
+        assert!(world.is_solvable());
+        assert_eq!(world.estimate_difficulty(), Difficulty::Hard);
+    }
+
+    #[test]
+    fn component_of_two_networks() {
+        let map = world("v1\n3,3\n><5\n  v\n^ ^\n").components();
+        assert_eq!(map.len(), 2);
+
+        let expected = [[Some(0), Some(0), None], [None, None, Some(1)], [None, None, Some(1)]];
+        for (row, cols) in expected.iter().enumerate() {
+            for (col, id) in cols.iter().enumerate() {
+                assert_eq!(map.component_of(row, col), *id, "({row}, {col})");
+            }
+        }
+        assert_eq!(map.iter().filter(|(_, _, id)| id.is_some()).count(), 4);
+
+        // out of range column must not wrap to the next row
+        assert_eq!(map.component_of(0, 3), None);
+        assert_eq!(map.component_of(3, 0), None);
+
+        assert!(world(" ").components().is_empty());
+    }
+
+    #[test]
+    fn validate_puzzle_reports_every_problem() {
+        use PuzzleValidationError::*;
+
+        assert_eq!(world("79\n13").validate_puzzle(), Ok(()));
+        // three endpoints and a fork pair up
+        assert_eq!(world("v1\n2,3\n>8<\n ^ \n").validate_puzzle(), Ok(()));
+
+        assert_eq!(
+            world("^").validate_puzzle(),
+            Err(vec![
+                TooFewBlocks,
+                OddEndpointAndForkCount,
+                IsolatedEndpoint(Pos::new(0, 0))
+            ])
+        );
+        // one endpoint, or one fork, or both with another fork
+        assert_eq!(world("^-").validate_puzzle(), Err(vec![OddEndpointAndForkCount]));
+        assert_eq!(world("8-").validate_puzzle(), Err(vec![OddEndpointAndForkCount]));
+        assert_eq!(
+            world("v1\n2,2\n^8\n8 \n").validate_puzzle(),
+            Err(vec![OddEndpointAndForkCount])
+        );
+
+        assert_eq!(
+            world("v1\n3,3\n^ ^\n   \n>-<\n").validate_puzzle(),
+            Err(vec![IsolatedEndpoint(Pos::new(0, 0)), IsolatedEndpoint(Pos::new(0, 2))])
+        );
+
+        // a cross can't fit in one row
+        assert_eq!(world(">5<").validate_puzzle(), Err(vec![Unsolvable]));
+    }
+
+    #[test]
+    fn border_passability_violations_in_order() {
+        assert!(world("79\n13").border_passability_violations().is_empty());
+
+        assert_eq!(
+            world("^<\n/-").border_passability_violations(),
+            [
+                (Pos::new(0, 0), Direction::Up),
+                (Pos::new(1, 0), Direction::Down),
+                (Pos::new(1, 1), Direction::Right),
+            ]
+        );
+
+        let mut cross = world("5");
+        assert_eq!(
+            cross.border_passability_violations(),
+            [Direction::Up, Direction::Right, Direction::Down, Direction::Left].map(|dir| (Pos::new(0, 0), dir))
+        );
+
+        cross.set_open_borders(true);
+        assert!(cross.border_passability_violations().is_empty());
+    }
+}
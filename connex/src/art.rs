@@ -0,0 +1,185 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{Block, Direction, World};
+
+/// Style of text art produced by [`World::to_art`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum ArtStyle {
+    /// One box-drawing character per block.
+    #[default]
+    Unicode,
+    /// 3x3 pure ASCII characters per block, so it looks like a table.
+    Ascii,
+}
+
+impl Block {
+    /// Get the box-drawing character which looks like this block.
+    pub fn box_char(&self) -> char {
+        match self {
+            Self::Empty => ' ',
+            Self::Endpoint(Direction::Up) => '╵',
+            Self::Endpoint(Direction::Right) => '╶',
+            Self::Endpoint(Direction::Down) => '╷',
+            Self::Endpoint(Direction::Left) => '╴',
+            Self::Through(Direction::Up | Direction::Down) => '│',
+            Self::Through(Direction::Left | Direction::Right) => '─',
+            Self::Turn(Direction::Up) => '└',
+            Self::Turn(Direction::Right) => '┌',
+            Self::Turn(Direction::Down) => '┐',
+            Self::Turn(Direction::Left) => '┘',
+            Self::Fork(Direction::Up) => '┬',
+            Self::Fork(Direction::Right) => '┤',
+            Self::Fork(Direction::Down) => '┴',
+            Self::Fork(Direction::Left) => '├',
+            Self::Cross => '┼',
+        }
+    }
+
+    /// Get 3 lines of 3 ASCII characters which looks like this block.
+    fn ascii_art(&self) -> [[char; 3]; 3] {
+        let mut art = [[' '; 3]; 3];
+
+        art[1][1] = match self {
+            Self::Empty => ' ',
+            Self::Endpoint(_) => 'o',
+            Self::Through(Direction::Up | Direction::Down) => '|',
+            Self::Through(Direction::Left | Direction::Right) => '-',
+            _ => '+',
+        };
+
+        if self.passable(Direction::Up) {
+            art[0][1] = '|';
+        }
+        if self.passable(Direction::Right) {
+            art[1][2] = '-';
+        }
+        if self.passable(Direction::Down) {
+            art[2][1] = '|';
+        }
+        if self.passable(Direction::Left) {
+            art[1][0] = '-';
+        }
+
+        art
+    }
+}
+
+impl World {
+    /// Describe this world in text, for screen readers and logs, each row is a line ends with `\n`.
+    ///
+    /// Rows and columns are counted from 1, e.g.
+    /// `Row 1: Turn block facing Up at column 1, Through block horizontal at column 2`.
+    pub fn to_accessible_string(&self) -> String {
+        let mut result = String::new();
+
+        for (row, blocks) in self.rows().enumerate() {
+            write!(result, "Row {}:", row + 1).unwrap();
+            for (col, block) in blocks.iter().enumerate() {
+                let separator = if col == 0 { " " } else { ", " };
+                let name = block.variant_name();
+                match block {
+                    Block::Empty | Block::Cross => write!(result, "{separator}{name} block"),
+                    Block::Through(dir) if dir.horizontal() => write!(result, "{separator}{name} block horizontal"),
+                    Block::Through(_) => write!(result, "{separator}{name} block vertical"),
+                    _ => write!(
+                        result,
+                        "{separator}{name} block facing {}",
+                        block.direction().unwrap().display_name()
+                    ),
+                }
+                .unwrap();
+                write!(result, " at column {}", col + 1).unwrap();
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    /// Describe this world in one line, more compact than [`World::to_accessible_string`], e.g.
+    /// `Row 1: fork opening down, through vertical, empty; Row 2: ...`.
+    ///
+    /// Forks are described by their middle link, which is opposite to the side can't be connected.
+    pub fn describe(&self) -> String {
+        let mut result = String::new();
+
+        for (row, blocks) in self.rows().enumerate() {
+            if row > 0 {
+                result.push_str("; ");
+            }
+            write!(result, "Row {}: ", row + 1).unwrap();
+
+            for (col, block) in blocks.iter().enumerate() {
+                if col > 0 {
+                    result.push_str(", ");
+                }
+
+                let name = |dir: Direction| dir.display_name().to_lowercase();
+                match block {
+                    Block::Empty => result.push_str("empty"),
+                    Block::Endpoint(dir) => write!(result, "endpoint facing {}", name(*dir)).unwrap(),
+                    Block::Through(dir) if dir.horizontal() => result.push_str("through horizontal"),
+                    Block::Through(_) => result.push_str("through vertical"),
+                    Block::Turn(dir) => write!(result, "turn {}-{}", name(*dir), name(dir.rotated())).unwrap(),
+                    Block::Fork(dir) => write!(result, "fork opening {}", name(dir.opposite())).unwrap(),
+                    Block::Cross => result.push_str("cross"),
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Draw this world as text art, each line ends with `\n`.
+    pub fn to_art(&self, style: ArtStyle) -> String {
+        let mut result = String::new();
+
+        for row in self.rows() {
+            match style {
+                ArtStyle::Unicode => {
+                    result.extend(row.iter().map(Block::box_char));
+                    result.push('\n');
+                }
+                ArtStyle::Ascii => {
+                    let arts: Vec<_> = row.iter().map(Block::ascii_art).collect();
+                    for line in 0..3 {
+                        result.extend(arts.iter().flat_map(|art| art[line]));
+                        result.push('\n');
+                    }
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> World {
+        "^/76\n -5<".parse().unwrap()
+    }
+
+    #[test]
+    fn describe_exact_output() {
+        assert_eq!(
+            world().describe(),
+            "Row 1: endpoint facing up, through vertical, turn right-down, fork opening left; \
+             Row 2: empty, through horizontal, cross, endpoint facing left"
+        );
+    }
+
+    #[test]
+    fn accessible_string_exact_output() {
+        assert_eq!(
+            world().to_accessible_string(),
+            "Row 1: Endpoint block facing Up at column 1, Through block vertical at column 2, \
+             Turn block facing Right at column 3, Fork block facing Right at column 4\n\
+             Row 2: Empty block at column 1, Through block horizontal at column 2, Cross block at column 3, \
+             Endpoint block facing Left at column 4\n"
+        );
+    }
+}
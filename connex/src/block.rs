@@ -28,6 +28,22 @@ impl Direction {
         }
     }
 
+    /// Get position in clockwise order starting from [`Direction::Up`], which is 0, to [`Direction::Left`], which
+    /// is 3.
+    pub fn clockwise_index(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Get bit of this direction in [`Block::passable_mask`], which is `1 << self.clockwise_index()`.
+    pub fn bit(&self) -> u8 {
+        1 << self.clockwise_index()
+    }
+
+    /// Get how many clockwise turns it takes to face `other`, from 0 to 3.
+    pub fn clockwise_distance(&self, other: &Self) -> u8 {
+        (other.clockwise_index() + 4 - self.clockwise_index()) % 4
+    }
+
     /// Get result of rotate clockwise.
     pub fn rotated(&self) -> Self {
         match self {
@@ -48,6 +64,16 @@ impl Direction {
         matches!(self, Self::Up | Self::Down)
     }
 
+    /// Get human readable name.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Up => "Up",
+            Self::Right => "Right",
+            Self::Down => "Down",
+            Self::Left => "Left",
+        }
+    }
+
     /// Get opposite direction.
     pub fn opposite(&self) -> Self {
         match self {
@@ -83,6 +109,14 @@ impl Direction {
 /// ```
 ///
 /// [^1]: `-`/`|` means passable direction, center number is the character for that type of block.
+///
+/// [`Block::parse_lenient`] also accepts some aliases, but [`Display`] always uses the characters above:
+///
+/// - `|` for vertical [`Block::Through`].
+/// - `L`, `J`, `F` for [`Block::Turn`] like `1`, `3`, `7`, as in pipe puzzle notation. Its `7` for the turn
+///   connecting left and down is not supported, because `7` already means another turn here, use `9` instead.
+/// - `+` for [`Block::Cross`].
+/// - Box-drawing characters returned by [`Block::box_char`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Block {
     /// Empty block.
@@ -134,12 +168,37 @@ impl FromStr for Block {
     }
 }
 
+impl Block {
+    /// Parse a block like [`FromStr`] implementation, but also accept aliases listed in [`Block`] document, return
+    /// None if it's not a valid block char.
+    pub fn parse_lenient(s: &str) -> Option<Self> {
+        match s {
+            "|" | "│" => Some(Self::Through(Direction::Up)),
+            "─" => Some(Self::Through(Direction::Left)),
+            "L" | "└" => Some(Self::Turn(Direction::Up)),
+            "F" | "┌" => Some(Self::Turn(Direction::Right)),
+            "┐" => Some(Self::Turn(Direction::Down)),
+            "J" | "┘" => Some(Self::Turn(Direction::Left)),
+            "┬" => Some(Self::Fork(Direction::Up)),
+            "┤" => Some(Self::Fork(Direction::Right)),
+            "┴" => Some(Self::Fork(Direction::Down)),
+            "├" => Some(Self::Fork(Direction::Left)),
+            "+" | "┼" => Some(Self::Cross),
+            "╵" => Some(Self::Endpoint(Direction::Up)),
+            "╶" => Some(Self::Endpoint(Direction::Right)),
+            "╷" => Some(Self::Endpoint(Direction::Down)),
+            "╴" => Some(Self::Endpoint(Direction::Left)),
+            _ => s.parse().ok(),
+        }
+    }
+}
+
 impl Display for Block {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_char(match self {
             Self::Empty => ' ',
             Self::Endpoint(Direction::Up) => '^',
-            Self::Endpoint(Direction::Right) => '^',
+            Self::Endpoint(Direction::Right) => '>',
             Self::Endpoint(Direction::Down) => 'v',
             Self::Endpoint(Direction::Left) => '<',
             Self::Through(Direction::Up | Direction::Down) => '/',
@@ -174,6 +233,12 @@ impl Block {
         }
     }
 
+    /// Create a random block from a seed, same seed always gives same block on every platform.
+    #[cfg(feature = "random")]
+    pub fn random_seeded(seed: u64, allow_empty: bool) -> Self {
+        Self::random(crate::seeded_rng(seed), allow_empty)
+    }
+
     /// Shuffle self, make direction random.
     #[cfg(feature = "random")]
     pub fn shuffle<R: rand::Rng>(&mut self, mut r: R) {
@@ -186,6 +251,41 @@ impl Block {
         }
     }
 
+    /// Get name of the variant.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Empty => "Empty",
+            Self::Endpoint(_) => "Endpoint",
+            Self::Through(_) => "Through",
+            Self::Turn(_) => "Turn",
+            Self::Fork(_) => "Fork",
+            Self::Cross => "Cross",
+        }
+    }
+
+    /// Get a short player-facing name of this block with its orientation, e.g. `turn up-right`, for tooltips
+    /// and debug output. Blocks look the same have the same label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Endpoint(Direction::Up) => "endpoint facing up",
+            Self::Endpoint(Direction::Right) => "endpoint facing right",
+            Self::Endpoint(Direction::Down) => "endpoint facing down",
+            Self::Endpoint(Direction::Left) => "endpoint facing left",
+            Self::Through(Direction::Up | Direction::Down) => "through vertical",
+            Self::Through(Direction::Right | Direction::Left) => "through horizontal",
+            Self::Turn(Direction::Up) => "turn up-right",
+            Self::Turn(Direction::Right) => "turn right-down",
+            Self::Turn(Direction::Down) => "turn down-left",
+            Self::Turn(Direction::Left) => "turn left-up",
+            Self::Fork(Direction::Up) => "fork blocked up",
+            Self::Fork(Direction::Right) => "fork blocked right",
+            Self::Fork(Direction::Down) => "fork blocked down",
+            Self::Fork(Direction::Left) => "fork blocked left",
+            Self::Cross => "cross junction",
+        }
+    }
+
     /// Get result of rotate this block clockwise.
     pub fn rotated(&self) -> Self {
         match self {
@@ -205,18 +305,121 @@ impl Block {
         }
     }
 
-    /// Check if this block is passable to a direction.
-    pub fn passable(&self, rhs: Direction) -> bool {
+    /// Get result of mirror this block left to right.
+    pub fn flipped_horizontally(&self) -> Self {
+        use Direction::*;
+
+        match self {
+            Self::Endpoint(Left) | Self::Fork(Left) => self.with_direction(Right),
+            Self::Endpoint(Right) | Self::Fork(Right) => self.with_direction(Left),
+            Self::Turn(t) => Self::Turn(match t {
+                Up => Left,
+                Left => Up,
+                Right => Down,
+                Down => Right,
+            }),
+            _ => *self,
+        }
+    }
+
+    /// Get result of mirror this block top to bottom.
+    pub fn flipped_vertically(&self) -> Self {
+        use Direction::*;
+
+        match self {
+            Self::Endpoint(Up) | Self::Fork(Up) => self.with_direction(Down),
+            Self::Endpoint(Down) | Self::Fork(Down) => self.with_direction(Up),
+            Self::Turn(t) => Self::Turn(match t {
+                Up => Right,
+                Right => Up,
+                Down => Left,
+                Left => Down,
+            }),
+            _ => *self,
+        }
+    }
+
+    /// Get result of mirror this block along the top left to bottom right diagonal, so up and left sides swap,
+    /// right and down sides swap.
+    pub fn transposed(&self) -> Self {
+        use Direction::*;
+
         match self {
-            Self::Empty => false,
-            Self::Endpoint(t) => t == &rhs,
-            Self::Through(t) => t.horizontal() == rhs.horizontal(),
-            Self::Turn(t) => t == &rhs || t.rotated() == rhs,
-            Self::Fork(t) => t != &rhs,
-            Self::Cross => true,
+            // turns are named by the first side clockwise, mirroring reverses the order
+            Self::Turn(t) => Self::Turn(match t {
+                Up => Down,
+                Down => Up,
+                t => *t,
+            }),
+            _ => self.with_direction(match self.direction() {
+                Some(Up) => Left,
+                Some(Left) => Up,
+                Some(Right) => Down,
+                Some(Down) => Right,
+                None => return *self,
+            }),
         }
     }
 
+    fn with_direction(&self, dir: Direction) -> Self {
+        let mut block = *self;
+        if let Some(t) = block.direction_mut() {
+            *t = dir;
+        }
+        block
+    }
+
+    /// Rotate this block clockwise `steps` times.
+    pub fn turn_by(&mut self, steps: u8) {
+        for _ in 0..steps % 4 {
+            self.rotate();
+        }
+    }
+
+    /// Get canonical form of this block, `Through(Down)` becomes `Through(Up)` and `Through(Right)` becomes
+    /// `Through(Left)`, other blocks are unchanged.
+    ///
+    /// Blocks connect the same sides are equal after normalized.
+    pub fn normalized(&self) -> Self {
+        match self {
+            Self::Through(Direction::Up | Direction::Down) => Self::Through(Direction::Up),
+            Self::Through(Direction::Left | Direction::Right) => Self::Through(Direction::Left),
+            _ => *self,
+        }
+    }
+
+    /// Get passable sides as a bitmask, [`Direction::bit`] of a side is set if this block is passable to it.
+    ///
+    /// Masks of blocks facing [`Direction::Up`], bits are Left, Down, Right, Up from high to low:
+    ///
+    /// | Block      | Passable sides    | Mask     |
+    /// |------------|-------------------|----------|
+    /// | `Empty`    | none              | `0b0000` |
+    /// | `Endpoint` | up                | `0b0001` |
+    /// | `Through`  | up, down          | `0b0101` |
+    /// | `Turn`     | up, right         | `0b0011` |
+    /// | `Fork`     | right, down, left | `0b1110` |
+    /// | `Cross`    | all               | `0b1111` |
+    ///
+    /// Every clockwise turn moves each bit one place higher, Left wraps to Up.
+    pub fn passable_mask(&self) -> u8 {
+        let (up_mask, dir) = match self {
+            Self::Empty => (0b0000, Direction::Up),
+            Self::Endpoint(dir) => (0b0001, *dir),
+            Self::Through(dir) => (0b0101, *dir),
+            Self::Turn(dir) => (0b0011, *dir),
+            Self::Fork(dir) => (0b1110, *dir),
+            Self::Cross => (0b1111, Direction::Up),
+        };
+        let shift = dir.clockwise_index();
+        ((up_mask << shift) | (up_mask >> (4 - shift))) & 0b1111
+    }
+
+    /// Check if this block is passable to a direction, see [`Block::passable_mask`].
+    pub fn passable(&self, rhs: Direction) -> bool {
+        self.passable_mask() & rhs.bit() != 0
+    }
+
     /// Get direction.
     pub fn direction(&self) -> Option<Direction> {
         match self {
@@ -233,8 +436,257 @@ impl Block {
         }
     }
 
+    /// Get how many times this block need to be rotated clockwise to connect the same sides as `other`.
+    ///
+    /// Returns the smallest count, or None if `other` is not reachable by rotation.
+    pub fn turns_to(&self, other: &Self) -> Option<u8> {
+        match (self, other) {
+            (Self::Empty, Self::Empty) | (Self::Cross, Self::Cross) => Some(0),
+            // a through block looks the same after two turns
+            (Self::Through(from), Self::Through(to)) => Some(from.clockwise_distance(to) % 2),
+            (Self::Endpoint(from), Self::Endpoint(to))
+            | (Self::Turn(from), Self::Turn(to))
+            | (Self::Fork(from), Self::Fork(to)) => Some(from.clockwise_distance(to)),
+            _ => None,
+        }
+    }
+
     /// Check if this block is fit another block at given side.
     pub fn fit(&self, side: Direction, other: &Self) -> bool {
         self.passable(side) == other.passable(side.opposite())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::string::ToString;
+    use std::collections::HashSet;
+
+    use super::{Block::*, Direction::*, *};
+
+    const DIRECTIONS: [Direction; 4] = [Up, Right, Down, Left];
+
+    /// Expected passable sides of every block state, written out by hand instead of derived from masks.
+    const PASSABLE: [(Block, &[Direction]); 18] = [
+        (Empty, &[]),
+        (Endpoint(Up), &[Up]),
+        (Endpoint(Right), &[Right]),
+        (Endpoint(Down), &[Down]),
+        (Endpoint(Left), &[Left]),
+        (Through(Up), &[Up, Down]),
+        (Through(Right), &[Right, Left]),
+        (Through(Down), &[Up, Down]),
+        (Through(Left), &[Right, Left]),
+        (Turn(Up), &[Up, Right]),
+        (Turn(Right), &[Right, Down]),
+        (Turn(Down), &[Down, Left]),
+        (Turn(Left), &[Up, Left]),
+        (Fork(Up), &[Right, Down, Left]),
+        (Fork(Right), &[Up, Down, Left]),
+        (Fork(Down), &[Up, Right, Left]),
+        (Fork(Left), &[Up, Right, Down]),
+        (Cross, &[Up, Right, Down, Left]),
+    ];
+
+    fn blocks() -> impl Iterator<Item = Block> {
+        PASSABLE.iter().map(|(block, _)| *block)
+    }
+
+    #[test]
+    fn clockwise_distance_of_all_pairs() {
+        // expected[from][to], written out by hand
+        let expected = [[0, 1, 2, 3], [3, 0, 1, 2], [2, 3, 0, 1], [1, 2, 3, 0]];
+        for (from, row) in DIRECTIONS.iter().zip(expected) {
+            for (to, distance) in DIRECTIONS.iter().zip(row) {
+                assert_eq!(from.clockwise_distance(to), distance, "{from:?} -> {to:?}");
+
+                let mut turned = *from;
+                for _ in 0..distance {
+                    turned = turned.rotated();
+                }
+                assert_eq!(turned, *to);
+            }
+        }
+    }
+
+    #[test]
+    fn passable_matches_table() {
+        for (block, sides) in PASSABLE {
+            let mask = sides.iter().fold(0, |mask, side| mask | side.bit());
+            assert_eq!(block.passable_mask(), mask, "{block:?}");
+            for side in DIRECTIONS {
+                assert_eq!(block.passable(side), sides.contains(&side), "{block:?} {side:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn fit_is_symmetric() {
+        for a in blocks() {
+            for b in blocks() {
+                for side in DIRECTIONS {
+                    assert_eq!(a.fit(side, &b), b.fit(side.opposite(), &a), "{a:?} {side:?} {b:?}");
+                    assert_eq!(a.fit(side, &b), a.passable(side) == b.passable(side.opposite()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn four_turns_are_identity() {
+        for block in blocks() {
+            let mut turned = block;
+            for _ in 0..4 {
+                let mask = turned.passable_mask();
+                turned.rotate();
+                // each turn moves passable sides one step clockwise
+                assert_eq!(
+                    turned.passable_mask(),
+                    ((mask << 1) | (mask >> 3)) & 0b1111,
+                    "{block:?}"
+                );
+            }
+            assert_eq!(turned, block);
+
+            let mut turned = block;
+            turned.turn_by(4);
+            assert_eq!(turned, block);
+            assert_eq!(block.rotated().rotated().rotated().rotated(), block);
+        }
+    }
+
+    #[test]
+    fn mirrors_compose_correctly() {
+        // maps a side to the side it lands on after the mirror
+        let mirror_sides = |block: Block, map: fn(Direction) -> Direction| {
+            DIRECTIONS
+                .iter()
+                .filter(|side| block.passable(**side))
+                .fold(0, |mask, side| mask | map(*side).bit())
+        };
+
+        for block in blocks() {
+            let horizontal = block.flipped_horizontally();
+            let vertical = block.flipped_vertically();
+            let transposed = block.transposed();
+
+            assert_eq!(horizontal.flipped_horizontally(), block, "{block:?}");
+            assert_eq!(vertical.flipped_vertically(), block, "{block:?}");
+            assert_eq!(transposed.transposed(), block, "{block:?}");
+
+            let h = |side| match side {
+                Left => Right,
+                Right => Left,
+                side => side,
+            };
+            let v = |side| match side {
+                Up => Down,
+                Down => Up,
+                side => side,
+            };
+            let t = |side| match side {
+                Up => Left,
+                Left => Up,
+                Right => Down,
+                Down => Right,
+            };
+            assert_eq!(horizontal.passable_mask(), mirror_sides(block, h), "{block:?}");
+            assert_eq!(vertical.passable_mask(), mirror_sides(block, v), "{block:?}");
+            assert_eq!(transposed.passable_mask(), mirror_sides(block, t), "{block:?}");
+
+            // both flips are a half turn, transpose then flip left to right is a clockwise quarter turn
+            let mask = |block: Block| block.passable_mask();
+            assert_eq!(
+                mask(horizontal.flipped_vertically()),
+                mask(block.rotated().rotated()),
+                "{block:?}"
+            );
+            assert_eq!(
+                mask(vertical.flipped_horizontally()),
+                mask(block.rotated().rotated()),
+                "{block:?}"
+            );
+            assert_eq!(
+                mask(transposed.flipped_horizontally()),
+                mask(block.rotated()),
+                "{block:?}"
+            );
+            assert_eq!(
+                mask(transposed.flipped_vertically()),
+                mask(block.rotated().rotated().rotated())
+            );
+        }
+    }
+
+    #[test]
+    fn aliases_are_lenient_only() {
+        let aliases = [
+            ("|", Through(Up)),
+            ("│", Through(Up)),
+            ("─", Through(Left)),
+            ("L", Turn(Up)),
+            ("└", Turn(Up)),
+            ("F", Turn(Right)),
+            ("┌", Turn(Right)),
+            ("┐", Turn(Down)),
+            ("J", Turn(Left)),
+            ("┘", Turn(Left)),
+            ("┬", Fork(Up)),
+            ("┤", Fork(Right)),
+            ("┴", Fork(Down)),
+            ("├", Fork(Left)),
+            ("+", Cross),
+            ("┼", Cross),
+            ("╵", Endpoint(Up)),
+            ("╶", Endpoint(Right)),
+            ("╷", Endpoint(Down)),
+            ("╴", Endpoint(Left)),
+        ];
+
+        for (alias, block) in aliases {
+            assert_eq!(Block::parse_lenient(alias), Some(block), "{alias}");
+            assert_eq!(alias.parse::<Block>(), Err(()), "{alias}");
+            // display always uses the canonical char
+            assert_eq!(block.to_string().parse(), Ok(block), "{alias}");
+        }
+
+        // canonical chars are accepted by both, pipe notation's 7 keeps its meaning here
+        for block in blocks() {
+            let canonical = block.to_string();
+            assert_eq!(Block::parse_lenient(&canonical), canonical.parse().ok());
+        }
+        assert_eq!(Block::parse_lenient("7"), Some(Turn(Right)));
+        assert_eq!(Block::parse_lenient("x"), None);
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn random_seeded_is_pinned() {
+        // pinned, so the same seed gives the same block on every platform and version
+        let blocks = |allow_empty| (0..8).map(move |seed| Block::random_seeded(seed, allow_empty).to_string());
+        assert_eq!(blocks(true).collect::<alloc::string::String>(), "21v 8>/>");
+        assert_eq!(blocks(false).collect::<alloc::string::String>(), "215v8-//");
+    }
+
+    #[test]
+    fn labels_are_distinct_per_look() {
+        // blocks look different exactly when their passable sides differ
+        let labels: HashSet<_> = blocks().map(|block| block.label()).collect();
+        let looks: HashSet<_> = blocks().map(|block| block.passable_mask()).collect();
+        assert_eq!(labels.len(), looks.len());
+        assert_eq!(labels.len(), 16);
+        assert!(labels.iter().all(|label| !label.is_empty()));
+
+        for a in blocks() {
+            for b in blocks() {
+                assert_eq!(
+                    a.label() == b.label(),
+                    a.passable_mask() == b.passable_mask(),
+                    "{a:?} {b:?}"
+                );
+            }
+        }
+    }
+}
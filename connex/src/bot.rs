@@ -0,0 +1,150 @@
+//! Bots which play the game by themselves, used to evaluate levels and as demo players.
+
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use rand::seq::SliceRandom;
+
+use crate::{Block, Command, Direction, Game, Pos, World};
+
+const SIDES: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+/// A player which chooses next command by looking at the game.
+pub trait Bot {
+    /// Choose next command to apply, [`Command::Noop`] if it has nothing to do.
+    fn choose_command(&self, game: &Game) -> Command;
+}
+
+/// Positions of non-empty blocks which don't fit all their neighbors yet.
+fn unsolved_cells(world: &World) -> impl Iterator<Item = Pos> + '_ {
+    let solved = world.solved_cells();
+    world
+        .iter()
+        .filter(|(_, _, block)| **block != Block::Empty)
+        .map(|(row, col, _)| Pos::new(row, col))
+        .filter(move |pos| solved.binary_search(pos).is_err())
+}
+
+/// Bot rotates a random unsolved block clockwise every time.
+///
+/// It's seeded, so the same seed always plays the same way.
+#[derive(Debug)]
+pub struct RandomBot {
+    rng: RefCell<rand_chacha::ChaCha8Rng>,
+}
+
+impl RandomBot {
+    /// Create a random bot with a seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: RefCell::new(crate::seeded_rng(seed)),
+        }
+    }
+}
+
+impl Bot for RandomBot {
+    fn choose_command(&self, game: &Game) -> Command {
+        let candidates: Vec<_> = unsolved_cells(game.world()).collect();
+        match candidates.choose(&mut *self.rng.borrow_mut()) {
+            Some(pos) => Command::RotateBlock(*pos),
+            None => Command::Noop,
+        }
+    }
+}
+
+/// Bot rotates the unsolved block which increases [`World::count_correct_edges`] the most, the first one in
+/// row-major order if there is a tie.
+///
+/// It can get stuck in a local maximum, rotating the same block back and forth.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GreedyBot;
+
+impl Bot for GreedyBot {
+    fn choose_command(&self, game: &Game) -> Command {
+        let world = game.world();
+        // only edges around the rotated block change, so compare fit sides of it before and after rotating
+        let fit_sides = |pos: Pos, block: &Block| {
+            SIDES
+                .iter()
+                .filter(|side| world.side_fits(pos.row, pos.col, block, **side))
+                .count() as isize
+        };
+
+        let best = unsolved_cells(world)
+            .map(|pos| {
+                let block = &world[pos];
+                let mut rotated = *block;
+                rotated.rotate();
+                (pos, fit_sides(pos, &rotated) - fit_sides(pos, block))
+            })
+            // `max_by_key` returns the last one of ties, so fold to keep the first one
+            .fold(None, |best, (pos, increase)| match best {
+                Some((_, best_increase)) if best_increase >= increase => best,
+                _ => Some((pos, increase)),
+            });
+
+        match best {
+            Some((pos, _)) => Command::RotateBlock(pos),
+            None => Command::Noop,
+        }
+    }
+}
+
+/// Let `bot` play `world` until it's solved or `max_steps` commands are applied, returns the game and count of
+/// applied commands.
+pub fn simulate(world: World, bot: &dyn Bot, max_steps: usize) -> (Game, usize) {
+    let mut game = Game::new(world);
+    let mut steps = 0;
+
+    while !game.solved() && steps < max_steps {
+        game.apply(bot.choose_command(&game));
+        steps += 1;
+    }
+
+    (game, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+
+    fn world(s: &str) -> World {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn seeded_random_bot_is_deterministic() {
+        let play = |seed| simulate(world("11\n11"), &RandomBot::new(seed), 100);
+        let (game, steps) = play(7);
+        assert!(game.solved());
+        assert_eq!(game.history().len(), steps);
+
+        for _ in 0..3 {
+            let (again, again_steps) = play(7);
+            assert_eq!(again_steps, steps);
+            // commands can't be compared, their debug forms can
+            assert_eq!(format!("{:?}", again.history()), format!("{:?}", game.history()));
+        }
+
+        // only unsolved blocks are chosen
+        let (game, steps) = simulate(world("79\n13"), &RandomBot::new(7), 100);
+        assert!(game.solved());
+        assert_eq!(steps, 0);
+    }
+
+    #[test]
+    fn greedy_bot_solves_small_level_in_bound() {
+        for (level, bound) in [("71\n13", 2), ("7/9\n/ /\n1-3", 1), ("^ \n1<", 2), ("> \n1^", 4)] {
+            let (game, steps) = simulate(world(level), &GreedyBot, 100);
+            assert!(game.solved(), "{level:?}");
+            assert!(steps <= bound, "{level:?}: {steps} steps");
+        }
+
+        // it can get stuck in a local maximum, like here
+        let (game, steps) = simulate(world("11\n11"), &GreedyBot, 100);
+        assert!(!game.solved());
+        assert_eq!(steps, 100);
+    }
+}
@@ -0,0 +1,131 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::num::NonZeroUsize;
+
+use crate::{Block, World};
+
+/// A world whose blocks are shared with its clones until it's written, created by [`World::copy_on_write`].
+///
+/// Cloning only bumps a reference count, [`CowWorld::set`] copies blocks first if they are still shared with other
+/// clones, so there is at most one copy per written clone, and the shared one is freed with its last user.
+#[derive(Debug, Clone)]
+pub struct CowWorld {
+    blocks: Arc<Vec<Block>>,
+    height: NonZeroUsize,
+    width: NonZeroUsize,
+    open_borders: bool,
+}
+
+impl CowWorld {
+    /// Get height of the world.
+    pub fn height(&self) -> NonZeroUsize {
+        self.height
+    }
+
+    /// Get width of the world.
+    pub fn width(&self) -> NonZeroUsize {
+        self.width
+    }
+
+    fn index(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.height.get() && col < self.width.get()).then(|| row * self.width.get() + col)
+    }
+
+    /// Get block at `(row, col)`, return None if index out of range.
+    pub fn get(&self, row: usize, col: usize) -> Option<&Block> {
+        self.index(row, col).map(|i| &self.blocks[i])
+    }
+
+    /// Check if blocks are shared with other clones, so the next write copies them.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.blocks) > 1
+    }
+
+    /// Replace block at `(row, col)`, copy blocks first if they are shared with other clones.
+    ///
+    /// Returns the replaced block, or None if index out of range, nothing is copied then.
+    pub fn set(&mut self, row: usize, col: usize, block: Block) -> Option<Block> {
+        let index = self.index(row, col)?;
+        Some(core::mem::replace(&mut Arc::make_mut(&mut self.blocks)[index], block))
+    }
+
+    /// Convert back to a normal world, blocks are only copied if they are still shared with other clones.
+    pub fn into_world(self) -> World {
+        let blocks = Arc::try_unwrap(self.blocks).unwrap_or_else(|shared| shared.as_ref().clone());
+        let mut world = World::new_from_blocks(self.height, self.width, blocks);
+        world.set_open_borders(self.open_borders);
+        world
+    }
+}
+
+impl World {
+    /// Create a copy-on-write version of this world, see [`CowWorld`].
+    pub fn copy_on_write(&self) -> CowWorld {
+        CowWorld {
+            blocks: Arc::new(self.iter().map(|(_, _, block)| *block).collect()),
+            height: self.height(),
+            width: self.width(),
+            open_borders: self.open_borders(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world(s: &str) -> World {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn clones_share_storage_until_written() {
+        let a = world("79\n31").copy_on_write();
+        assert!(!a.is_shared());
+
+        let mut b = a.clone();
+        assert!(Arc::ptr_eq(&a.blocks, &b.blocks));
+        assert!(a.is_shared() && b.is_shared());
+
+        assert_eq!(b.set(1, 0, Block::Cross), Some(world("79\n31")[(1, 0)]));
+        assert!(!Arc::ptr_eq(&a.blocks, &b.blocks));
+        assert!(!a.is_shared() && !b.is_shared());
+        assert_eq!(a.get(1, 0), Some(&world("79\n31")[(1, 0)]));
+        assert_eq!(b.get(1, 0), Some(&Block::Cross));
+
+        // a written clone is shared again by its own clones
+        let c = b.clone();
+        assert!(Arc::ptr_eq(&b.blocks, &c.blocks));
+
+        // writing an unshared world doesn't copy
+        let mut a = a;
+        let before = Arc::as_ptr(&a.blocks);
+        a.set(0, 0, Block::Empty);
+        assert_eq!(Arc::as_ptr(&a.blocks), before);
+    }
+
+    #[test]
+    fn out_of_range_write_copies_nothing() {
+        let a = world("79\n31").copy_on_write();
+        let mut b = a.clone();
+        assert_eq!(b.set(0, 2, Block::Cross), None);
+        assert_eq!(b.set(2, 0, Block::Cross), None);
+        assert!(Arc::ptr_eq(&a.blocks, &b.blocks));
+    }
+
+    #[test]
+    fn round_trips_to_world() {
+        let mut origin = world("v1\n2,3\n>-<\n7 9\n");
+        origin.set_open_borders(true);
+
+        let cow = origin.copy_on_write();
+        let clone = cow.clone();
+        assert_eq!(cow.into_world(), origin);
+
+        let mut written = clone.clone();
+        written.set(1, 1, Block::Cross);
+        let mut expected = origin.clone();
+        expected[(1, 1)] = Block::Cross;
+        assert_eq!(written.into_world(), expected);
+        assert_eq!(clone.into_world(), origin);
+    }
+}
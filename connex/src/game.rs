@@ -1,4 +1,6 @@
-use crate::{Block, Direction, World};
+use alloc::vec::Vec;
+
+use crate::{Block, Direction, Pos, World};
 
 /// Command is game control command.
 #[derive(Debug, Clone)]
@@ -7,28 +9,87 @@ pub enum Command {
     Noop,
     /// Reset game world, use to switch level or restart level.
     Reset(World),
+    /// Replace all blocks with [`Block::Empty`], world size and cursor are kept.
+    Clear,
     /// Move cursor one block towards given direction.
     MoveCursor(Direction),
     /// Turn block under cursor clockwise.
     RotateCursorBlock,
-    /// Turn block at given index clockwise.
-    RotateBlock(usize, usize),
-    /// Rotate whole world, with or without block rotation.
+    /// Turn block at given position clockwise, do nothing if position out of range.
+    RotateBlock(Pos),
+    /// Rotate whole world clockwise, with or without block rotation, cursor follows its block.
     RotateWholeWorld(bool),
+    /// Mirror whole world left to right, blocks are mirrored too, cursor follows its block.
+    FlipHorizontally,
+    /// Mirror whole world top to bottom, blocks are mirrored too, cursor follows its block.
+    FlipVertically,
+    /// Mirror whole world along the top left to bottom right diagonal, blocks are mirrored too, cursor follows its
+    /// block.
+    Transpose,
     /// Replace current block.
     ReplaceCursorBlock(Block),
-    /// Replace block at given index.
-    ReplaceBlock(usize, usize, Block),
-    /// Insert a row of empty block at given index.
+    /// Replace block at given position, do nothing if position out of range.
+    ReplaceBlock(Pos, Block),
+    /// Replace all blocks in a region, as `(top, left, height, width, block)`, do nothing if region is invalid,
+    /// see [`World::region`].
+    FillRegion(usize, usize, usize, usize, Block),
+    /// Replace blocks of the connected area of the same block kind around given position, do nothing if position
+    /// out of range, see [`World::flood_fill`].
+    FloodFill(Pos, Block),
+    /// Replace blocks with a world's, placed with its top left block at `(top, left)`, blocks out of range are
+    /// ignored, see [`World::paste`].
+    ReplaceWorldRegion(usize, usize, World),
+    /// Replace all blocks of a row, do nothing if index out of range or blocks count not equal to width.
+    ReplaceRow(usize, Vec<Block>),
+    /// Replace all blocks of a column, do nothing if index out of range or blocks count not equal to height.
+    ReplaceColumn(usize, Vec<Block>),
+    /// Insert a row of empty block at given index, do nothing if index is larger than height or world is too large.
     InsertRow(usize),
-    /// Insert a column of empty block  at given index.
+    /// Insert a column of empty block at given index, do nothing if index is larger than width or world is too
+    /// large.
     InsertColumn(usize),
-    /// Remove a row at given index.
+    /// Remove a row at given index, do nothing if index out of range or it's the last row.
     RemoveRow(usize),
-    /// Remove a row at given index.
+    /// Remove a column at given index, do nothing if index out of range or it's the last column.
     RemoveColumn(usize),
 }
 
+/// Event of state transitions in a game, returned by [`Game::apply`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GameEvent {
+    /// Cursor moved to the new position.
+    CursorMoved(Pos),
+    /// Block at given position is turned clockwise.
+    BlockRotated(Pos),
+    /// Block at given position is replaced with a different block.
+    BlockReplaced(Pos),
+    /// Game world is reset.
+    WorldReset,
+    /// Game world is rotated, mirrored or transposed as a whole, size may change.
+    WorldTransformed,
+    /// A row is inserted at given index.
+    RowInserted(usize),
+    /// A row at given index is removed.
+    RowRemoved(usize),
+    /// A column is inserted at given index.
+    ColumnInserted(usize),
+    /// A column at given index is removed.
+    ColumnRemoved(usize),
+    /// Game world becomes solved.
+    Solved,
+    /// Game world becomes unsolved.
+    Unsolved,
+}
+
+/// Outcome of a command, returned by [`Game::apply_with_outcome`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ApplyOutcome {
+    /// Game world becomes solved by this command, it's `true` only once until it becomes unsolved again.
+    pub solved_now: bool,
+    /// Game state, world or cursor, is changed by this command.
+    pub changed: bool,
+}
+
 /// Game accept standard commands to a game world, make it playable.
 #[derive(Debug, Clone)]
 pub struct Game {
@@ -36,6 +97,18 @@ pub struct Game {
     row: usize,
     col: usize,
     solved: bool,
+    history: Vec<Command>,
+    record_history: bool,
+    // count of applied commands which changed something
+    moves: usize,
+    // moves count when world becomes solved last time
+    solved_at: Option<usize>,
+    events: Vec<GameEvent>,
+    // world is mutated by commands after last solved check, except rotations which update cache below in place
+    changed: bool,
+    // cache for solved check, see `Game::refresh_solved`
+    correct_edges: usize,
+    empty: bool,
 }
 
 impl Default for Game {
@@ -47,17 +120,28 @@ impl Default for Game {
 impl Game {
     /// Create a new game.
     pub fn new(world: World) -> Self {
-        Self {
-            solved: world.solved(),
+        let mut game = Self {
+            solved: false,
             col: 0,
             row: 0,
             world,
-        }
+            history: Vec::new(),
+            record_history: true,
+            moves: 0,
+            solved_at: None,
+            events: Vec::new(),
+            changed: false,
+            correct_edges: 0,
+            empty: true,
+        };
+        game.refresh_solved();
+        game.solved_at = game.solved.then_some(0);
+        game
     }
 
     /// Get cursor.
-    pub fn cursor(&self) -> (usize, usize) {
-        (self.row, self.col)
+    pub fn cursor(&self) -> Pos {
+        Pos::new(self.row, self.col)
     }
 
     /// Get col of cursor.
@@ -75,73 +159,212 @@ impl Game {
         self.solved
     }
 
+    /// Get count of applied commands since game world becomes solved last time, `None` if it never was solved.
+    ///
+    /// It keeps counting after the world becomes unsolved again, commands changed nothing are not counted.
+    pub fn elapsed_moves_since_solve(&self) -> Option<usize> {
+        self.solved_at.map(|at| self.moves - at)
+    }
+
     /// Get inner game world reference.
     pub fn world(&self) -> &World {
         &self.world
     }
 
+    /// Get applied commands since the last [`Command::Reset`], which is included, in order.
+    ///
+    /// Commands changed nothing are not recorded, and it's always empty when recording is turned off, see
+    /// [`Game::set_record_history`].
+    pub fn history(&self) -> &[Command] {
+        &self.history
+    }
+
+    /// Turn history recording on or off, it's on by default. Turning off also drops recorded commands.
+    ///
+    /// Long running games which never need a replay, e.g. on a server, should turn it off to keep memory bounded.
+    pub fn set_record_history(&mut self, record: bool) {
+        self.record_history = record;
+        if !record {
+            self.history = Vec::new();
+        }
+    }
+
     /// Get inner game world.
     pub fn into_inner(self) -> World {
         self.world
     }
 
+    /// Mutate inner game world directly, solved state is recomputed and cursor is clamped into the world after.
+    pub fn with_world_mut<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut World),
+    {
+        f(&mut self.world);
+        let solved = self.solved;
+        self.refresh_solved();
+        if self.solved && !solved {
+            self.solved_at = Some(self.moves);
+        }
+        self.row = self.row.min(self.world.height().get() - 1);
+        self.col = self.col.min(self.world.width().get() - 1);
+    }
+
+    /// Recompute solved state and its cache from the whole world.
+    fn refresh_solved(&mut self) {
+        self.correct_edges = self.world.count_correct_edges();
+        self.empty = self.world.is_empty();
+        self.update_solved();
+    }
+
+    /// Update solved state from cache, all edges fit means solved, see [`World::count_correct_edges`].
+    fn update_solved(&mut self) {
+        self.solved = !self.empty && self.correct_edges == self.world.count_edges();
+    }
+
     fn mutate_world<F>(&mut self, f: F)
     where
         F: FnOnce(&mut World),
     {
         f(&mut self.world);
-        self.solved = self.world.solved();
+        self.changed = true;
     }
 
     fn reset(&mut self, mut world: World) {
+        // commands before are for another world, drop them so history doesn't grow across levels
+        self.history.clear();
         self.col = 0;
         self.row = 0;
         self.mutate_world(|old| core::mem::swap(old, &mut world));
+        self.events.push(GameEvent::WorldReset);
     }
 
-    fn move_cursor(&mut self, dir: Direction) {
-        match dir {
-            Direction::Up => {
-                if self.row > 0 {
-                    self.row -= 1
-                }
-            }
-            Direction::Right => {
-                if self.col < self.world.width().get() - 1 {
-                    self.col += 1
-                }
-            }
-            Direction::Down => {
-                if self.row < self.world.height().get() - 1 {
-                    self.row += 1
+    /// Replace world with `f`'s result, `cursor` maps cursor position of old world to the new one.
+    fn transform<F, C>(&mut self, f: F, cursor: C)
+    where
+        F: FnOnce(&World) -> World,
+        C: FnOnce(Pos, usize, usize) -> Pos,
+    {
+        let (height, width) = (self.world.height().get(), self.world.width().get());
+        let Pos { row, col } = cursor(self.cursor(), height, width);
+        self.mutate_world(|w| *w = f(w));
+        self.row = row;
+        self.col = col;
+        self.events.push(GameEvent::WorldTransformed);
+    }
+
+    fn rotate_whole_world(&mut self, rotate_blocks: bool) {
+        self.transform(
+            |w| {
+                let mut world = w.rotated();
+                if !rotate_blocks {
+                    // turn blocks back, only their positions are rotated
+                    world.iter_mut().for_each(|(_, _, block)| block.turn_by(3));
                 }
+                world
+            },
+            |pos, height, _| Pos::new(pos.col, height - 1 - pos.row),
+        );
+    }
+
+    fn move_cursor(&mut self, dir: Direction) {
+        if let Some(pos) = self.cursor().step(dir) {
+            if self.world.get(pos.row, pos.col).is_some() {
+                self.row = pos.row;
+                self.col = pos.col;
             }
-            Direction::Left => {
-                if self.col > 0 {
-                    self.col -= 1
-                }
+        }
+    }
+
+    fn rotate_block(&mut self, pos: Pos) {
+        if self.world.get(pos.row, pos.col).is_some() {
+            // rotation only changes edges around the block, update the cache instead of checking the whole world
+            let before = self.world.count_block_correct_edges(pos.row, pos.col);
+            self.world.rotate(pos.row, pos.col);
+            let after = self.world.count_block_correct_edges(pos.row, pos.col);
+            self.correct_edges = self.correct_edges + after - before;
+            self.events.push(GameEvent::BlockRotated(pos));
+        }
+    }
+
+    /// Replace blocks at given positions, positions must be in range.
+    fn replace_blocks<I>(&mut self, blocks: I)
+    where
+        I: IntoIterator<Item = (Pos, Block)>,
+    {
+        for (pos, block) in blocks {
+            let old = self.world.get_mut(pos.row, pos.col).unwrap();
+            if *old != block {
+                *old = block;
+                self.events.push(GameEvent::BlockReplaced(pos));
+                self.changed = true;
             }
-        };
+        }
     }
 
-    fn rotate_block(&mut self, row: usize, col: usize) {
-        self.mutate_world(|w| w.get_mut(row, col).unwrap().rotate());
+    fn replace_block(&mut self, pos: Pos, block: Block) {
+        if self.world.get(pos.row, pos.col).is_some() {
+            self.replace_blocks([(pos, block)]);
+        }
     }
 
-    fn replace_block(&mut self, row: usize, col: usize, block: Block) {
-        self.mutate_world(|w| *w.get_mut(row, col).unwrap() = block);
+    fn clear(&mut self) {
+        let (height, width) = (self.world.height().get(), self.world.width().get());
+        self.fill_region(0, 0, height, width, Block::Empty);
+    }
+
+    fn fill_region(&mut self, top: usize, left: usize, height: usize, width: usize, block: Block) {
+        if let Ok(region) = self.world.region(top, left, height, width) {
+            self.replace_blocks(region.positions().map(|position| (position, block)));
+        }
+    }
+
+    fn flood_fill(&mut self, pos: Pos, block: Block) {
+        let positions = self.world.flood_positions(pos.row, pos.col);
+        self.replace_blocks(positions.into_iter().map(|position| (position, block)));
+    }
+
+    fn replace_world_region(&mut self, top: usize, left: usize, world: &World) {
+        let blocks: Vec<_> = self.world.paste_positions(top, left, world).collect();
+        self.replace_blocks(blocks);
+    }
+
+    fn replace_row(&mut self, index: usize, blocks: Vec<Block>) {
+        if index < self.world.height().get() && blocks.len() == self.world.width().get() {
+            self.replace_blocks(
+                blocks
+                    .into_iter()
+                    .enumerate()
+                    .map(|(col, block)| (Pos::new(index, col), block)),
+            );
+        }
+    }
+
+    fn replace_column(&mut self, index: usize, blocks: Vec<Block>) {
+        if index < self.world.width().get() && blocks.len() == self.world.height().get() {
+            self.replace_blocks(
+                blocks
+                    .into_iter()
+                    .enumerate()
+                    .map(|(row, block)| (Pos::new(row, index), block)),
+            );
+        }
     }
 
     fn insert_row(&mut self, index: usize) {
-        self.mutate_world(|w| w.insert_row(index));
+        if index > self.world.height().get() || self.world.try_insert_row(index).is_err() {
+            return;
+        }
+        self.changed = true;
+        self.events.push(GameEvent::RowInserted(index));
         if self.row >= index {
             self.row += 1;
         }
     }
 
     fn remove_row(&mut self, index: usize) {
-        if self.world.height().get() > 1 {
+        if self.world.height().get() > 1 && index < self.world.height().get() {
             self.mutate_world(|w| w.remove_row(index));
+            self.events.push(GameEvent::RowRemoved(index));
             if self.row == self.world.height().get() {
                 self.row -= 1;
             }
@@ -149,36 +372,475 @@ impl Game {
     }
 
     fn insert_column(&mut self, index: usize) {
-        self.mutate_world(|w| w.insert_column(index));
+        if index > self.world.width().get() || self.world.try_insert_column(index).is_err() {
+            return;
+        }
+        self.changed = true;
+        self.events.push(GameEvent::ColumnInserted(index));
         if self.col >= index {
             self.col += 1;
         }
     }
 
     fn remove_column(&mut self, index: usize) {
-        if self.world.width().get() > 1 {
+        if self.world.width().get() > 1 && index < self.world.width().get() {
             self.mutate_world(|w| w.remove_column(index));
+            self.events.push(GameEvent::ColumnRemoved(index));
             if self.col == self.world.width().get() {
                 self.col -= 1;
             }
         }
     }
 
-    /// Apply a command in this game.
-    pub fn apply(&mut self, command: Command) {
+    /// Apply a command in this game, returns events of state transitions it caused, in order.
+    ///
+    /// [`GameEvent::CursorMoved`] and [`GameEvent::Solved`]/[`GameEvent::Unsolved`] are always the last ones,
+    /// command that changes nothing returns no event.
+    pub fn apply(&mut self, command: Command) -> Vec<GameEvent> {
+        self.apply_batch(core::iter::once(command))
+    }
+
+    /// Apply a command in this game, returns whether it solved the world or changed anything, see [`ApplyOutcome`].
+    pub fn apply_with_outcome(&mut self, command: Command) -> ApplyOutcome {
+        let events = self.apply(command);
+        ApplyOutcome {
+            solved_now: events.contains(&GameEvent::Solved),
+            changed: !events.is_empty(),
+        }
+    }
+
+    /// Apply many commands in order, solved state is only checked once after all commands are applied.
+    ///
+    /// Returns events like [`Game::apply`], [`GameEvent::CursorMoved`] follows events of each command, but
+    /// [`GameEvent::Solved`]/[`GameEvent::Unsolved`] is only emitted at last if the solved state changed.
+    pub fn apply_batch<I>(&mut self, commands: I) -> Vec<GameEvent>
+    where
+        I: IntoIterator<Item = Command>,
+    {
+        let solved = self.solved;
+
+        for command in commands {
+            self.apply_command(command);
+        }
+
+        if self.changed {
+            self.changed = false;
+            self.refresh_solved();
+        } else {
+            self.update_solved();
+        }
+        if self.solved != solved {
+            if self.solved {
+                self.solved_at = Some(self.moves);
+            }
+            self.events.push(if self.solved {
+                GameEvent::Solved
+            } else {
+                GameEvent::Unsolved
+            });
+        }
+
+        core::mem::take(&mut self.events)
+    }
+
+    fn apply_command(&mut self, command: Command) {
+        let cursor = self.cursor();
+        let events = self.events.len();
+        let recorded = self.record_history.then(|| command.clone());
+
         match command {
             Command::Noop => (),
             Command::Reset(world) => self.reset(world),
+            Command::Clear => self.clear(),
             Command::MoveCursor(dir) => self.move_cursor(dir),
-            Command::RotateCursorBlock => self.rotate_block(self.row, self.col),
-            Command::RotateBlock(row, col) => self.rotate_block(row, col),
-            Command::RotateWholeWorld(_) => unimplemented!(),
-            Command::ReplaceCursorBlock(block) => self.replace_block(self.row, self.col, block),
-            Command::ReplaceBlock(row, col, block) => self.replace_block(row, col, block),
+            Command::RotateCursorBlock => self.rotate_block(self.cursor()),
+            Command::RotateBlock(pos) => self.rotate_block(pos),
+            Command::RotateWholeWorld(rotate_blocks) => self.rotate_whole_world(rotate_blocks),
+            Command::FlipHorizontally => self.transform(World::flipped_horizontally, |pos, _, width| {
+                Pos::new(pos.row, width - 1 - pos.col)
+            }),
+            Command::FlipVertically => self.transform(World::flipped_vertically, |pos, height, _| {
+                Pos::new(height - 1 - pos.row, pos.col)
+            }),
+            Command::Transpose => self.transform(World::transposed, |pos, _, _| Pos::new(pos.col, pos.row)),
+            Command::ReplaceCursorBlock(block) => self.replace_block(self.cursor(), block),
+            Command::ReplaceBlock(pos, block) => self.replace_block(pos, block),
+            Command::FillRegion(top, left, height, width, block) => self.fill_region(top, left, height, width, block),
+            Command::FloodFill(pos, block) => self.flood_fill(pos, block),
+            Command::ReplaceWorldRegion(top, left, world) => self.replace_world_region(top, left, &world),
+            Command::ReplaceRow(index, blocks) => self.replace_row(index, blocks),
+            Command::ReplaceColumn(index, blocks) => self.replace_column(index, blocks),
             Command::InsertRow(index) => self.insert_row(index),
             Command::InsertColumn(index) => self.insert_column(index),
             Command::RemoveRow(index) => self.remove_row(index),
             Command::RemoveColumn(index) => self.remove_column(index),
         }
+
+        if self.cursor() != cursor {
+            self.events.push(GameEvent::CursorMoved(self.cursor()));
+        }
+
+        if self.events.len() > events {
+            self.moves += 1;
+            self.history.extend(recorded);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use super::*;
+
+    fn world(s: &str) -> World {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn out_of_range_row_and_column_commands_are_ignored() {
+        for content in ["><", "79\n31"] {
+            let initial = world(content);
+            let game = Game::from_notation(initial.clone(), "ic9 dc9 ir9 dr9 ic3 dc3 ir3 dr3").unwrap();
+            assert_eq!(game.world(), &initial);
+            assert_eq!(game.cursor(), Pos::new(0, 0));
+        }
+    }
+
+    #[test]
+    fn out_of_range_block_commands_are_ignored() {
+        let initial = world("79\n31");
+        let mut game = Game::new(initial.clone());
+        // column past the width must not wrap to the next row
+        for pos in [
+            Pos::new(0, 2),
+            Pos::new(2, 0),
+            Pos::new(99, 99),
+            Pos::new(usize::MAX, usize::MAX),
+        ] {
+            game.apply(Command::RotateBlock(pos));
+            game.apply(Command::ReplaceBlock(pos, Block::Cross));
+        }
+        assert_eq!(game.world(), &initial);
+        assert!(!game.solved());
+    }
+
+    #[test]
+    fn with_world_mut_updates_solved_state_and_cursor() {
+        let mut game = Game::new(world("79\n31"));
+        game.apply_batch([
+            Command::MoveCursor(Direction::Down),
+            Command::MoveCursor(Direction::Right),
+        ]);
+        assert!(!game.solved());
+
+        game.with_world_mut(|w| {
+            w.rotate(1, 0);
+            w.rotate(1, 1);
+            w.rotate(1, 1);
+            w.rotate(1, 1);
+        });
+        assert!(game.solved());
+
+        game.with_world_mut(|w| w.rotate(0, 0));
+        assert!(!game.solved());
+
+        game.with_world_mut(|w| *w = "><".parse().unwrap());
+        assert!(game.solved());
+        assert_eq!(game.cursor(), Pos::new(0, 1));
+    }
+
+    #[test]
+    fn replace_row_and_column() {
+        let block = |c: &str| c.parse::<Block>().unwrap();
+        let mut game = Game::new(world("79\n31"));
+
+        game.apply(Command::ReplaceRow(1, vec![block("1"), block("3")]));
+        assert_eq!(game.world(), &world("79\n13"));
+        assert!(game.solved());
+
+        game.apply(Command::ReplaceColumn(0, vec![block("v"), block("^")]));
+        assert_eq!(game.world(), &world("v1\n2,2\nv9\n^3"));
+        assert!(!game.solved());
+    }
+
+    #[test]
+    fn wrong_length_row_and_column_are_ignored() {
+        let initial = world("79\n31");
+        let mut game = Game::new(initial.clone());
+
+        for blocks in [vec![], vec![Block::Cross], vec![Block::Cross; 3]] {
+            game.apply(Command::ReplaceRow(0, blocks.clone()));
+            game.apply(Command::ReplaceColumn(0, blocks));
+        }
+        game.apply(Command::ReplaceRow(2, vec![Block::Cross; 2]));
+        game.apply(Command::ReplaceColumn(2, vec![Block::Cross; 2]));
+
+        assert_eq!(game.world(), &initial);
+        assert!(game.history().is_empty());
+    }
+
+    #[test]
+    fn event_sequences() {
+        use GameEvent::*;
+
+        let mut game = Game::new(world("79\n31"));
+        let mut expect = |command: Command, events: &[GameEvent]| {
+            let notation = command.to_string();
+            assert_eq!(game.apply(command), events, "{notation}");
+        };
+
+        expect(Command::MoveCursor(Direction::Down), &[CursorMoved(Pos::new(1, 0))]);
+        expect(Command::MoveCursor(Direction::Down), &[]);
+        expect(Command::RotateCursorBlock, &[BlockRotated(Pos::new(1, 0))]);
+        expect(Command::RotateBlock(Pos::new(1, 1)), &[BlockRotated(Pos::new(1, 1))]);
+        expect(Command::RotateBlock(Pos::new(1, 1)), &[BlockRotated(Pos::new(1, 1))]);
+        expect(
+            Command::RotateBlock(Pos::new(1, 1)),
+            &[BlockRotated(Pos::new(1, 1)), Solved],
+        );
+        expect(
+            Command::ReplaceBlock(Pos::new(0, 0), Block::Cross),
+            &[BlockReplaced(Pos::new(0, 0)), Unsolved],
+        );
+        // same block, nothing changes
+        expect(Command::ReplaceBlock(Pos::new(0, 0), Block::Cross), &[]);
+        // cursor follows its block
+        expect(Command::InsertRow(0), &[RowInserted(0), CursorMoved(Pos::new(2, 0))]);
+        expect(Command::InsertColumn(2), &[ColumnInserted(2)]);
+        expect(Command::RemoveColumn(2), &[ColumnRemoved(2)]);
+        expect(Command::RemoveRow(2), &[RowRemoved(2), CursorMoved(Pos::new(1, 0))]);
+        expect(Command::Transpose, &[WorldTransformed, CursorMoved(Pos::new(0, 1))]);
+        expect(
+            Command::Reset(world("79\n13")),
+            &[WorldReset, CursorMoved(Pos::new(0, 0)), Solved],
+        );
+        expect(
+            Command::FillRegion(0, 0, 1, 2, Block::Empty),
+            &[BlockReplaced(Pos::new(0, 0)), BlockReplaced(Pos::new(0, 1)), Unsolved],
+        );
+        expect(Command::Noop, &[]);
+    }
+
+    #[test]
+    fn batch_events_report_solved_state_change_of_whole_batch() {
+        let mut game = Game::new(world("79\n31"));
+        // solved after the fourth command, unsolved again after the fifth
+        let turns = [(1, 0), (1, 1), (1, 1), (1, 1), (1, 1)].map(|(row, col)| Command::RotateBlock(Pos::new(row, col)));
+        let events = game.apply_batch(turns);
+        assert_eq!(events.len(), 5);
+        assert!(events.iter().all(|event| matches!(event, GameEvent::BlockRotated(_))));
+
+        let events = game.apply_batch([(1, 1); 3].map(|(row, col)| Command::RotateBlock(Pos::new(row, col))));
+        assert_eq!(events.last(), Some(&GameEvent::Solved));
+    }
+
+    #[test]
+    fn history_only_records_commands_changed_something() {
+        let mut game = Game::new(world("79\n31"));
+        game.apply(Command::Noop);
+        game.apply(Command::RotateBlock(Pos::new(9, 9)));
+        game.apply(Command::MoveCursor(Direction::Up));
+        assert!(game.history().is_empty());
+
+        game.apply(Command::RotateCursorBlock);
+        game.apply(Command::MoveCursor(Direction::Right));
+        assert_eq!(game.history().len(), 2);
+    }
+
+    #[test]
+    fn reset_drops_previous_history() {
+        let mut game = Game::new(world("79\n31"));
+        game.apply_batch([Command::RotateCursorBlock, Command::MoveCursor(Direction::Down)]);
+        game.apply(Command::Reset(world("><")));
+        game.apply(Command::RotateCursorBlock);
+        assert_eq!(game.to_notation_string(), "reset r");
+    }
+
+    #[test]
+    fn history_can_be_turned_off() {
+        let mut game = Game::new(world("><"));
+        game.apply(Command::RotateCursorBlock);
+        game.set_record_history(false);
+        assert!(game.history().is_empty());
+        for _ in 0..3 {
+            game.apply(Command::RotateCursorBlock);
+        }
+        assert!(game.history().is_empty());
+        assert!(game.solved());
+        assert_eq!(game.elapsed_moves_since_solve(), Some(0));
+        game.apply(Command::RotateCursorBlock);
+        assert_eq!(game.elapsed_moves_since_solve(), Some(1));
+    }
+
+    #[test]
+    fn apply_outcome_flags_flip_on_solving_move() {
+        let mut game = Game::new(world("79\n31"));
+        let rotate = Command::RotateBlock(Pos::new(1, 1));
+        let outcome = |solved_now, changed| ApplyOutcome { solved_now, changed };
+
+        assert_eq!(game.elapsed_moves_since_solve(), None);
+        assert_eq!(
+            game.apply_with_outcome(Command::MoveCursor(Direction::Up)),
+            outcome(false, false)
+        );
+        assert_eq!(
+            game.apply_with_outcome(Command::RotateBlock(Pos::new(1, 0))),
+            outcome(false, true)
+        );
+        assert_eq!(game.apply_with_outcome(rotate.clone()), outcome(false, true));
+        assert_eq!(game.apply_with_outcome(rotate.clone()), outcome(false, true));
+        assert_eq!(game.apply_with_outcome(rotate.clone()), outcome(true, true));
+        assert_eq!(game.elapsed_moves_since_solve(), Some(0));
+
+        // fires only once, until the world is unsolved and solved again
+        assert_eq!(
+            game.apply_with_outcome(Command::MoveCursor(Direction::Right)),
+            outcome(false, true)
+        );
+        assert_eq!(game.elapsed_moves_since_solve(), Some(1));
+        for _ in 0..3 {
+            assert_eq!(game.apply_with_outcome(rotate.clone()), outcome(false, true));
+        }
+        assert_eq!(game.apply_with_outcome(rotate), outcome(true, true));
+        assert_eq!(game.elapsed_moves_since_solve(), Some(0));
+    }
+
+    #[test]
+    fn clear_keeps_size_and_cursor() {
+        let mut game = Game::new(world("v1\n2,3\n7-9\n1-3\n"));
+        assert!(game.solved());
+        game.apply_batch([
+            Command::MoveCursor(Direction::Down),
+            Command::MoveCursor(Direction::Right),
+        ]);
+
+        let events = game.apply(Command::Clear);
+        assert_eq!(events.last(), Some(&GameEvent::Unsolved));
+        assert_eq!(game.world().size(), world("v1\n2,3\n7-9\n1-3\n").size());
+        assert!(game.world().is_empty());
+        assert_eq!((game.row(), game.col()), (1, 1));
+        assert!(!game.solved());
+
+        // already blank, nothing changes
+        assert_eq!(game.apply(Command::Clear), []);
+        assert_eq!(game.to_notation_string().split(' ').next_back(), Some("clear"));
+    }
+
+    #[test]
+    fn flood_fill_command_tracks_solved_state_and_history() {
+        let mut game = Game::new(world("v1\n3,3\n7-9\n/ /\n1-3\n"));
+        let events = game.apply(Command::FloodFill(Pos::new(0, 1), Block::Cross));
+        assert_eq!(events, [GameEvent::BlockReplaced(Pos::new(0, 1)), GameEvent::Unsolved]);
+
+        let events = game.apply(Command::FloodFill(Pos::new(0, 1), Block::Through(Direction::Left)));
+        assert_eq!(events, [GameEvent::BlockReplaced(Pos::new(0, 1)), GameEvent::Solved]);
+
+        // no-op fill is not recorded
+        assert_eq!(game.apply(Command::FloodFill(Pos::new(5, 5), Block::Cross)), []);
+        assert_eq!(game.history().len(), 2);
+
+        let replayed = Game::from_notation(world("v1\n3,3\n7-9\n/ /\n1-3\n"), &game.to_notation_string()).unwrap();
+        assert_eq!(replayed.world(), game.world());
+    }
+
+    #[test]
+    fn last_row_and_column_are_not_removed() {
+        let initial = world(">");
+        let mut game = Game::new(initial.clone());
+        assert!(game.apply(Command::RemoveRow(0)).is_empty());
+        assert!(game.apply(Command::RemoveColumn(0)).is_empty());
+        assert_eq!(game.world(), &initial);
+    }
+
+    // xorshift64, enough to make the cases different and reproducible without a dependency
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn block(&mut self) -> Block {
+            let chars = " ^>v<-/1793862455";
+            chars[self.below(chars.len())..]
+                .chars()
+                .next()
+                .unwrap()
+                .to_string()
+                .parse()
+                .unwrap()
+        }
+
+        fn direction(&mut self) -> Direction {
+            [Direction::Up, Direction::Right, Direction::Down, Direction::Left][self.below(4)]
+        }
+    }
+
+    fn random_command(rng: &mut Rng, world: &World) -> Command {
+        let (height, width) = (world.height().get(), world.width().get());
+        // indexes may be one past the end, to also run the out of range paths
+        let pos = |rng: &mut Rng| Pos::new(rng.below(height + 1), rng.below(width + 1));
+
+        match rng.below(20) {
+            0 => Command::Noop,
+            1 => Command::MoveCursor(rng.direction()),
+            2..=5 => Command::RotateCursorBlock,
+            6..=8 => Command::RotateBlock(pos(rng)),
+            9 => Command::RotateWholeWorld(rng.below(2) == 0),
+            10 => Command::FlipHorizontally,
+            11 => Command::FlipVertically,
+            12 => Command::Transpose,
+            13 => Command::ReplaceCursorBlock(rng.block()),
+            14 => Command::ReplaceBlock(pos(rng), rng.block()),
+            15 => Command::FloodFill(pos(rng), rng.block()),
+            16 => Command::FillRegion(rng.below(height), rng.below(width), 1 + rng.below(2), 1, rng.block()),
+            17 => Command::ReplaceRow(rng.below(height + 1), (0..width).map(|_| rng.block()).collect()),
+            18 => match rng.below(4) {
+                0 => Command::InsertRow(rng.below(height + 2)),
+                1 => Command::InsertColumn(rng.below(width + 2)),
+                2 => Command::RemoveRow(rng.below(height + 1)),
+                _ => Command::RemoveColumn(rng.below(width + 1)),
+            },
+            _ => Command::Clear,
+        }
+    }
+
+    #[test]
+    fn cached_solved_state_matches_world() {
+        // small solved worlds, so random turns solve them again now and then
+        let worlds = ["><", "v\n^", "79\n13", "7-9\n1-3", ">8<\n 2 ", "v\n5\n^"];
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let mut solved_count = 0;
+
+        for i in 0..400 {
+            let mut initial = world(worlds[i % worlds.len()]);
+            initial.set_open_borders(i % 3 == 0);
+            let mut game = Game::new(initial);
+            assert_eq!(game.solved(), game.world().solved());
+
+            for _ in 0..60 {
+                let command = random_command(&mut rng, game.world());
+                let notation = command.to_string();
+                game.apply(command);
+                assert_eq!(
+                    game.solved(),
+                    game.world().solved(),
+                    "after {notation} in {:?}",
+                    game.world()
+                );
+                solved_count += usize::from(game.solved());
+            }
+        }
+
+        // make sure both states are covered
+        assert!(solved_count > 100, "only {solved_count} solved states");
     }
 }
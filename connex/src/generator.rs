@@ -0,0 +1,369 @@
+//! Random level generator.
+//!
+//! Worlds are built from a random spanning tree of the grid, so they are always solvable.
+
+use alloc::{vec, vec::Vec};
+use core::num::NonZeroUsize;
+
+use rand::Rng;
+
+use crate::{analysis::difficulty, Block, Direction, World};
+
+/// How many worlds [`generate_with_difficulty`] tries before giving up.
+pub const DIFFICULTY_ATTEMPTS: usize = 1000;
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+fn neighbor(height: usize, width: usize, index: usize, dir: Direction) -> Option<usize> {
+    let (row, col) = (index / width, index % width);
+    match dir {
+        Direction::Up if row > 0 => Some(index - width),
+        Direction::Right if col + 1 < width => Some(index + 1),
+        Direction::Down if row + 1 < height => Some(index + width),
+        Direction::Left if col > 0 => Some(index - 1),
+        _ => None,
+    }
+}
+
+/// Convert a set of connected sides to the block that connects exactly those sides.
+fn block_of(sides: u8) -> Block {
+    let connected = |dir: Direction| sides & dir.bit() != 0;
+
+    match sides.count_ones() {
+        0 => Block::Empty,
+        1 => Block::Endpoint(DIRECTIONS.into_iter().find(|d| connected(*d)).unwrap()),
+        2 => match DIRECTIONS.into_iter().find(|d| connected(*d) && connected(d.rotated())) {
+            Some(dir) => Block::Turn(dir),
+            None if connected(Direction::Up) => Block::Through(Direction::Up),
+            None => Block::Through(Direction::Left),
+        },
+        3 => Block::Fork(DIRECTIONS.into_iter().find(|d| !connected(*d)).unwrap()),
+        _ => Block::Cross,
+    }
+}
+
+/// Symmetry of generated worlds, names match fields of [`Symmetries`](crate::analysis::Symmetries).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Symmetry {
+    /// No symmetry.
+    #[default]
+    None,
+    /// Same after mirrored left to right.
+    Horizontal,
+    /// Same after mirrored top to bottom.
+    Vertical,
+    /// Same after rotated 180 degrees.
+    Rotational180,
+}
+
+impl Symmetry {
+    /// Get index of the block which `index` is mapped to.
+    fn image(&self, height: usize, width: usize, index: usize) -> usize {
+        let (row, col) = (index / width, index % width);
+        match self {
+            Self::None => index,
+            Self::Horizontal => row * width + (width - 1 - col),
+            Self::Vertical => (height - 1 - row) * width + col,
+            Self::Rotational180 => height * width - 1 - index,
+        }
+    }
+
+    /// Get side which `dir` side is mapped to.
+    fn image_direction(&self, dir: Direction) -> Direction {
+        match (self, dir) {
+            (Self::Horizontal, Direction::Left | Direction::Right) => dir.opposite(),
+            (Self::Vertical, Direction::Up | Direction::Down) => dir.opposite(),
+            (Self::Rotational180, _) => dir.opposite(),
+            _ => dir,
+        }
+    }
+
+    fn image_sides(&self, sides: u8) -> u8 {
+        DIRECTIONS
+            .into_iter()
+            .filter(|dir| sides & dir.bit() != 0)
+            .fold(0, |result, dir| result | self.image_direction(dir).bit())
+    }
+}
+
+/// Options of generator.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Options {
+    /// Fraction of non-empty blocks, in range `[0, 1]`. At least two blocks are used if world is not 1x1.
+    pub density: f64,
+    /// Whether [`Block::Cross`] can be generated.
+    pub allow_cross: bool,
+    /// Symmetry of generated world.
+    pub symmetry: Symmetry,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            allow_cross: true,
+            symmetry: Symmetry::None,
+        }
+    }
+}
+
+/// Generate a world in solved state, using default [`Options`].
+///
+/// See [`generate_solved_with`] for how the world is built.
+///
+/// ## Panics
+///
+/// height * width > usize::MAX.
+pub fn generate_solved(height: NonZeroUsize, width: NonZeroUsize, rng: &mut impl Rng) -> World {
+    generate_solved_with(height, width, &Options::default(), rng)
+}
+
+/// Generate a world in solved state.
+///
+/// A random spanning tree of blocks is built first, then every block is chosen by the tree edges it has:
+/// a leaf becomes a [`Block::Endpoint`], degree 2 becomes [`Block::Through`] or [`Block::Turn`], degree 3 becomes
+/// [`Block::Fork`] and degree 4 becomes [`Block::Cross`].
+///
+/// The tree stops growing when it contains enough blocks for [`Options::density`], blocks not in the tree are
+/// [`Block::Empty`]. If [`Options::allow_cross`] is false, tree nodes can have at most 3 edges, so some blocks may
+/// not be reachable and stay empty even with density 1.
+///
+/// A 1x1 world has no edge, so its only block is [`Block::Empty`].
+///
+/// ## Symmetry
+///
+/// With [`Options::symmetry`], the tree is only built in the half of world whose blocks are mapped to themselves or
+/// to a block with larger index, e.g. the left half and the middle column for [`Symmetry::Horizontal`]. Then every
+/// edge is copied to its image in the other half.
+///
+/// If the tree contains a block mapped to itself, like one in the middle column, both halves share it and are
+/// already connected. Otherwise a seam is needed: an edge from a tree block to a block outside the half, whose image
+/// is also in the tree, is added together with its image. The tree keeps growing beyond the density target until
+/// such an edge exists, and one is chosen randomly. If [`Options::allow_cross`] is false, it grows until a seam
+/// which doesn't make a [`Block::Cross`] exists, a cross is only used when the tree can't grow any more. So the result
+/// is one connected network, may contain loops across the seam, and only depends on the state of `rng`.
+///
+/// ## Panics
+///
+/// height * width > usize::MAX.
+pub fn generate_solved_with(height: NonZeroUsize, width: NonZeroUsize, options: &Options, rng: &mut impl Rng) -> World {
+    let (h, w) = (height.get(), width.get());
+    let size = h.checked_mul(w).expect("too many blocks");
+    let symmetry = options.symmetry;
+
+    let image = |index: usize| symmetry.image(h, w, index);
+    let in_half = |index: usize| index <= image(index);
+    // sides a block will finally have, a block mapped to itself also gets image of its own sides
+    let final_sides = |index: usize, sides: u8| {
+        if image(index) == index {
+            sides | symmetry.image_sides(sides)
+        } else {
+            sides
+        }
+    };
+
+    let half_size = (0..size).filter(|i| in_half(*i)).count();
+    let target =
+        ((options.density.clamp(0.0, 1.0) * half_size as f64 + 0.5) as usize).clamp(half_size.min(2), half_size);
+
+    let mut sides = vec![0u8; size];
+    let mut visited = vec![false; size];
+
+    // halves are connected if they share a block
+    let shared = |visited: &[bool]| (0..size).any(|i| visited[i] && image(i) == i);
+    // edges from tree to a block outside the half, whose image is in the tree.
+    // when `no_cross`, blocks on both end of the edge must have less than 3 sides before adding it.
+    let seams = |visited: &[bool], sides: &[u8], no_cross: bool| -> Vec<(usize, Direction)> {
+        (0..size)
+            .filter(|i| visited[*i])
+            .flat_map(|from| DIRECTIONS.into_iter().map(move |dir| (from, dir)))
+            .filter(|(from, dir)| {
+                neighbor(h, w, *from, *dir).is_some_and(|to| {
+                    !in_half(to)
+                        && visited[image(to)]
+                        && (!no_cross || sides[*from].count_ones() < 3 && sides[image(to)].count_ones() < 3)
+                })
+            })
+            .collect()
+    };
+    let need_seam = |visited: &[bool]| half_size < size && !shared(visited);
+
+    // randomized Prim's algorithm, frontier contains edges from visited blocks to maybe unvisited blocks.
+    let mut frontier = Vec::new();
+    let start = loop {
+        let start = rng.gen_range(0..size);
+        if in_half(start) {
+            break start;
+        }
+    };
+    visited[start] = true;
+    frontier.extend(DIRECTIONS.into_iter().map(|dir| (start, dir)));
+    let mut count = 1;
+
+    while (count < target || need_seam(&visited) && seams(&visited, &sides, !options.allow_cross).is_empty())
+        && !frontier.is_empty()
+    {
+        let (from, dir) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        let to = match neighbor(h, w, from, dir) {
+            Some(to) if !visited[to] && in_half(to) => to,
+            _ => continue,
+        };
+
+        if !options.allow_cross && final_sides(from, sides[from] | dir.bit()).count_ones() > 3 {
+            continue;
+        }
+
+        visited[to] = true;
+        sides[from] |= dir.bit();
+        sides[to] |= dir.opposite().bit();
+        frontier.extend(DIRECTIONS.into_iter().map(|dir| (to, dir)));
+        count += 1;
+    }
+
+    let mut seam_candidates = Vec::new();
+    if need_seam(&visited) {
+        seam_candidates = seams(&visited, &sides, !options.allow_cross);
+        if seam_candidates.is_empty() {
+            // the tree can't grow any more, keep the network connected even it needs a cross
+            seam_candidates = seams(&visited, &sides, false);
+        }
+    }
+
+    for index in 0..size {
+        if in_half(index) {
+            sides[index] = final_sides(index, sides[index]);
+        } else {
+            sides[index] = symmetry.image_sides(sides[image(index)]);
+        }
+    }
+
+    if !seam_candidates.is_empty() {
+        let (from, dir) = seam_candidates[rng.gen_range(0..seam_candidates.len())];
+        let to = neighbor(h, w, from, dir).unwrap();
+        for (from, to, dir) in [(from, to, dir), (image(from), image(to), symmetry.image_direction(dir))] {
+            sides[from] |= dir.bit();
+            sides[to] |= dir.opposite().bit();
+        }
+    }
+
+    World::new_from_blocks(height, width, sides.into_iter().map(block_of).collect())
+}
+
+/// Generate a shuffled, guaranteed solvable world.
+///
+/// See [`generate_solved`] for how the world is built.
+///
+/// ## Panics
+///
+/// height * width > usize::MAX.
+pub fn generate(height: NonZeroUsize, width: NonZeroUsize, rng: &mut impl Rng) -> World {
+    let mut world = generate_solved(height, width, rng);
+    world.shuffle(rng);
+    world
+}
+
+/// Generate a shuffled, guaranteed solvable world like [`generate`] with a seed, same seed always gives same
+/// world on every platform, so it can be shared, e.g. as a daily level.
+///
+/// ## Panics
+///
+/// height * width > usize::MAX.
+pub fn generate_seeded(height: NonZeroUsize, width: NonZeroUsize, seed: u64) -> World {
+    generate(height, width, &mut crate::seeded_rng(seed))
+}
+
+/// Generate a shuffled world whose [`difficulty`] is close to `target_difficulty`.
+///
+/// Worlds are generated until one scores in `[target_difficulty * 0.9, target_difficulty * 1.1]`. After
+/// [`DIFFICULTY_ATTEMPTS`] tries, the closest one is returned.
+///
+/// ## Panics
+///
+/// height * width > usize::MAX.
+pub fn generate_with_difficulty(
+    height: NonZeroUsize, width: NonZeroUsize, target_difficulty: u32, rng: &mut impl Rng,
+) -> World {
+    let target = u64::from(target_difficulty);
+    let mut closest: Option<(u64, World)> = None;
+
+    for _ in 0..DIFFICULTY_ATTEMPTS {
+        let world = generate(height, width, rng);
+        let score = u64::from(difficulty(&world));
+
+        if score * 10 >= target * 9 && score * 10 <= target * 11 {
+            return world;
+        }
+
+        let distance = score.abs_diff(target);
+        if closest.as_ref().map(|(d, _)| distance < *d).unwrap_or(true) {
+            closest = Some((distance, world));
+        }
+    }
+
+    closest.unwrap().1
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+
+    #[test]
+    fn seeded_generation_is_pinned() {
+        let size = NonZeroUsize::new(3).unwrap();
+        let world = generate_seeded(size, size, 20240229);
+        // changing this breaks shared seeds like the daily level, bump it only on purpose
+        assert_eq!(world.to_string(), "v1\n3,3\n<<<\n352\nv1<\n");
+        assert_eq!(world, generate_seeded(size, size, 20240229));
+    }
+
+    #[test]
+    fn generated_worlds_are_solvable() {
+        for seed in 0..100 {
+            let mut rng = crate::seeded_rng(seed);
+            let height = NonZeroUsize::new(rng.gen_range(1..=8)).unwrap();
+            let width = NonZeroUsize::new(rng.gen_range(2..=8)).unwrap();
+
+            let world = generate(height, width, &mut rng);
+            assert_eq!(world.size(), (height, width));
+            assert!(world.is_solvable(), "seed {seed}: {world:?}");
+            // spanning tree reaches every block
+            assert!(world.iter().all(|(_, _, block)| *block != Block::Empty), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn symmetric_worlds_are_solved_connected_and_symmetric() {
+        let symmetries = [Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Rotational180];
+        let sizes = [(1, 2), (2, 1), (2, 2), (3, 3), (4, 5), (5, 4), (6, 6), (7, 3)];
+
+        for seed in 0..20 {
+            for symmetry in symmetries {
+                for (height, width) in sizes {
+                    let (height, width) = (NonZeroUsize::new(height).unwrap(), NonZeroUsize::new(width).unwrap());
+                    let options = Options {
+                        density: [1.0, 0.5][seed as usize % 2],
+                        allow_cross: seed % 3 != 0,
+                        symmetry,
+                    };
+                    let world = generate_solved_with(height, width, &options, &mut crate::seeded_rng(seed));
+                    let case = format!("{symmetry:?} {height}x{width} seed {seed}: {world:?}");
+
+                    assert!(world.solved(), "{case}");
+                    assert_eq!(world.components().len(), 1, "{case}");
+                    let found = world.symmetries();
+                    let expected = match symmetry {
+                        Symmetry::Horizontal => found.horizontal_mirror,
+                        Symmetry::Vertical => found.vertical_mirror,
+                        _ => found.rotation_180,
+                    };
+                    assert!(expected, "{case}");
+
+                    let again = generate_solved_with(height, width, &options, &mut crate::seeded_rng(seed));
+                    assert_eq!(world, again, "{case}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Iterators over blocks of a [`World`](crate::World), with their positions.
+
+use alloc::vec::{self, Vec};
+use core::{iter::Enumerate, slice};
+
+use crate::Block;
+
+macro_rules! position_iter {
+    ($iter:ty, $item:ty) => {
+        impl<'a> Iterator for $iter {
+            type Item = (usize, usize, $item);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let width = self.width;
+                self.inner.next().map(|(i, block)| (i / width, i % width, block))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+        }
+
+        impl<'a> DoubleEndedIterator for $iter {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let width = self.width;
+                self.inner
+                    .next_back()
+                    .map(|(i, block)| (i / width, i % width, block))
+            }
+        }
+
+        impl<'a> ExactSizeIterator for $iter {}
+    };
+}
+
+/// Consuming iterator over blocks of a world as `(row, col, block)`, in row-major order.
+///
+/// Created by [`World`](crate::World)'s [`IntoIterator`] implementation.
+#[derive(Debug, Clone)]
+pub struct IntoIter {
+    inner: Enumerate<vec::IntoIter<Block>>,
+    width: usize,
+}
+
+position_iter!(IntoIter, Block);
+
+impl IntoIter {
+    pub(crate) fn new(blocks: Vec<Block>, width: usize) -> Self {
+        Self {
+            inner: blocks.into_iter().enumerate(),
+            width,
+        }
+    }
+}
+
+/// Iterator over blocks of a world as `(row, col, &block)`, in row-major order.
+///
+/// Created by [`World::iter`](crate::World::iter).
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    inner: Enumerate<slice::Iter<'a, Block>>,
+    width: usize,
+}
+
+position_iter!(Iter<'a>, &'a Block);
+
+impl<'a> Iter<'a> {
+    pub(crate) fn new(blocks: &'a [Block], width: usize) -> Self {
+        Self {
+            inner: blocks.iter().enumerate(),
+            width,
+        }
+    }
+}
+
+/// Iterator over mutable blocks of a world as `(row, col, &mut block)`, in row-major order.
+///
+/// Created by [`World::iter_mut`](crate::World::iter_mut).
+#[derive(Debug)]
+pub struct IterMut<'a> {
+    inner: Enumerate<slice::IterMut<'a, Block>>,
+    width: usize,
+}
+
+position_iter!(IterMut<'a>, &'a mut Block);
+
+impl<'a> IterMut<'a> {
+    pub(crate) fn new(blocks: &'a mut [Block], width: usize) -> Self {
+        Self {
+            inner: blocks.iter_mut().enumerate(),
+            width,
+        }
+    }
+}
@@ -2,18 +2,63 @@
 #![warn(missing_docs, missing_debug_implementations)]
 #![deny(warnings)]
 #![forbid(unsafe_code)]
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! # Connex
 //!
 //! Base library for connex gameplay logic.
+//!
+//! The `random` feature doesn't need OS randomness, so it also works on `wasm32-unknown-unknown`, use seeded
+//! helpers like `World::shuffle_seeded` there.
 
+mod art;
 mod block;
+mod cow;
 mod game;
+mod notation;
+mod patch;
+mod pos;
+mod region;
+mod solver;
 mod world;
 
+#[cfg(feature = "render")]
+mod render;
+#[cfg(feature = "svg")]
+mod svg;
+
+pub mod analysis;
+#[cfg(feature = "bot")]
+pub mod bot;
+#[cfg(feature = "generator")]
+pub mod generator;
+pub mod iter;
+pub mod prelude;
+pub mod shape;
+
 extern crate alloc;
 
+/// RNG of seeded helpers, ChaCha8 is used because its output is portable, unlike [`rand::rngs::SmallRng`].
+#[cfg(feature = "random")]
+fn seeded_rng(seed: u64) -> rand_chacha::ChaCha8Rng {
+    rand::SeedableRng::seed_from_u64(seed)
+}
+
+pub use art::ArtStyle;
 pub use block::{Block, Direction};
-pub use game::{Command, Game};
-pub use world::World;
+pub use cow::CowWorld;
+pub use game::{ApplyOutcome, Command, Game, GameEvent};
+pub use notation::NotationParseError;
+pub use patch::WorldPatch;
+pub use pos::Pos;
+pub use region::Region;
+#[cfg(feature = "render")]
+pub use render::{PngError, PngOptions, MAX_PNG_PIXELS};
+#[cfg(feature = "std")]
+pub use solver::SolveResult;
+pub use solver::{Solutions, SolveStep};
+#[cfg(feature = "svg")]
+pub use svg::SvgOptions;
+#[cfg(feature = "std")]
+pub use world::ParseWorldError;
+pub use world::{BlockCounts, CapacityError, SolvedState, WinMode, World, WorldParseError};
@@ -0,0 +1,261 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Display, Write},
+    num::NonZeroUsize,
+    str::FromStr,
+};
+
+use crate::{Block, Command, Direction, Game, Pos, World};
+
+/// Error of [`Game::from_notation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotationParseError {
+    index: usize,
+    token: String,
+}
+
+impl NotationParseError {
+    /// Get 0-based index of the invalid command in the notation string.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the invalid command notation.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl Display for NotationParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "command {}: invalid notation: {}", self.index + 1, self.token)
+    }
+}
+
+fn direction_char(dir: Direction) -> char {
+    match dir {
+        Direction::Up => 'u',
+        Direction::Right => 'r',
+        Direction::Down => 'd',
+        Direction::Left => 'l',
+    }
+}
+
+// space is used as command separator, so empty block is written as `_`
+fn block_char(block: &Block) -> char {
+    match block {
+        Block::Empty => '_',
+        _ => block.to_string().chars().next().unwrap(),
+    }
+}
+
+fn parse_block(s: &str) -> Result<Block, ()> {
+    match s {
+        "_" => Ok(Block::Empty),
+        " " => Err(()),
+        _ => s.parse(),
+    }
+}
+
+fn parse_blocks(s: &str) -> Result<Vec<Block>, ()> {
+    s.char_indices()
+        .map(|(i, c)| parse_block(&s[i..i + c.len_utf8()]))
+        .collect()
+}
+
+// rows of blocks separated by `|`
+fn parse_world(s: &str) -> Result<World, ()> {
+    let mut blocks = Vec::new();
+    let mut height = 0;
+    let mut width = None;
+
+    for row in s.split('|') {
+        let row = parse_blocks(row)?;
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(());
+        }
+        blocks.extend(row);
+        height += 1;
+    }
+
+    let height = NonZeroUsize::new(height).ok_or(())?;
+    let width = width.and_then(NonZeroUsize::new).ok_or(())?;
+    Ok(World::new_from_blocks(height, width, blocks))
+}
+
+// 1-based number in notation to 0-based index
+fn parse_index(s: &str) -> Result<usize, ()> {
+    if s.starts_with('+') {
+        return Err(());
+    }
+    s.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).ok_or(())
+}
+
+// `r<row>c<col>`
+fn parse_position(s: &str) -> Result<Pos, ()> {
+    let (row, col) = s.strip_prefix('r').and_then(|s| s.split_once('c')).ok_or(())?;
+    Ok(Pos::new(parse_index(row)?, parse_index(col)?))
+}
+
+/// Command notation, commands in a game record are separated by whitespaces.
+///
+/// Rows, columns and indexes are 1-based in notation:
+///
+/// - `noop`, `reset`, `clear`.
+/// - `mu`, `mr`, `md`, `ml`: move cursor up, right, down, left.
+/// - `r`: rotate block under cursor.
+/// - `r2c3`: rotate block at row 2 col 3.
+/// - `rw`, `rwb`: rotate whole world clockwise, without or with block rotation.
+/// - `fh`, `fv`, `tp`: mirror whole world left to right, top to bottom, or along the diagonal.
+/// - `=5`: replace block under cursor with a cross, see [`Block`] for block characters, empty block is `_`.
+/// - `r2c3=5`: replace block at row 2 col 3 with a cross.
+/// - `r2c3+2x4=5`: replace blocks in the 2 rows 4 columns region start from row 2 col 3 with crosses.
+/// - `r2c3~5`: replace the connected blocks of the same kind as the one at row 2 col 3 with crosses.
+/// - `r2c3:1-7|5/_`: replace blocks start from row 2 col 3 with a 2 rows 3 columns world, rows are separated by `|`.
+/// - `rr2=1-7`, `rc2=/5/`: replace all blocks of row/column 2, from left to right or top to bottom.
+/// - `ir2`, `ic2`: insert a row/column at index 2.
+/// - `dr2`, `dc2`: remove row/column 2.
+impl Display for Command {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Noop => f.write_str("noop"),
+            Self::Reset(_) => f.write_str("reset"),
+            Self::Clear => f.write_str("clear"),
+            Self::MoveCursor(dir) => write!(f, "m{}", direction_char(*dir)),
+            Self::RotateCursorBlock => f.write_char('r'),
+            Self::RotateBlock(pos) => write!(f, "r{}c{}", pos.row + 1, pos.col + 1),
+            Self::RotateWholeWorld(false) => f.write_str("rw"),
+            Self::RotateWholeWorld(true) => f.write_str("rwb"),
+            Self::FlipHorizontally => f.write_str("fh"),
+            Self::FlipVertically => f.write_str("fv"),
+            Self::Transpose => f.write_str("tp"),
+            Self::ReplaceCursorBlock(block) => write!(f, "={}", block_char(block)),
+            Self::ReplaceBlock(pos, block) => write!(f, "r{}c{}={}", pos.row + 1, pos.col + 1, block_char(block)),
+            Self::FillRegion(top, left, height, width, block) => {
+                write!(f, "r{}c{}+{height}x{width}={}", top + 1, left + 1, block_char(block))
+            }
+            Self::FloodFill(pos, block) => write!(f, "r{}c{}~{}", pos.row + 1, pos.col + 1, block_char(block)),
+            Self::ReplaceWorldRegion(top, left, world) => {
+                write!(f, "r{}c{}:", top + 1, left + 1)?;
+                for (i, row) in world.rows().enumerate() {
+                    if i > 0 {
+                        f.write_char('|')?;
+                    }
+                    row.iter().try_for_each(|block| f.write_char(block_char(block)))?;
+                }
+                Ok(())
+            }
+            Self::ReplaceRow(index, blocks) | Self::ReplaceColumn(index, blocks) => {
+                let kind = if matches!(self, Self::ReplaceRow(..)) { 'r' } else { 'c' };
+                write!(f, "r{kind}{}=", index + 1)?;
+                blocks.iter().try_for_each(|block| f.write_char(block_char(block)))
+            }
+            Self::InsertRow(index) => write!(f, "ir{}", index + 1),
+            Self::InsertColumn(index) => write!(f, "ic{}", index + 1),
+            Self::RemoveRow(index) => write!(f, "dr{}", index + 1),
+            Self::RemoveColumn(index) => write!(f, "dc{}", index + 1),
+        }
+    }
+}
+
+/// Parse command notation, see [`Command`]'s [`Display`] implementation for the format.
+///
+/// Notation `reset` does not contain a world, it is parsed to a reset to [`World::default`],
+/// [`Game::from_notation`] replaces it with the initial world of the replay.
+impl FromStr for Command {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "noop" => Self::Noop,
+            "reset" => Self::Reset(World::default()),
+            "clear" => Self::Clear,
+            "mu" => Self::MoveCursor(Direction::Up),
+            "mr" => Self::MoveCursor(Direction::Right),
+            "md" => Self::MoveCursor(Direction::Down),
+            "ml" => Self::MoveCursor(Direction::Left),
+            "r" => Self::RotateCursorBlock,
+            "rw" => Self::RotateWholeWorld(false),
+            "rwb" => Self::RotateWholeWorld(true),
+            "fh" => Self::FlipHorizontally,
+            "fv" => Self::FlipVertically,
+            "tp" => Self::Transpose,
+            _ => {
+                if let Some(block) = s.strip_prefix('=') {
+                    Self::ReplaceCursorBlock(parse_block(block)?)
+                } else if let Some((index, blocks)) = s.strip_prefix("rr").and_then(|s| s.split_once('=')) {
+                    Self::ReplaceRow(parse_index(index)?, parse_blocks(blocks)?)
+                } else if let Some((index, blocks)) = s.strip_prefix("rc").and_then(|s| s.split_once('=')) {
+                    Self::ReplaceColumn(parse_index(index)?, parse_blocks(blocks)?)
+                } else if let Some(index) = s.strip_prefix("ir") {
+                    Self::InsertRow(parse_index(index)?)
+                } else if let Some(index) = s.strip_prefix("ic") {
+                    Self::InsertColumn(parse_index(index)?)
+                } else if let Some(index) = s.strip_prefix("dr") {
+                    Self::RemoveRow(parse_index(index)?)
+                } else if let Some(index) = s.strip_prefix("dc") {
+                    Self::RemoveColumn(parse_index(index)?)
+                } else if let Some((position, block)) = s.split_once('~') {
+                    Self::FloodFill(parse_position(position)?, parse_block(block)?)
+                } else if let Some((position, rows)) = s.split_once(':') {
+                    let Pos { row: top, col: left } = parse_position(position)?;
+                    Self::ReplaceWorldRegion(top, left, parse_world(rows)?)
+                } else if let Some((position, block)) = s.split_once('=') {
+                    let block = parse_block(block)?;
+                    if let Some((position, size)) = position.split_once('+') {
+                        let Pos { row: top, col: left } = parse_position(position)?;
+                        let (height, width) = size.split_once('x').ok_or(())?;
+                        let size = |s: &str| s.parse::<usize>().map_err(|_| ());
+                        Self::FillRegion(top, left, size(height)?, size(width)?, block)
+                    } else {
+                        Self::ReplaceBlock(parse_position(position)?, block)
+                    }
+                } else {
+                    Self::RotateBlock(parse_position(s)?)
+                }
+            }
+        })
+    }
+}
+
+impl Game {
+    /// Serialize applied commands to notation string, see [`Command`]'s [`Display`] implementation for the format.
+    pub fn to_notation_string(&self) -> String {
+        let mut result = String::new();
+
+        for (i, command) in self.history().iter().enumerate() {
+            if i > 0 {
+                result.push(' ');
+            }
+            write!(result, "{command}").unwrap();
+        }
+
+        result
+    }
+
+    /// Create a game from initial world, and replay commands in notation string on it.
+    ///
+    /// `reset` in notation resets to the initial world.
+    pub fn from_notation(world: World, notation: &str) -> Result<Game, NotationParseError> {
+        let commands = notation
+            .split_whitespace()
+            .enumerate()
+            .map(|(index, token)| match token.parse() {
+                Ok(Command::Reset(_)) => Ok(Command::Reset(world.clone())),
+                Ok(command) => Ok(command),
+                Err(()) => Err(NotationParseError {
+                    index,
+                    token: token.into(),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut game = Game::new(world);
+        game.apply_batch(commands);
+
+        Ok(game)
+    }
+}
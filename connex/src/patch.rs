@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+use crate::{Block, Pos, World};
+
+/// Changed blocks between two same size worlds, created by [`World::diff_patch`].
+///
+/// It's much smaller than a full [`World`] when only a few blocks changed, e.g. one rotation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WorldPatch {
+    changes: Vec<(usize, usize, Block)>,
+}
+
+impl WorldPatch {
+    /// Get changes as `(row, col, new block)`, in row-major order.
+    pub fn changes(&self) -> &[(usize, usize, Block)] {
+        &self.changes
+    }
+
+    /// Check if this patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl World {
+    /// Get the patch which turns this world into `to`, return None if size not match.
+    pub fn diff_patch(&self, to: &World) -> Option<WorldPatch> {
+        if self.size() != to.size() {
+            return None;
+        }
+
+        let changes = self
+            .rows()
+            .zip(to.rows())
+            .enumerate()
+            .flat_map(|(row, (from, to))| {
+                from.iter()
+                    .zip(to)
+                    .enumerate()
+                    .filter(|(_, (from, to))| from != to)
+                    .map(move |(col, (_, to))| (row, col, *to))
+            })
+            .collect();
+
+        Some(WorldPatch { changes })
+    }
+
+    /// Get positions whose blocks connect different sides, in row-major order.
+    ///
+    /// Only the area both worlds have is compared, check [`World::size`] for dimension changes.
+    pub fn diff(&self, other: &World) -> Vec<Pos> {
+        self.rows()
+            .zip(other.rows())
+            .enumerate()
+            .flat_map(|(row, (a, b))| {
+                a.iter()
+                    .zip(b)
+                    .enumerate()
+                    .filter(|(_, (a, b))| a.normalized() != b.normalized())
+                    .map(move |(col, _)| Pos::new(row, col))
+            })
+            .collect()
+    }
+
+    /// Apply a patch to this world.
+    ///
+    /// ## Panics
+    ///
+    /// If any change is out of range.
+    pub fn apply_patch(&mut self, patch: &WorldPatch) {
+        for (row, col, block) in &patch.changes {
+            *self.get_mut(*row, *col).expect("block index out of range") = *block;
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use core::fmt::Display;
+
+use crate::Direction;
+
+/// Position of a block in world, 0-based.
+///
+/// Ordered by row first, so sorting positions gives row-major order. Converts from and into `(row, col)` tuples.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pos {
+    /// Row index, counted from top.
+    pub row: usize,
+    /// Column index, counted from left.
+    pub col: usize,
+}
+
+impl Pos {
+    /// Create a position.
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Get the neighbor position towards `dir`, return None if it's above the first row or left of the first
+    /// column. There is no upper bound check because position doesn't know world size.
+    pub fn step(self, dir: Direction) -> Option<Self> {
+        let (row, col) = match dir {
+            Direction::Up => (self.row.checked_sub(1)?, self.col),
+            Direction::Right => (self.row, self.col.checked_add(1)?),
+            Direction::Down => (self.row.checked_add(1)?, self.col),
+            Direction::Left => (self.row, self.col.checked_sub(1)?),
+        };
+        Some(Self { row, col })
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(pos: Pos) -> Self {
+        (pos.row, pos.col)
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{},{}", self.row, self.col)
+    }
+}
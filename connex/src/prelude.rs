@@ -0,0 +1,23 @@
+//! Commonly used types, import them all with `use connex::prelude::*`.
+//!
+//! ```
+//! use connex::prelude::*;
+//!
+//! let world: World = "79\n31".parse().unwrap();
+//! assert_eq!(world.solved_state(), SolvedState::Misfit);
+//!
+//! let mut game = Game::new(world);
+//! game.apply(Command::RotateBlock(Pos::new(1, 0)));
+//! assert_eq!(game.world()[(1, 0)], Block::Turn(Direction::Up));
+//!
+//! let events = game.apply_batch((0..3).map(|_| Command::RotateBlock(Pos::new(1, 1))));
+//! assert_eq!(events.last(), Some(&GameEvent::Solved));
+//! assert!(game.world().is_won(WinMode::AllEdgesFit));
+//!
+//! assert_eq!(
+//!     "7?".parse::<World>(),
+//!     Err(WorldParseError::InvalidBlock { row: 0, col: 1, ch: '?' })
+//! );
+//! ```
+
+pub use crate::{Block, Command, Direction, Game, GameEvent, Pos, SolvedState, WinMode, World, WorldParseError};
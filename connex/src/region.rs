@@ -0,0 +1,237 @@
+use alloc::{format, string::String};
+use core::num::NonZeroUsize;
+
+use crate::{Block, Pos, World};
+
+/// A rectangle area of a [`World`], created by [`World::region`].
+///
+/// It only stores the position and size, so it can be used to read and modify the world it's created from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Region {
+    top: usize,
+    left: usize,
+    height: usize,
+    width: usize,
+}
+
+impl Region {
+    /// Get position of the top left block.
+    pub fn position(&self) -> Pos {
+        Pos::new(self.top, self.left)
+    }
+
+    /// Get size of the region, as `(height, width)`.
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Iterate over positions in this region, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let Self {
+            top,
+            left,
+            height,
+            width,
+        } = *self;
+        (top..top + height).flat_map(move |row| (left..left + width).map(move |col| Pos::new(row, col)))
+    }
+
+    /// Iterate over blocks in this region with their positions, in row-major order.
+    ///
+    /// ## Panics
+    ///
+    /// If region is out of range of the world.
+    pub fn iter<'a>(&self, world: &'a World) -> impl Iterator<Item = (Pos, &'a Block)> {
+        self.positions()
+            .map(move |pos| (pos, world.get(pos.row, pos.col).expect("region out of range")))
+    }
+
+    /// Copy blocks in this region out as a new world.
+    ///
+    /// ## Panics
+    ///
+    /// If region is out of range of the world.
+    pub fn crop(&self, world: &World) -> World {
+        let height = NonZeroUsize::new(self.height).unwrap();
+        let width = NonZeroUsize::new(self.width).unwrap();
+        World::new_with(height, width, |row, col| {
+            *world.get(self.top + row, self.left + col).expect("region out of range")
+        })
+    }
+
+    /// Replace all blocks in this region.
+    ///
+    /// ## Panics
+    ///
+    /// If region is out of range of the world.
+    pub fn fill(&self, world: &mut World, block: Block) {
+        self.map(world, |_| block)
+    }
+
+    /// Replace all blocks in this region with the result of `f`.
+    ///
+    /// ## Panics
+    ///
+    /// If region is out of range of the world.
+    pub fn map<F>(&self, world: &mut World, mut f: F)
+    where
+        F: FnMut(&Block) -> Block,
+    {
+        for pos in self.positions() {
+            let block = world.get_mut(pos.row, pos.col).expect("region out of range");
+            *block = f(block);
+        }
+    }
+}
+
+impl World {
+    /// Get a region of this world, start from `(top, left)` with given size.
+    ///
+    /// Returns error if region is empty or not fully inside this world.
+    pub fn region(&self, top: usize, left: usize, height: usize, width: usize) -> Result<Region, String> {
+        if height == 0 || width == 0 {
+            return Err(format!("empty region: {height}x{width}"));
+        }
+
+        let inside = |start: usize, len: usize, limit: usize| start.checked_add(len).is_some_and(|end| end <= limit);
+        if !inside(top, height, self.height().get()) || !inside(left, width, self.width().get()) {
+            return Err(format!(
+                "region {height}x{width} at ({top}, {left}) out of range of {}x{} world",
+                self.height(),
+                self.width()
+            ));
+        }
+
+        Ok(Region {
+            top,
+            left,
+            height,
+            width,
+        })
+    }
+
+    /// Replace all blocks in the region start from `(row, col)` with given size.
+    ///
+    /// ## Panics
+    ///
+    /// If region is empty or not fully inside this world, see [`World::region`].
+    #[track_caller]
+    pub fn fill_region(&mut self, row: usize, col: usize, height: usize, width: usize, block: Block) {
+        let region = self.region(row, col, height, width).unwrap_or_else(|e| panic!("{e}"));
+        region.fill(self, block);
+    }
+
+    /// Copy blocks in the region start from `(row, col)` with given size out as a new world.
+    ///
+    /// Returns error if region is empty or not fully inside this world, see [`World::region`].
+    pub fn crop(&self, row: usize, col: usize, height: usize, width: usize) -> Result<World, String> {
+        Ok(self.region(row, col, height, width)?.crop(self))
+    }
+
+    /// Get positions and blocks of `other` placed with its top left block at `(row, col)` of this world, as
+    /// `(position, block)` in row-major order, blocks out of range of this world are skipped.
+    pub(crate) fn paste_positions<'a>(
+        &self, row: usize, col: usize, other: &'a World,
+    ) -> impl Iterator<Item = (Pos, Block)> + 'a {
+        let (height, width) = (self.height().get(), self.width().get());
+        other.iter().filter_map(move |(r, c, block)| {
+            let (r, c) = (row.checked_add(r)?, col.checked_add(c)?);
+            (r < height && c < width).then_some((Pos::new(r, c), *block))
+        })
+    }
+
+    /// Replace blocks with `other` world's, placed with its top left block at `(row, col)`, blocks of `other`
+    /// fall outside this world are ignored.
+    pub fn paste(&mut self, row: usize, col: usize, other: &World) {
+        let blocks: alloc::vec::Vec<_> = self.paste_positions(row, col, other).collect();
+        for (pos, block) in blocks {
+            self[pos] = block;
+        }
+    }
+
+    /// Replace all blocks in the region start from `(row, col)` with empty blocks.
+    ///
+    /// ## Panics
+    ///
+    /// If region is empty or not fully inside this world, see [`World::region`].
+    #[track_caller]
+    pub fn clear_region(&mut self, row: usize, col: usize, height: usize, width: usize) {
+        self.fill_region(row, col, height, width, Block::Empty);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use super::*;
+    use crate::{Command, Game};
+
+    fn world() -> World {
+        "v1\n2,3\n>-<\n7 9\n".parse().unwrap()
+    }
+
+    #[test]
+    fn single_block_region() {
+        let mut world = world();
+        let region = world.region(1, 2, 1, 1).unwrap();
+        assert_eq!(region.position(), Pos::new(1, 2));
+        assert_eq!(
+            region.iter(&world).collect::<Vec<_>>(),
+            [(Pos::new(1, 2), &world[(1, 2)])]
+        );
+
+        region.fill(&mut world, Block::Cross);
+        assert_eq!(world.to_string(), "v1\n2,3\n>-<\n7 5\n");
+    }
+
+    #[test]
+    fn full_world_region() {
+        let mut world = world();
+        let region = world.region(0, 0, 2, 3).unwrap();
+        assert_eq!(region.size(), (2, 3));
+        assert_eq!(region.crop(&world), world);
+        assert_eq!(region.iter(&world).count(), 6);
+
+        region.map(&mut world, Block::rotated);
+        assert_eq!(world.to_string(), "v1\n2,3\nv/^\n9 3\n");
+        region.fill(&mut world, Block::Empty);
+        assert!(world.iter().all(|(_, _, block)| *block == Block::Empty));
+    }
+
+    #[test]
+    fn out_of_range_region_is_rejected() {
+        let world = world();
+        for (top, left, height, width) in [
+            (0, 0, 0, 1),
+            (0, 0, 1, 0),
+            (0, 0, 3, 1),
+            (0, 0, 1, 4),
+            (1, 2, 1, 2),
+            (2, 0, 1, 1),
+            (usize::MAX, 0, 2, 1),
+            (0, 1, 1, usize::MAX),
+        ] {
+            assert!(
+                world.region(top, left, height, width).is_err(),
+                "{top}, {left}, {height}x{width}"
+            );
+        }
+        assert!(world.crop(1, 1, 2, 1).is_err());
+    }
+
+    #[test]
+    fn fill_region_command_tracks_solved_state() {
+        let mut game = Game::new("v1\n2,2\n79\n31\n".parse().unwrap());
+        // out of range regions are ignored
+        game.apply(Command::FillRegion(1, 0, 2, 2, Block::Cross));
+        assert_eq!(game.world(), &"v1\n2,2\n79\n31\n".parse().unwrap());
+
+        game.apply(Command::FillRegion(0, 0, 2, 2, Block::Cross));
+        assert!(!game.solved());
+        game.apply(Command::FillRegion(0, 0, 2, 2, Block::Empty));
+        assert!(!game.solved());
+        game.apply(Command::ReplaceWorldRegion(0, 0, "v1\n2,2\n79\n13\n".parse().unwrap()));
+        assert!(game.solved());
+    }
+}
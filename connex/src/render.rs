@@ -0,0 +1,238 @@
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
+
+use crate::{shape, World};
+
+/// Largest image [`World::try_to_png_with`] renders, in pixels, it takes 768 MiB of memory before encoding.
+pub const MAX_PNG_PIXELS: u64 = 1 << 28;
+
+/// Error of [`World::try_to_png_with`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PngError {
+    /// [`PngOptions::cell_px`] is 0.
+    ZeroCellSize,
+    /// Image is larger than [`MAX_PNG_PIXELS`] or PNG allows.
+    TooLarge {
+        /// Image width in pixels.
+        width: u64,
+        /// Image height in pixels.
+        height: u64,
+    },
+    /// Allocator can't provide memory for the pixels.
+    AllocationFailed,
+}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ZeroCellSize => f.write_str("cell size must be positive"),
+            Self::TooLarge { width, height } => write!(f, "{width}x{height} image is too large"),
+            Self::AllocationFailed => f.write_str("failed to allocate image"),
+        }
+    }
+}
+
+impl core::error::Error for PngError {}
+
+/// Options of [`World::to_png_with`], colors are RGB.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PngOptions {
+    /// Pixels of each block.
+    pub cell_px: u32,
+    /// Color of background.
+    pub background: [u8; 3],
+    /// Color of pipe lines when world is not solved.
+    pub line_color: [u8; 3],
+    /// Color of pipe lines when world is solved.
+    pub solved_line_color: [u8; 3],
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self {
+            cell_px: 64,
+            background: [0xFF, 0xFF, 0xFF],
+            line_color: [0x20, 0x20, 0x20],
+            solved_line_color: [0x00, 0xA0, 0x00],
+        }
+    }
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn try_new(width: u32, height: u32, background: [u8; 3]) -> Result<Self, PngError> {
+        let size = width as usize * height as usize * 3;
+        let mut pixels = Vec::new();
+        pixels.try_reserve_exact(size).map_err(|_| PngError::AllocationFailed)?;
+        pixels.extend(background.iter().copied().cycle().take(size));
+        Ok(Self { width, height, pixels })
+    }
+
+    fn fill(&mut self, x: i64, y: i64, radius: i64, color: [u8; 3]) {
+        for py in (y - radius).max(0)..=(y + radius).min(self.height as i64 - 1) {
+            for px in (x - radius).max(0)..=(x + radius).min(self.width as i64 - 1) {
+                let offset = (py as usize * self.width as usize + px as usize) * 3;
+                self.pixels[offset..offset + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    fn line(&mut self, (x1, y1): (i64, i64), (x2, y2): (i64, i64), radius: i64, color: [u8; 3]) {
+        let steps = (x2 - x1).abs().max((y2 - y1).abs()).max(1);
+        for i in 0..=steps {
+            self.fill(x1 + (x2 - x1) * i / steps, y1 + (y2 - y1) * i / steps, radius, color);
+        }
+    }
+}
+
+impl World {
+    /// Render this world to a PNG image with default colors, each block is `cell_px` x `cell_px` pixels.
+    ///
+    /// ## Panics
+    ///
+    /// cell_px == 0 or image size overflows u32.
+    pub fn to_png(&self, cell_px: u32) -> Vec<u8> {
+        self.to_png_with(&PngOptions {
+            cell_px,
+            ..PngOptions::default()
+        })
+    }
+
+    /// Render this world to a PNG image, image size is world size multiplied by `options.cell_px`.
+    ///
+    /// ## Panics
+    ///
+    /// cell_px == 0 or image is too large, see [`World::try_to_png_with`] for the non-panicking version.
+    #[track_caller]
+    pub fn to_png_with(&self, options: &PngOptions) -> Vec<u8> {
+        self.try_to_png_with(options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Render this world to a PNG image like [`World::to_png_with`], return error if `options.cell_px` is 0, or image
+    /// is larger than [`MAX_PNG_PIXELS`] or can't be allocated.
+    pub fn try_to_png_with(&self, options: &PngOptions) -> Result<Vec<u8>, PngError> {
+        let cell_px = options.cell_px;
+        if cell_px == 0 {
+            return Err(PngError::ZeroCellSize);
+        }
+
+        // u64 can't overflow, usize and u32 are both smaller
+        let width = self.width().get() as u64 * u64::from(cell_px);
+        let height = self.height().get() as u64 * u64::from(cell_px);
+        let too_large = PngError::TooLarge { width, height };
+        if width.saturating_mul(height) > MAX_PNG_PIXELS {
+            return Err(too_large);
+        }
+        let width = u32::try_from(width).map_err(|_| too_large)?;
+        let height = u32::try_from(height).map_err(|_| too_large)?;
+
+        let color = if self.solved() {
+            options.solved_line_color
+        } else {
+            options.line_color
+        };
+        let cell = i64::from(cell_px);
+        let radius = cell / 16;
+
+        let mut canvas = Canvas::try_new(width, height, options.background)?;
+        for (row, col, block) in self {
+            let point = |(y, x): (u8, u8)| {
+                // keep points on the right/bottom edge inside the cell
+                let offset = |p: u8| (i64::from(p) * cell / 4).min(cell - 1);
+                (col as i64 * cell + offset(x), row as i64 * cell + offset(y))
+            };
+            for (from, to) in shape::block_lines(block) {
+                canvas.line(point(*from), point(*to), radius, color);
+            }
+        }
+
+        let mut data = vec![];
+        let mut encoder = png::Encoder::new(&mut data, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&canvas.pixels))
+            .expect("encode png to memory");
+
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REFERENCE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/render_2x2.png");
+
+    fn world() -> World {
+        "79\n13".parse().unwrap()
+    }
+
+    fn decode(data: &[u8]) -> (u32, u32, Vec<u8>) {
+        let mut reader = png::Decoder::new(data).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        pixels.truncate(info.buffer_size());
+        (info.width, info.height, pixels)
+    }
+
+    /// Set `CONNEX_UPDATE_SNAPSHOTS=1` to write the reference image after an intended rendering change.
+    #[test]
+    fn matches_reference_image() {
+        let rendered = world().to_png(16);
+        if std::env::var_os("CONNEX_UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(REFERENCE, &rendered).unwrap();
+        }
+
+        let (width, height, actual) = decode(&rendered);
+        let (ref_width, ref_height, expected) = decode(&std::fs::read(REFERENCE).unwrap());
+        assert_eq!((width, height), (ref_width, ref_height));
+
+        // small drawing changes like line rounding are allowed
+        let differ = actual
+            .chunks(3)
+            .zip(expected.chunks(3))
+            .filter(|(a, b)| a.iter().zip(b.iter()).any(|(a, b)| a.abs_diff(*b) > 8))
+            .count();
+        assert!(differ * 100 <= actual.len() / 3, "{differ} pixels differ");
+    }
+
+    #[test]
+    fn solved_world_uses_solved_line_color() {
+        let (_, _, pixels) = decode(&world().to_png(16));
+        let solved = PngOptions::default().solved_line_color;
+        assert!(pixels.chunks(3).any(|pixel| pixel == solved));
+    }
+
+    #[test]
+    fn image_size_is_world_size_times_scale() {
+        let world: World = "v1\n2,3\n>-<\n   ".parse().unwrap();
+        for cell_px in [1, 4, 16, 64] {
+            let (width, height, _) = decode(&world.to_png(cell_px));
+            assert_eq!((width, height), (3 * cell_px, 2 * cell_px));
+        }
+    }
+
+    #[test]
+    fn invalid_sizes_are_errors() {
+        let options = |cell_px| PngOptions {
+            cell_px,
+            ..PngOptions::default()
+        };
+        assert_eq!(world().try_to_png_with(&options(0)), Err(PngError::ZeroCellSize));
+        assert!(matches!(
+            world().try_to_png_with(&options(u32::MAX)),
+            Err(PngError::TooLarge { .. })
+        ));
+        assert!(matches!(
+            world().try_to_png_with(&options(1 << 14)),
+            Err(PngError::TooLarge { .. })
+        ));
+    }
+}
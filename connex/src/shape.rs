@@ -0,0 +1,90 @@
+//! Line geometry of blocks, shared by renderers.
+//!
+//! A block is drawn on a 5x5 point grid, points are `(y, x)` pairs in range `0..=4`, y grows downward.
+
+use crate::{Block, Direction};
+
+/// A line segment in block's point grid, from one `(y, x)` point to another.
+pub type BlockLine = ((u8, u8), (u8, u8));
+
+const BL_EP_UP: BlockLine = ((0, 2), (1, 2));
+const BL_EP_RIGHT: BlockLine = ((2, 3), (2, 4));
+const BL_EP_DOWN: BlockLine = ((3, 2), (4, 2));
+const BL_EP_LEFT: BlockLine = ((2, 0), (2, 1));
+const BL_TURN_LEFT_UP: BlockLine = ((1, 2), (2, 1));
+const BL_TURN_RIGHT_UP: BlockLine = ((2, 3), (1, 2));
+const BL_TURN_RIGHT_DOWN: BlockLine = ((3, 2), (2, 3));
+const BL_TURN_LEFT_DOWN: BlockLine = ((2, 1), (3, 2));
+
+const BL_TURN_ALL: &[BlockLine] = &[BL_TURN_LEFT_UP, BL_TURN_RIGHT_UP, BL_TURN_RIGHT_DOWN, BL_TURN_LEFT_DOWN];
+const BL_EP_ALL: &[BlockLine] = &[BL_EP_UP, BL_EP_RIGHT, BL_EP_DOWN, BL_EP_LEFT];
+
+const BL_THROUGH_UP_DOWN: BlockLine = ((0, 2), (4, 2));
+const BL_THROUGH_LEFT_RIGHT: BlockLine = ((2, 0), (2, 4));
+
+const BL_LEFT_UP_ARC: &[BlockLine] = &[BL_EP_LEFT, BL_EP_UP, BL_TURN_LEFT_UP];
+const BL_RIGHT_UP_ARC: &[BlockLine] = &[BL_EP_RIGHT, BL_EP_UP, BL_TURN_RIGHT_UP];
+const BL_RIGHT_DOWN_ARC: &[BlockLine] = &[BL_EP_RIGHT, BL_EP_DOWN, BL_TURN_RIGHT_DOWN];
+const BL_LEFT_DOWN_ARC: &[BlockLine] = &[BL_EP_LEFT, BL_EP_DOWN, BL_TURN_LEFT_DOWN];
+
+const BL_UP_FORK: &[BlockLine] = &[
+    BL_EP_RIGHT,
+    BL_TURN_RIGHT_DOWN,
+    BL_EP_DOWN,
+    BL_TURN_LEFT_DOWN,
+    BL_EP_LEFT,
+];
+const BL_RIGHT_FORK: &[BlockLine] = &[BL_EP_UP, BL_TURN_LEFT_UP, BL_EP_LEFT, BL_TURN_LEFT_DOWN, BL_EP_DOWN];
+const BL_DOWN_FORK: &[BlockLine] = &[BL_EP_LEFT, BL_TURN_LEFT_UP, BL_EP_UP, BL_TURN_RIGHT_UP, BL_EP_RIGHT];
+const BL_LEFT_FORK: &[BlockLine] = &[BL_EP_UP, BL_TURN_RIGHT_UP, BL_EP_RIGHT, BL_TURN_RIGHT_DOWN, BL_EP_DOWN];
+
+const BL_BOUNDARY_UP: BlockLine = ((0, 0), (0, 4));
+const BL_BOUNDARY_RIGHT: BlockLine = ((0, 4), (4, 4));
+const BL_BOUNDARY_DOWN: BlockLine = ((4, 0), (4, 4));
+const BL_BOUNDARY_LEFT: BlockLine = ((0, 0), (4, 0));
+
+/// Lines of the square boundary of a block.
+pub const BOUNDARY: &[BlockLine] = &[BL_BOUNDARY_UP, BL_BOUNDARY_RIGHT, BL_BOUNDARY_DOWN, BL_BOUNDARY_LEFT];
+
+fn common_lines(block: &Block) -> &'static [&'static [BlockLine]] {
+    match block {
+        Block::Endpoint(_) => &[BL_TURN_ALL],
+        Block::Cross => &[BL_TURN_ALL, BL_EP_ALL],
+        _ => &[],
+    }
+}
+
+fn side_lines(block: &Block) -> &'static [BlockLine] {
+    match block {
+        Block::Empty => &[],
+        Block::Endpoint(s) => match s {
+            Direction::Up => &[BL_EP_UP],
+            Direction::Right => &[BL_EP_RIGHT],
+            Direction::Down => &[BL_EP_DOWN],
+            Direction::Left => &[BL_EP_LEFT],
+        },
+        Block::Through(Direction::Up | Direction::Down) => &[BL_THROUGH_UP_DOWN],
+        Block::Through(Direction::Left | Direction::Right) => &[BL_THROUGH_LEFT_RIGHT],
+        Block::Turn(s) => match s {
+            Direction::Up => BL_RIGHT_UP_ARC,
+            Direction::Right => BL_RIGHT_DOWN_ARC,
+            Direction::Down => BL_LEFT_DOWN_ARC,
+            Direction::Left => BL_LEFT_UP_ARC,
+        },
+        Block::Fork(s) => match s {
+            Direction::Up => BL_UP_FORK,
+            Direction::Right => BL_RIGHT_FORK,
+            Direction::Down => BL_DOWN_FORK,
+            Direction::Left => BL_LEFT_FORK,
+        },
+        Block::Cross => &[],
+    }
+}
+
+/// Get all lines need to be drawn for a block.
+pub fn block_lines(block: &Block) -> impl Iterator<Item = &'static BlockLine> {
+    common_lines(block)
+        .iter()
+        .flat_map(|a| a.iter())
+        .chain(side_lines(block).iter())
+}
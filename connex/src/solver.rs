@@ -0,0 +1,330 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{Block, Direction, World};
+
+/// Result of a bounded [`Solutions::resume`] call.
+#[derive(Debug, Clone)]
+pub enum SolveStep {
+    /// A solution is found, search can be resumed to find the next one.
+    Found(World),
+    /// Budget used up before finding a new solution, search can be resumed.
+    Paused,
+    /// No more solutions.
+    Exhausted,
+}
+
+/// Result of [`World::solve_with_timeout`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub enum SolveResult {
+    /// A solved orientation is found.
+    Solved(World),
+    /// The world has no solution.
+    Unsolvable,
+    /// Time budget used up before the search ends.
+    TimedOut,
+}
+
+/// Backtracking search over all solved orientations of a world.
+///
+/// Blocks are decided in row-major order, every block only tries its distinct orientations, so each
+/// solution is yielded exactly once. Created by [`World::solutions`].
+#[derive(Debug, Clone)]
+pub struct Solutions {
+    base: World,
+    world: World,
+    turns: Vec<u8>,
+    // blocks keep their orientation in base world
+    fixed: Vec<bool>,
+    index: usize,
+    nodes: u64,
+    done: bool,
+}
+
+/// How many clockwise rotations make a block back to a equivalent state.
+fn period(block: &Block) -> u8 {
+    match block {
+        Block::Empty | Block::Cross => 1,
+        Block::Through(_) => 2,
+        _ => 4,
+    }
+}
+
+impl Solutions {
+    fn new(world: &World) -> Self {
+        let (height, width) = (world.height().get(), world.width().get());
+        // all empty world is not considered solved
+        let done = world.is_empty();
+
+        Self {
+            base: world.clone(),
+            world: world.clone(),
+            turns: vec![0; height * width],
+            fixed: vec![false; height * width],
+            index: 0,
+            nodes: 0,
+            done,
+        }
+    }
+
+    /// Create a search which doesn't turn blocks marked in `fixed`, in row-major order.
+    pub(crate) fn with_fixed(world: &World, fixed: Vec<bool>) -> Self {
+        Self {
+            fixed,
+            ..Self::new(world)
+        }
+    }
+
+    /// Count of search nodes visited so far, can be used to measure how hard the world is.
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    fn coordinate(&self, index: usize) -> (usize, usize) {
+        let width = self.world.width().get();
+        (index / width, index % width)
+    }
+
+    fn fits(&self, index: usize) -> bool {
+        let (row, col) = self.coordinate(index);
+        let block = self.world.get(row, col).unwrap();
+
+        if self.world.passes_boundary(row, col, block) {
+            return false;
+        }
+
+        if row > 0 && !block.fit(Direction::Up, self.world.get(row - 1, col).unwrap()) {
+            return false;
+        }
+
+        if col > 0 && !block.fit(Direction::Left, self.world.get(row, col - 1).unwrap()) {
+            return false;
+        }
+
+        true
+    }
+
+    /// Move current block to its next orientation, backtrack to previous blocks if all orientations are tried.
+    fn advance(&mut self) {
+        loop {
+            let index = self.index;
+            let (row, col) = self.coordinate(index);
+            let original = *self.base.get(row, col).unwrap();
+            let block = self.world.get_mut(row, col).unwrap();
+
+            self.turns[index] += 1;
+            if !self.fixed[index] && self.turns[index] < period(&original) {
+                block.rotate();
+                return;
+            }
+
+            self.turns[index] = 0;
+            *block = original;
+
+            if index == 0 {
+                self.done = true;
+                return;
+            }
+            self.index -= 1;
+        }
+    }
+
+    /// Continue the search, visit at most `budget` nodes.
+    pub fn resume(&mut self, budget: u64) -> SolveStep {
+        let size = self.turns.len();
+        let mut spent = 0;
+
+        loop {
+            if self.done {
+                return SolveStep::Exhausted;
+            }
+
+            if self.index == size {
+                let found = self.world.clone();
+                self.index -= 1;
+                self.advance();
+                return SolveStep::Found(found);
+            }
+
+            if spent == budget {
+                return SolveStep::Paused;
+            }
+            spent += 1;
+            self.nodes += 1;
+
+            if self.fits(self.index) {
+                self.index += 1;
+            } else {
+                self.advance();
+            }
+        }
+    }
+}
+
+impl Solutions {
+    /// Branch and bound over the same search, find the solution needs least clockwise turns from `from`.
+    ///
+    /// Caller must make sure every block of `from` can be turned into the block at same position of base world.
+    fn min_moves(mut self, from: &World) -> Option<World> {
+        let size = self.turns.len();
+        // costs[i] is total turns of blocks before index i in current partial solution
+        let mut costs = vec![0; size + 1];
+        let mut best: Option<(u32, World)> = None;
+
+        while !self.done {
+            if self.index == size {
+                if best.as_ref().is_none_or(|(cost, _)| costs[size] < *cost) {
+                    best = Some((costs[size], self.world.clone()));
+                }
+                self.index -= 1;
+                self.advance();
+                continue;
+            }
+
+            self.nodes += 1;
+
+            let (row, col) = self.coordinate(self.index);
+            let turns = from
+                .get(row, col)
+                .unwrap()
+                .turns_to(self.world.get(row, col).unwrap())
+                .unwrap();
+            let cost = costs[self.index] + u32::from(turns);
+
+            if best.as_ref().is_none_or(|(best, _)| cost < *best) && self.fits(self.index) {
+                self.index += 1;
+                costs[self.index] = cost;
+            } else {
+                self.advance();
+            }
+        }
+
+        best.map(|(_, world)| world)
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = World;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.resume(u64::MAX) {
+            SolveStep::Found(world) => Some(world),
+            _ => None,
+        }
+    }
+}
+
+impl World {
+    /// Iterate over all solved orientations of this world.
+    pub fn solutions(&self) -> Solutions {
+        Solutions::new(self)
+    }
+
+    /// Find a solved orientation of this world.
+    pub fn solve(&self) -> Option<World> {
+        self.solutions().next()
+    }
+
+    /// Find the solution of this world needs least clockwise turns to reach from `from`, which is usually a
+    /// scrambled state of this world.
+    ///
+    /// Return None if this world is not solvable, or `from` has different size or blocks can't be turned into
+    /// blocks of this world.
+    pub fn solve_min_moves(&self, from: &World) -> Option<World> {
+        if from.size() != self.size() || crate::analysis::block_rotations(from, self).is_none() {
+            return None;
+        }
+
+        Solutions::new(self).min_moves(from)
+    }
+
+    /// Get the canonical solved form of this world, which is the same for all orientations of the same puzzle, so
+    /// a player's solution can be compared to it without requiring exact rotation match.
+    ///
+    /// Every block is turned the least times clockwise from facing up that still allows a solution, earlier blocks
+    /// in row-major order take priority. Return None if this world is not solvable.
+    pub fn normalize_solved_orientation(&self) -> Option<World> {
+        let mut base = self.clone();
+        for (_, _, block) in base.iter_mut() {
+            if let Some(dir) = block.direction_mut() {
+                *dir = Direction::Up;
+            }
+        }
+
+        // search tries orientations in clockwise order from base, so the first solution is the smallest one
+        base.solve()
+    }
+
+    /// Find a solved orientation of this world like [`World::solve`], but give up when `budget` is used up, so large
+    /// worlds don't block the caller too long.
+    ///
+    /// Elapsed time is checked every 1000 search nodes, so it may run a little longer than `budget`.
+    #[cfg(feature = "std")]
+    pub fn solve_with_timeout(&self, budget: std::time::Duration) -> SolveResult {
+        const NODES_PER_CHECK: u64 = 1000;
+
+        let start = std::time::Instant::now();
+        let mut solutions = self.solutions();
+        loop {
+            match solutions.resume(NODES_PER_CHECK) {
+                SolveStep::Found(world) => return SolveResult::Solved(world),
+                SolveStep::Exhausted => return SolveResult::Unsolvable,
+                SolveStep::Paused if start.elapsed() >= budget => return SolveResult::TimedOut,
+                SolveStep::Paused => (),
+            }
+        }
+    }
+
+    /// Check if this world can be solved by rotating blocks.
+    pub fn is_solvable(&self) -> bool {
+        self.solve().is_some()
+    }
+
+    /// Count solutions of this world, stop counting when reach `limit`.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        self.solutions().take(limit).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::minimum_rotations;
+
+    fn world(s: &str) -> World {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn min_moves_solution_is_no_farther_than_any_other() {
+        let puzzle = world("><><\n><><\n><><");
+        let solutions: Vec<_> = puzzle.solutions().collect();
+        assert!(solutions.len() > 1);
+
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..50 {
+            let mut from = puzzle.clone();
+            for (_, _, block) in from.iter_mut() {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                block.turn_by((seed % 4) as u8);
+            }
+
+            let best = puzzle.solve_min_moves(&from).unwrap();
+            assert!(best.solved());
+            let distance = minimum_rotations(&from, &best).unwrap();
+            for solution in &solutions {
+                assert!(distance <= minimum_rotations(&from, solution).unwrap(), "{from}");
+            }
+        }
+    }
+
+    #[test]
+    fn min_moves_rejects_mismatched_board() {
+        let puzzle = world("79\n31");
+        assert_eq!(puzzle.solve_min_moves(&puzzle), Some(world("79\n13")));
+        assert_eq!(puzzle.solve_min_moves(&world("79")), None);
+        assert_eq!(puzzle.solve_min_moves(&world("79\n35")), None);
+    }
+}
@@ -0,0 +1,238 @@
+//! SVG export of worlds.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{shape, World};
+
+/// Options of [`World::to_svg`], colors are RGB.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// Size of each block.
+    pub cell_size: u32,
+    /// Width of pipe lines.
+    pub stroke_width: u32,
+    /// Color of background.
+    pub background: [u8; 3],
+    /// Color of pipe lines when world is not solved.
+    pub line_color: [u8; 3],
+    /// Color of pipe lines when world is solved.
+    pub solved_line_color: [u8; 3],
+    /// Whether to draw block boundaries.
+    pub grid: bool,
+    /// Color of block boundaries.
+    pub grid_color: [u8; 3],
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 64,
+            stroke_width: 4,
+            background: [0xFF, 0xFF, 0xFF],
+            line_color: [0x20, 0x20, 0x20],
+            solved_line_color: [0x00, 0xA0, 0x00],
+            grid: false,
+            grid_color: [0xD0, 0xD0, 0xD0],
+        }
+    }
+}
+
+struct Color([u8; 3]);
+
+impl core::fmt::Display for Color {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+impl World {
+    /// Render this world to a standalone SVG document, blocks use the same line geometry as other renderers,
+    /// see [`shape`].
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let cell = u64::from(options.cell_size);
+        let width = self.width().get() as u64 * cell;
+        let height = self.height().get() as u64 * cell;
+        let color = if self.solved() {
+            options.solved_line_color
+        } else {
+            options.line_color
+        };
+
+        let mut svg = String::new();
+        // writing to a String never fails
+        let _ = self.write_svg(&mut svg, options, (width, height), color);
+        svg
+    }
+
+    fn write_svg(
+        &self, svg: &mut String, options: &SvgOptions, (width, height): (u64, u64), color: [u8; 3],
+    ) -> core::fmt::Result {
+        let cell = u64::from(options.cell_size);
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        )?;
+        writeln!(
+            svg,
+            r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+            Color(options.background)
+        )?;
+
+        if options.grid {
+            svg.push_str(r#"<path d=""#);
+            for row in 0..=self.height().get() as u64 {
+                write!(svg, "M0 {y}H{width}", y = row * cell)?;
+            }
+            for col in 0..=self.width().get() as u64 {
+                write!(svg, "M{x} 0V{height}", x = col * cell)?;
+            }
+            writeln!(
+                svg,
+                r#"" stroke="{}" stroke-width="1" fill="none"/>"#,
+                Color(options.grid_color)
+            )?;
+        }
+
+        for (row, col, block) in self {
+            let mut lines = shape::block_lines(block).peekable();
+            if lines.peek().is_none() {
+                continue;
+            }
+
+            // points are on a 5x5 grid, so the step is a quarter of cell, which is exact in f64
+            let point = |p: u8, offset: usize| (offset as u64 * cell * 4 + u64::from(p) * cell) as f64 / 4.0;
+            svg.push_str(r#"<path d=""#);
+            for ((y1, x1), (y2, x2)) in lines {
+                write!(
+                    svg,
+                    "M{} {}L{} {}",
+                    point(*x1, col),
+                    point(*y1, row),
+                    point(*x2, col),
+                    point(*y2, row)
+                )?;
+            }
+            writeln!(
+                svg,
+                r#"" stroke="{}" stroke-width="{}" stroke-linecap="round" fill="none"/>"#,
+                Color(color),
+                options.stroke_width
+            )?;
+        }
+
+        writeln!(svg, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{string::ToString, vec::Vec};
+
+    use super::*;
+
+    fn world() -> World {
+        "79\n13".parse().unwrap()
+    }
+
+    /// Set `CONNEX_UPDATE_SNAPSHOTS=1` to write the reference file after an intended rendering change.
+    fn assert_snapshot(name: &str, actual: &str) {
+        let path = std::format!("{}/testdata/{name}.svg", env!("CARGO_MANIFEST_DIR"));
+        if std::env::var_os("CONNEX_UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, actual).unwrap();
+        }
+
+        let expected = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(actual, expected, "{name}");
+    }
+
+    /// Check tags are balanced and attribute values are quoted, enough for the subset of XML we write.
+    fn assert_well_formed(svg: &str) {
+        let mut stack = Vec::new();
+        let mut rest = svg;
+        while let Some(start) = rest.find('<') {
+            assert!(
+                rest[..start].trim().is_empty(),
+                "text outside of tags: {:?}",
+                &rest[..start]
+            );
+            let end = start + rest[start..].find('>').expect("unclosed tag");
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(stack.pop(), Some(name.to_string()), "mismatched end tag");
+                continue;
+            }
+
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let (name, mut attrs) = tag.split_once(' ').unwrap_or((tag, ""));
+            assert!(
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric()),
+                "bad tag {tag:?}"
+            );
+            while let Some((key, value)) = attrs.trim_start().split_once("=\"") {
+                assert!(
+                    key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+                    "bad attribute {key:?}"
+                );
+                let close = value.find('"').expect("unquoted attribute");
+                assert!(!value[..close].contains(['<', '&']), "bad attribute value");
+                attrs = &value[close + 1..];
+            }
+            assert!(attrs.trim().is_empty(), "bad attributes in {tag:?}");
+
+            if !self_closing {
+                stack.push(name.to_string());
+            }
+        }
+        assert!(rest.trim().is_empty());
+        assert!(stack.is_empty(), "unclosed tags {stack:?}");
+    }
+
+    #[test]
+    fn matches_snapshots() {
+        let options = SvgOptions {
+            cell_size: 16,
+            ..SvgOptions::default()
+        };
+        assert_snapshot("svg_2x2", &world().to_svg(&options));
+
+        let options = SvgOptions {
+            grid: true,
+            stroke_width: 2,
+            ..options
+        };
+        assert_snapshot("svg_2x2_grid", &"79\n31".parse::<World>().unwrap().to_svg(&options));
+    }
+
+    #[test]
+    fn output_is_well_formed() {
+        let options = SvgOptions {
+            grid: true,
+            ..SvgOptions::default()
+        };
+        for s in ["79\n13", "79\n31", " ", "v1\n3,3\n>8<\n/5/\n^ ^\n"] {
+            let svg = s.parse::<World>().unwrap().to_svg(&options);
+            assert_well_formed(&svg);
+            assert!(svg.starts_with("<svg "));
+        }
+    }
+
+    #[test]
+    fn solved_color_and_size() {
+        let options = SvgOptions::default();
+        let solved = world().to_svg(&options);
+        assert!(solved.contains(r#"width="128" height="128""#));
+        assert!(solved.contains(r##"stroke="#00a000""##));
+
+        let unsolved = "79\n31".parse::<World>().unwrap().to_svg(&options);
+        assert!(unsolved.contains(r##"stroke="#202020""##));
+        assert!(!unsolved.contains("#00a000"));
+    }
+}
@@ -1,11 +1,54 @@
-use alloc::{format, string::String, vec::Vec};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use core::{
     fmt::{Debug, Display, Write},
-    num::NonZeroUsize,
+    num::{NonZeroUsize, ParseIntError},
+    ops::{Index, IndexMut, Range},
     str::FromStr,
 };
 
-use crate::{Block, Direction};
+use crate::{
+    iter::{IntoIter, Iter, IterMut},
+    Block, Direction, Pos,
+};
+
+/// Count of each kind of blocks in a [`World`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[allow(missing_docs)]
+pub struct BlockCounts {
+    pub empty: usize,
+    pub endpoint: usize,
+    pub through: usize,
+    pub turn: usize,
+    pub fork: usize,
+    pub cross: usize,
+}
+
+/// Solved state of a world, returned by [`World::solved_state`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SolvedState {
+    /// All blocks fit.
+    Solved,
+    /// All blocks are empty, it's not considered solved.
+    Empty,
+    /// Some blocks don't fit with their neighbors or pass to the boundary.
+    Misfit,
+}
+
+/// Rule to decide whether a world is won, see [`World::is_won`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum WinMode {
+    /// All blocks fit, same as [`World::solved`].
+    #[default]
+    AllEdgesFit,
+    /// All endpoints are joined into one network and no block of it leaks, like flow puzzles. Blocks outside the
+    /// network are ignored.
+    EndpointsConnected,
+}
 
 /// World is a connex game world.
 ///
@@ -14,6 +57,7 @@ use crate::{Block, Direction};
 /// It has a string representation(used in [`core::str::FromStr`] trait implementation) in following format:
 ///
 /// ```none
+/// v1
 /// <height>,<width>
 /// <char representation of block at (0, 0)><char representation of block at (0, 1)>...
 /// <char representation of block at (1, 0)><char representation of block at (1, 1)>...
@@ -24,11 +68,48 @@ use crate::{Block, Direction};
 /// ```
 ///
 /// See [`Block`] document for blocks' representation.
+///
+/// The first line is the format version, files without it are legacy version 0 files which start with the size
+/// line, see [`World::format_version`]. Both are accepted when parsing, but only the current version is written.
+///
+/// The version and size lines can be omitted when parsing, then size is inferred from the rows, all rows must have
+/// the same count of blocks. Format with `{:#}` to omit the version and size lines when displaying.
+///
+/// When there is a size line, rows shorter than the width are padded with [`Block::Empty`], because editors may
+/// strip trailing spaces. A row of only empty blocks can even become an empty line, but [`World::parse_many`]
+/// splits on empty lines, so keep it if that's used.
+///
+/// Border mode is not part of the text format, parsed worlds always have closed borders,
+/// see [`World::set_open_borders`].
 #[derive(Debug, Clone)]
 pub struct World {
     width: NonZeroUsize,
     height: NonZeroUsize,
     blocks: Vec<Block>,
+    open_borders: bool,
+    format_version: u32,
+}
+
+// format version only records where the world comes from, it's not part of the world itself
+
+impl PartialEq for World {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.blocks == other.blocks
+            && self.open_borders == other.open_borders
+    }
+}
+
+impl Eq for World {}
+
+impl core::hash::Hash for World {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.blocks.hash(state);
+        self.open_borders.hash(state);
+    }
 }
 
 impl Default for World {
@@ -37,53 +118,133 @@ impl Default for World {
     }
 }
 
+/// Error of [`World`]'s [`FromStr`] implementation.
+///
+/// Rows and columns are 0-based and counted in blocks, the size line is not counted as a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldParseError {
+    /// Input is empty, has no size line and its first row is empty, or has a version line without size line.
+    MissingSizeLine,
+    /// Version line has a version this library doesn't support, see [`World::format_version`].
+    UnsupportedVersion(String),
+    /// Height in size line is not a positive integer.
+    BadHeight(ParseIntError),
+    /// Width in size line is not a positive integer.
+    BadWidth(ParseIntError),
+    /// Height * width in size line is larger than [`usize::MAX`].
+    TooManyBlocks,
+    /// A char is not a valid block char.
+    InvalidBlock {
+        /// Row of the invalid char.
+        row: usize,
+        /// Column of the invalid char.
+        col: usize,
+        /// The invalid char.
+        ch: char,
+    },
+    /// A row has more blocks than the width, or less when there is no size line, see [`World`] for padding.
+    RowLengthMismatch {
+        /// Row with wrong length.
+        row: usize,
+        /// Width of the world, from size line or the first row.
+        expected: usize,
+        /// Count of blocks in this row.
+        found: usize,
+    },
+    /// Count of rows is different from height in size line.
+    RowCountMismatch {
+        /// Height in size line.
+        expected: usize,
+        /// Count of rows.
+        found: usize,
+    },
+}
+
+impl Display for WorldParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingSizeLine => f.write_str("missing size line or first row"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported format version: {v}"),
+            Self::BadHeight(e) => write!(f, "invalid height: {e}"),
+            Self::BadWidth(e) => write!(f, "invalid width: {e}"),
+            Self::TooManyBlocks => f.write_str("too many blocks"),
+            Self::InvalidBlock { row, col, ch } => {
+                write!(f, "row {}, column {}: invalid block char: {ch}", row + 1, col + 1)
+            }
+            Self::RowLengthMismatch { row, expected, found } => {
+                write!(f, "row {}: expect {expected} blocks, found {found}", row + 1)
+            }
+            Self::RowCountMismatch { expected, found } => write!(f, "expect {expected} rows, found {found}"),
+        }
+    }
+}
+
+impl core::error::Error for WorldParseError {}
+
+impl From<WorldParseError> for String {
+    fn from(e: WorldParseError) -> Self {
+        e.to_string()
+    }
+}
+
 impl FromStr for World {
-    type Err = String;
+    type Err = WorldParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
+        Self::from_lines(s.lines().map(Ok), false)
+    }
+}
 
-        let first_line = lines.next().ok_or("missing size line")?;
+/// Error of [`World::from_reader`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ParseWorldError {
+    /// Read failed.
+    Io(std::io::Error),
+    /// Content is not a valid world.
+    Invalid(WorldParseError),
+}
 
-        let mut hw = first_line.split(',');
-        let height = hw
-            .next()
-            .ok_or("can't get height of world")?
-            .parse::<NonZeroUsize>()
-            .map_err(|e| format!("{}", e))?;
-        let width = hw
-            .next()
-            .ok_or("can't get width of world")?
-            .parse::<NonZeroUsize>()
-            .map_err(|e| format!("{}", e))?;
+#[cfg(feature = "std")]
+impl From<WorldParseError> for ParseWorldError {
+    fn from(e: WorldParseError) -> Self {
+        Self::Invalid(e)
+    }
+}
 
-        if height.get().checked_mul(width.get()).is_none() {
-            return Err("too many blocks".into());
+#[cfg(feature = "std")]
+impl Display for ParseWorldError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(e) => Display::fmt(e, f),
+            Self::Invalid(e) => Display::fmt(e, f),
         }
+    }
+}
 
-        let mut blocks = Vec::new();
-
-        for line in lines {
-            for (i, part) in line.char_indices() {
-                let block = line
-                    .get(i..i + part.len_utf8())
-                    .unwrap()
-                    .parse()
-                    .map_err(|_| format!("invalid block char: {part}"))?;
-                blocks.push(block);
-            }
+#[cfg(feature = "std")]
+impl std::error::Error for ParseWorldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Invalid(e) => Some(e),
         }
-
-        Ok(Self::new_from_blocks(height, width, blocks))
     }
 }
 
 impl Display for World {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_fmt(format_args!("{},{}\n", self.height, self.width))?;
+        if !f.alternate() {
+            f.write_fmt(format_args!(
+                "v{}\n{},{}\n",
+                Self::FORMAT_VERSION,
+                self.height,
+                self.width
+            ))?;
+        }
         for row in 0..self.height.get() {
             for col in 0..self.width.get() {
-                Display::fmt(self.get(row, col).unwrap(), f)?;
+                Display::fmt(&self[(row, col)], f)?;
             }
             f.write_char('\n')?;
         }
@@ -92,6 +253,138 @@ impl Display for World {
     }
 }
 
+/// Error of fallible constructors and size changing methods of [`World`], like [`World::try_new_with`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CapacityError {
+    /// Count of blocks is larger than [`usize::MAX`].
+    Overflow,
+    /// Count of blocks is larger than the limit given by caller.
+    ExceedsLimit {
+        /// Count of blocks needed.
+        blocks: usize,
+        /// The limit.
+        limit: usize,
+    },
+    /// Allocator can't provide memory for the blocks.
+    AllocationFailed {
+        /// Count of blocks needed.
+        blocks: usize,
+    },
+}
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("too many blocks"),
+            Self::ExceedsLimit { blocks, limit } => write!(f, "{blocks} blocks exceeds limit {limit}"),
+            Self::AllocationFailed { blocks } => write!(f, "failed to allocate {blocks} blocks"),
+        }
+    }
+}
+
+impl core::error::Error for CapacityError {}
+
+/// Get block at `(row, col)`, see [`World::get`] for the non-panicking version.
+///
+/// ## Panics
+///
+/// Index out of range.
+impl Index<(usize, usize)> for World {
+    type Output = Block;
+
+    #[track_caller]
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        match self.get(row, col) {
+            Some(block) => block,
+            None => panic!(
+                "index ({row}, {col}) out of range of {}x{} world",
+                self.height, self.width
+            ),
+        }
+    }
+}
+
+/// Get mutable block at `(row, col)`, see [`World::get_mut`] for the non-panicking version.
+///
+/// ## Panics
+///
+/// Index out of range.
+impl IndexMut<(usize, usize)> for World {
+    #[track_caller]
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        let (height, width) = (self.height, self.width);
+        match self.get_mut(row, col) {
+            Some(block) => block,
+            None => panic!("index ({row}, {col}) out of range of {height}x{width} world"),
+        }
+    }
+}
+
+/// Get block at position, same as indexing with `(row, col)`.
+///
+/// ## Panics
+///
+/// Index out of range.
+impl Index<Pos> for World {
+    type Output = Block;
+
+    #[track_caller]
+    fn index(&self, pos: Pos) -> &Self::Output {
+        &self[(pos.row, pos.col)]
+    }
+}
+
+/// Get mutable block at position, same as indexing with `(row, col)`.
+///
+/// ## Panics
+///
+/// Index out of range.
+impl IndexMut<Pos> for World {
+    #[track_caller]
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        &mut self[(pos.row, pos.col)]
+    }
+}
+
+impl IntoIterator for World {
+    type Item = (usize, usize, Block);
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.blocks, self.width.get())
+    }
+}
+
+impl<'a> IntoIterator for &'a World {
+    type Item = (usize, usize, &'a Block);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut World {
+    type Item = (usize, usize, &'a mut Block);
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Set blocks at `(row, col)`, entries out of range are ignored. Items have the same form as [`World`]'s
+/// [`IntoIterator`] items, so blocks of another world with the same size can be copied by extending with it.
+impl Extend<(usize, usize, Block)> for World {
+    fn extend<T: IntoIterator<Item = (usize, usize, Block)>>(&mut self, iter: T) {
+        for (row, col, block) in iter {
+            if let Some(b) = self.get_mut(row, col) {
+                *b = block;
+            }
+        }
+    }
+}
+
 impl World {
     #[track_caller]
     fn unchecked_size(height: usize, width: usize) -> usize {
@@ -100,27 +393,66 @@ impl World {
         size.unwrap()
     }
 
+    fn try_size(height: usize, width: usize, limit: Option<usize>) -> Result<usize, CapacityError> {
+        let blocks = height.checked_mul(width).ok_or(CapacityError::Overflow)?;
+        match limit {
+            Some(limit) if blocks > limit => Err(CapacityError::ExceedsLimit { blocks, limit }),
+            _ => Ok(blocks),
+        }
+    }
+
+    fn try_alloc(blocks: usize) -> Result<Vec<Block>, CapacityError> {
+        let mut result = Vec::new();
+        result
+            .try_reserve_exact(blocks)
+            .map_err(|_| CapacityError::AllocationFailed { blocks })?;
+        Ok(result)
+    }
+
     /// Create a all empty world in given size.
     ///
     /// ## Panics
     ///
-    /// height * width > usize::MAX.
+    /// height * width > usize::MAX, see [`World::try_empty`] for the non-panicking version.
+    #[track_caller]
     pub fn empty(height: NonZeroUsize, width: NonZeroUsize) -> Self {
         Self::new_with(height, width, |_, _| Block::Empty)
     }
 
+    /// Create a all empty world in the same size and border mode of `other`.
+    pub fn empty_like(other: &World) -> Self {
+        Self::empty(other.height, other.width).with_open_borders(other.open_borders)
+    }
+
+    /// Create a all empty world in given size, return error if height * width > usize::MAX or allocation failed.
+    pub fn try_empty(height: NonZeroUsize, width: NonZeroUsize) -> Result<Self, CapacityError> {
+        Self::try_new_with(height, width, None, |_, _| Block::Empty)
+    }
+
     /// Create a given size world using an init function, this function will be called in each block,
     /// given argument of `row` and `col`, start from zero.
     ///
     /// ## Panics
     ///
-    /// height * width > usize::MAX.
-    pub fn new_with<F>(height: NonZeroUsize, width: NonZeroUsize, mut f: F) -> Self
+    /// height * width > usize::MAX, see [`World::try_new_with`] for the non-panicking version.
+    #[track_caller]
+    pub fn new_with<F>(height: NonZeroUsize, width: NonZeroUsize, f: F) -> Self
     where
         F: FnMut(usize, usize) -> Block,
     {
-        let size = Self::unchecked_size(height.get(), width.get());
-        let mut blocks = Vec::with_capacity(size);
+        Self::try_new_with(height, width, None, f).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like [`World::new_with`], but return error instead of panic or abort, when height * width > usize::MAX,
+    /// it's larger than `max_blocks` if given, or allocation failed.
+    pub fn try_new_with<F>(
+        height: NonZeroUsize, width: NonZeroUsize, max_blocks: Option<usize>, mut f: F,
+    ) -> Result<Self, CapacityError>
+    where
+        F: FnMut(usize, usize) -> Block,
+    {
+        let size = Self::try_size(height.get(), width.get(), max_blocks)?;
+        let mut blocks = Self::try_alloc(size)?;
 
         let mut cur = 0;
         blocks.resize_with(size, move || {
@@ -129,7 +461,7 @@ impl World {
             cell
         });
 
-        Self::new_from_blocks(height, width, blocks)
+        Ok(Self::new_from_blocks(height, width, blocks))
     }
 
     /// Create a given size world using given blocks. blocks' size must be equal to height * width.
@@ -142,7 +474,277 @@ impl World {
 
         assert!(size == blocks.len(), "block size not match");
 
-        Self { height, width, blocks }
+        Self {
+            height,
+            width,
+            blocks,
+            open_borders: false,
+            format_version: Self::FORMAT_VERSION,
+        }
+    }
+
+    /// Parse a version line like `v1`, return None if `line` is not a version line.
+    ///
+    /// `1` is not a block char, so a version line can't be a row.
+    fn parse_version(line: &str) -> Option<Result<u32, WorldParseError>> {
+        let version = line.strip_prefix('v')?;
+        if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Some(match version.parse() {
+            Ok(v) if (1..=Self::FORMAT_VERSION).contains(&v) => Ok(v),
+            _ => Err(WorldParseError::UnsupportedVersion(version.to_string())),
+        })
+    }
+
+    fn parse_size(line: &str) -> Result<(NonZeroUsize, NonZeroUsize), WorldParseError> {
+        // callers make sure line contains `,`, so there are always two parts, extra `,` makes width invalid
+        let (height, width) = line.split_once(',').unwrap();
+        let height = height.parse::<NonZeroUsize>().map_err(WorldParseError::BadHeight)?;
+        let width = width.parse::<NonZeroUsize>().map_err(WorldParseError::BadWidth)?;
+
+        if height.get().checked_mul(width.get()).is_none() {
+            return Err(WorldParseError::TooManyBlocks);
+        }
+
+        Ok((height, width))
+    }
+
+    /// Parse a row of blocks into `blocks`, return count of blocks in this row, without padding.
+    ///
+    /// Invalid chars are recorded into `errors` and parsed as empty blocks, so the count is still correct.
+    fn parse_row(
+        line: &str, row: usize, lenient: bool, blocks: &mut Vec<Block>, errors: &mut Vec<WorldParseError>,
+    ) -> usize {
+        let mut cols = 0;
+
+        for (i, ch) in line.char_indices() {
+            let s = line.get(i..i + ch.len_utf8()).unwrap();
+            let block = if lenient {
+                Block::parse_lenient(s)
+            } else {
+                s.parse().ok()
+            };
+            let block = block.unwrap_or_else(|| {
+                errors.push(WorldParseError::InvalidBlock { row, col: cols, ch });
+                Block::Empty
+            });
+            blocks.push(block);
+            cols += 1;
+        }
+
+        cols
+    }
+
+    fn from_lines<L, E>(mut lines: impl Iterator<Item = Result<L, E>>, lenient: bool) -> Result<Self, E>
+    where
+        L: AsRef<str>,
+        E: From<WorldParseError>,
+    {
+        let mut first_line = lines.next().ok_or(WorldParseError::MissingSizeLine)??;
+        let mut version = 0;
+        if let Some(v) = Self::parse_version(first_line.as_ref()) {
+            version = v?;
+            first_line = lines.next().ok_or(WorldParseError::MissingSizeLine)??;
+            if !first_line.as_ref().contains(',') {
+                return Err(WorldParseError::MissingSizeLine.into());
+            }
+        }
+        let first_line = first_line.as_ref();
+
+        let mut blocks = Vec::new();
+        let mut errors = Vec::new();
+
+        // `,` is not a block char, so a line contains it can only be the size line
+        let size = if first_line.contains(',') {
+            Some(Self::parse_size(first_line)?)
+        } else {
+            if Self::parse_row(first_line, 0, lenient, &mut blocks, &mut errors) == 0 {
+                return Err(WorldParseError::MissingSizeLine.into());
+            }
+            None
+        };
+
+        if !errors.is_empty() {
+            return Err(errors.swap_remove(0).into());
+        }
+
+        // without size line, width is decided by the first row
+        let width = size.map_or(blocks.len(), |(_, width)| width.get());
+        let mut rows = usize::from(size.is_none());
+
+        for line in lines {
+            let line = line?;
+            let found = Self::parse_row(line.as_ref(), rows, lenient, &mut blocks, &mut errors);
+            if !errors.is_empty() {
+                return Err(errors.swap_remove(0).into());
+            }
+
+            // trailing spaces may be stripped by editors, only possible to restore with a size line
+            if size.is_some() && found < width {
+                blocks.resize(blocks.len() + width - found, Block::Empty);
+            } else if found != width {
+                return Err(WorldParseError::RowLengthMismatch {
+                    row: rows,
+                    expected: width,
+                    found,
+                }
+                .into());
+            }
+
+            rows += 1;
+        }
+
+        let (height, width) = match size {
+            Some((height, width)) => {
+                if rows != height.get() {
+                    return Err(WorldParseError::RowCountMismatch {
+                        expected: height.get(),
+                        found: rows,
+                    }
+                    .into());
+                }
+                (height, width)
+            }
+            None => (NonZeroUsize::new(rows).unwrap(), NonZeroUsize::new(width).unwrap()),
+        };
+
+        Ok(Self::new_from_blocks(height, width, blocks).with_format_version(version))
+    }
+
+    /// Parse a world like [`World`]'s [`FromStr`] implementation, but also accept block aliases, see
+    /// [`Block::parse_lenient`].
+    pub fn parse_lenient(s: &str) -> Result<Self, WorldParseError> {
+        Self::from_lines(s.lines().map(Ok), true)
+    }
+
+    /// Parse a world like [`World`]'s [`FromStr`] implementation, but continue after errors to report all of them,
+    /// in the order they appear.
+    ///
+    /// When size line is invalid, width is decided by the first row, and row count is not checked.
+    pub fn parse_all_errors(s: &str) -> Result<Self, Vec<WorldParseError>> {
+        let mut lines = s.lines();
+        let mut first_line = lines.next().ok_or_else(|| vec![WorldParseError::MissingSizeLine])?;
+
+        let mut blocks = Vec::new();
+        let mut errors = Vec::new();
+        let mut width = None;
+        let mut version = 0;
+
+        if let Some(v) = Self::parse_version(first_line) {
+            // keep parsing with the current version to find more errors
+            version = v.unwrap_or_else(|e| {
+                errors.push(e);
+                Self::FORMAT_VERSION
+            });
+            first_line = lines.next().unwrap_or_default();
+            if !first_line.contains(',') {
+                return Err(errors.into_iter().chain([WorldParseError::MissingSizeLine]).collect());
+            }
+        }
+
+        // rows shorter than width are padded only if width is from the size line
+        let has_size_line = first_line.contains(',');
+        let (size, mut rows) = if has_size_line {
+            let size = Self::parse_size(first_line).map_err(|e| errors.push(e)).ok();
+            width = size.map(|(_, width)| width.get());
+            (size, 0)
+        } else {
+            match Self::parse_row(first_line, 0, false, &mut blocks, &mut errors) {
+                0 => errors.push(WorldParseError::MissingSizeLine),
+                found => width = Some(found),
+            }
+            (None, 1)
+        };
+
+        for line in lines {
+            let found = Self::parse_row(line, rows, false, &mut blocks, &mut errors);
+            match width {
+                Some(expected) if has_size_line && found < expected => {
+                    blocks.resize(blocks.len() + expected - found, Block::Empty)
+                }
+                Some(expected) if found != expected => errors.push(WorldParseError::RowLengthMismatch {
+                    row: rows,
+                    expected,
+                    found,
+                }),
+                Some(_) => (),
+                None => width = Some(found),
+            }
+            rows += 1;
+        }
+
+        if let Some((height, _)) = size {
+            if rows != height.get() {
+                errors.push(WorldParseError::RowCountMismatch {
+                    expected: height.get(),
+                    found: rows,
+                });
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        // no error means there is at least one row and all rows have the same non-zero width
+        let height = NonZeroUsize::new(rows).unwrap();
+        let width = NonZeroUsize::new(width.unwrap()).unwrap();
+        Ok(Self::new_from_blocks(height, width, blocks).with_format_version(version))
+    }
+
+    /// Parse a world from a reader line by line, without reading all content into memory first.
+    ///
+    /// See [`World`] document for the format.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<Self, ParseWorldError> {
+        use std::io::BufRead;
+
+        Self::from_lines(
+            std::io::BufReader::new(reader)
+                .lines()
+                .map(|line| line.map_err(ParseWorldError::Io)),
+            false,
+        )
+    }
+
+    /// Write this world's string representation to a writer.
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: std::io::Write>(&self, mut w: W) -> std::io::Result<()> {
+        write!(w, "{self}")
+    }
+
+    /// Parse many worlds from one string, each in [`World`]'s string representation, separated by empty lines or
+    /// `---` lines.
+    ///
+    /// Error message contains the 1-based index of the section failed to parse, row numbers in it are counted
+    /// from the start of that section.
+    pub fn parse_many(s: &str) -> Result<Vec<Self>, String> {
+        let mut worlds = Vec::new();
+        let mut section = String::new();
+
+        let mut parse_section = |section: &mut String| -> Result<(), String> {
+            if !section.is_empty() {
+                let world = section
+                    .parse()
+                    .map_err(|e| format!("section {}: {e}", worlds.len() + 1))?;
+                worlds.push(world);
+                section.clear();
+            }
+            Ok(())
+        };
+
+        for line in s.lines() {
+            if line.is_empty() || line == "---" {
+                parse_section(&mut section)?;
+            } else {
+                section.push_str(line);
+                section.push('\n');
+            }
+        }
+        parse_section(&mut section)?;
+
+        Ok(worlds)
     }
 
     /// Shuffle all blocks.
@@ -153,6 +755,31 @@ impl World {
         }
     }
 
+    /// Shuffle all blocks like [`World::shuffle`], but in parallel, it's faster for large worlds on multi-core
+    /// machines.
+    ///
+    /// `rng_factory` is called to create an RNG for each batch of blocks handled by a thread, so it should not
+    /// return RNGs with the same seed, or batches get the same directions, `rand::thread_rng` is a good choice.
+    /// Result is not reproducible, because how blocks are split into batches depends on scheduling.
+    #[cfg(feature = "parallel")]
+    pub fn shuffle_par<F, R>(&mut self, rng_factory: F)
+    where
+        F: Fn() -> R + Sync + Send,
+        R: rand::Rng,
+    {
+        use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+
+        self.blocks
+            .par_iter_mut()
+            .for_each_init(rng_factory, |rng, block| block.shuffle(rng));
+    }
+
+    /// Shuffle all blocks with a seed, same seed always gives same result on every platform.
+    #[cfg(feature = "random")]
+    pub fn shuffle_seeded(&mut self, seed: u64) {
+        self.shuffle(crate::seeded_rng(seed));
+    }
+
     /// Get size of the world.
     pub fn size(&self) -> (NonZeroUsize, NonZeroUsize) {
         (self.height, self.width)
@@ -168,72 +795,249 @@ impl World {
         self.width
     }
 
+    fn index(&self, row: usize, col: usize) -> Option<usize> {
+        if row < self.height.get() && col < self.width.get() {
+            Some(row * self.width.get() + col)
+        } else {
+            None
+        }
+    }
+
     /// Get a block in given index, return None if out of range.
     pub fn get(&self, row: usize, col: usize) -> Option<&Block> {
-        self.blocks.get(row * self.width.get() + col)
+        self.index(row, col).map(|i| &self.blocks[i])
     }
 
     /// get a mutable block in given location, return None if out of range.
     pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut Block> {
-        self.blocks.get_mut(row * self.width.get() + col)
+        self.index(row, col).map(|i| &mut self.blocks[i])
     }
 
-    /// Get inner blocks.
-    pub fn into_inner(self) -> Vec<Block> {
-        self.blocks
+    /// Get blocks of a row, return None if out of range.
+    pub fn row(&self, index: usize) -> Option<&[Block]> {
+        let width = self.width.get();
+        if index < self.height.get() {
+            Some(&self.blocks[index * width..(index + 1) * width])
+        } else {
+            None
+        }
     }
 
-    /// Insert a row with empty blocks at index, index range [0, world.height].
-    ///
-    /// ## Panics
-    ///
-    /// Index out of range.
-    pub fn insert_row(&mut self, index: usize) {
-        assert!(index <= self.height.get(), "index out of range");
+    /// Iterate over blocks with their positions, as `(row, col, &block)`, in row-major order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(&self.blocks, self.width.get())
+    }
 
-        let after = self.blocks.split_off(self.width.get() * index);
-        self.blocks
-            .extend(core::iter::repeat(Block::Empty).take(self.width.get()));
-        self.blocks.extend(after);
+    /// Iterate over mutable blocks with their positions, as `(row, col, &mut block)`, in row-major order.
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut::new(&mut self.blocks, self.width.get())
+    }
 
-        self.height = NonZeroUsize::new(self.height.get() + 1).unwrap();
+    /// Iterate over rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[Block]> + '_ {
+        self.blocks.chunks(self.width.get())
     }
 
-    /// Remove row at index, index range [0, world.height).
-    ///
-    /// ## Panics
+    /// Convert into owned rows, from top to bottom, see [`World::rows`] for the borrowed version.
+    pub fn into_rows(self) -> Vec<Vec<Block>> {
+        self.blocks.chunks(self.width.get()).map(<[Block]>::to_vec).collect()
+    }
+
+    /// Create a world from rows of blocks, the inverse of [`World::into_rows`].
     ///
-    /// world.height == 1 or index out of range.
-    pub fn remove_row(&mut self, index: usize) {
-        assert!(index < self.height.get(), "index out of range");
+    /// Returns [`WorldParseError::MissingSizeLine`] if there is no row or the first row is empty,
+    /// and [`WorldParseError::RowLengthMismatch`] if rows have different length.
+    pub fn from_rows(rows: Vec<Vec<Block>>) -> Result<Self, WorldParseError> {
+        let height = NonZeroUsize::new(rows.len()).ok_or(WorldParseError::MissingSizeLine)?;
+        let width = NonZeroUsize::new(rows[0].len()).ok_or(WorldParseError::MissingSizeLine)?;
 
-        let start = index * self.width.get();
-        self.blocks.drain(start..start + self.width.get());
+        let mut blocks = Vec::new();
+        for (row, blocks_of_row) in rows.into_iter().enumerate() {
+            if blocks_of_row.len() != width.get() {
+                return Err(WorldParseError::RowLengthMismatch {
+                    row,
+                    expected: width.get(),
+                    found: blocks_of_row.len(),
+                });
+            }
+            blocks.extend(blocks_of_row);
+        }
 
-        self.height = NonZeroUsize::new(self.height.get() - 1).expect("can't remove last row");
+        Ok(Self::new_from_blocks(height, width, blocks))
     }
 
-    /// Insert a column with empty blocks at index, index range [0. world.width].
+    /// Iterate over columns, from left to right.
     ///
-    /// ## Panics
+    /// Blocks are stored in row-major order, so unlike [`World::rows`], each column needs to be collected into a
+    /// new Vec.
+    pub fn columns(&self) -> impl Iterator<Item = Vec<Block>> + '_ {
+        (0..self.width.get()).map(move |col| self.rows().map(|row| row[col]).collect())
+    }
+
+    /// Normalize all blocks, see [`Block::normalized`].
     ///
-    /// Index out of range.
-    pub fn insert_column(&mut self, index: usize) {
-        assert!(index <= self.width.get(), "index out of range");
+    /// Worlds whose blocks connect the same sides are equal after normalized.
+    pub fn normalize(&mut self) {
+        for block in &mut self.blocks {
+            *block = block.normalized();
+        }
+    }
 
-        let mut new_blocks = Vec::with_capacity(self.height.get() * (self.width.get() + 1));
+    /// Count blocks by their kind.
+    pub fn block_counts(&self) -> BlockCounts {
+        let mut counts = BlockCounts::default();
 
-        for row in self.blocks.chunks(self.width.get()) {
-            new_blocks.extend(row[..index].iter().copied());
-            new_blocks.push(Block::Empty);
-            new_blocks.extend(row[index..].iter().copied());
+        for block in &self.blocks {
+            *match block {
+                Block::Empty => &mut counts.empty,
+                Block::Endpoint(_) => &mut counts.endpoint,
+                Block::Through(_) => &mut counts.through,
+                Block::Turn(_) => &mut counts.turn,
+                Block::Fork(_) => &mut counts.fork,
+                Block::Cross => &mut counts.cross,
+            } += 1;
         }
 
-        self.blocks = new_blocks;
-        self.width = NonZeroUsize::new(self.width.get() + 1).unwrap();
+        counts
     }
 
-    /// Remove column at index, index range [0, world.width).
+    /// Get count of blocks, which is height * width.
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Get a checksum of size, blocks and border mode, it's stable across runs, platforms and versions, unlike
+    /// [`Hash`](core::hash::Hash), so it can be used as a cache key, e.g. for solver results.
+    ///
+    /// Blocks connect the same sides are treated as equal, see [`Block::normalized`].
+    pub fn checksum(&self) -> u64 {
+        // FNV-1a 64 bit
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        let size = [self.height.get() as u64, self.width.get() as u64];
+        let bytes = size
+            .into_iter()
+            .flat_map(u64::to_le_bytes)
+            .chain(self.blocks.iter().map(|block| {
+                let block = block.normalized();
+                let kind = match block {
+                    Block::Empty => 0,
+                    Block::Endpoint(_) => 1,
+                    Block::Through(_) => 2,
+                    Block::Turn(_) => 3,
+                    Block::Fork(_) => 4,
+                    Block::Cross => 5,
+                };
+                kind << 2 | block.direction().map_or(0, |dir| dir as u8)
+            }))
+            .chain([u8::from(self.open_borders)]);
+
+        bytes.fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+    }
+
+    /// Get estimated heap memory used by this world in bytes, it's the capacity of inner block storage.
+    pub fn heap_size_estimate(&self) -> usize {
+        self.blocks.capacity() * core::mem::size_of::<Block>()
+    }
+
+    /// Get inner blocks.
+    pub fn into_inner(self) -> Vec<Block> {
+        self.blocks
+    }
+
+    /// Insert a row with empty blocks at index, index range [0, world.height].
+    ///
+    /// ## Panics
+    ///
+    /// Index out of range, or too many blocks, see [`World::try_insert_row`].
+    #[track_caller]
+    pub fn insert_row(&mut self, index: usize) {
+        self.try_insert_row(index).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Insert a row with empty blocks at index, index range [0, world.height], return error if blocks count
+    /// overflows or allocation failed, this world is not changed then.
+    ///
+    /// ## Panics
+    ///
+    /// Index out of range.
+    #[track_caller]
+    pub fn try_insert_row(&mut self, index: usize) -> Result<(), CapacityError> {
+        assert!(index <= self.height.get(), "index out of range");
+
+        let height = self.height.get().checked_add(1).ok_or(CapacityError::Overflow)?;
+        let size = Self::try_size(height, self.width.get(), None)?;
+        self.blocks
+            .try_reserve_exact(self.width.get())
+            .map_err(|_| CapacityError::AllocationFailed { blocks: size })?;
+
+        let start = self.width.get() * index;
+        self.blocks
+            .splice(start..start, core::iter::repeat_n(Block::Empty, self.width.get()));
+
+        self.height = NonZeroUsize::new(height).unwrap();
+        Ok(())
+    }
+
+    /// Remove row at index, index range [0, world.height).
+    ///
+    /// ## Panics
+    ///
+    /// world.height == 1 or index out of range.
+    pub fn remove_row(&mut self, index: usize) {
+        assert!(index < self.height.get(), "index out of range");
+
+        // check before removing, so a panic doesn't leave blocks count mismatched with size
+        let height = NonZeroUsize::new(self.height.get() - 1).expect("can't remove last row");
+
+        let start = index * self.width.get();
+        self.blocks.drain(start..start + self.width.get());
+
+        self.height = height;
+    }
+
+    /// Insert a column with empty blocks at index, index range [0. world.width].
+    ///
+    /// ## Panics
+    ///
+    /// Index out of range, or too many blocks, see [`World::try_insert_column`].
+    #[track_caller]
+    pub fn insert_column(&mut self, index: usize) {
+        self.try_insert_column(index).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Insert a column with empty blocks at index, index range [0. world.width], return error if blocks count
+    /// overflows or allocation failed, this world is not changed then.
+    ///
+    /// ## Panics
+    ///
+    /// Index out of range.
+    #[track_caller]
+    pub fn try_insert_column(&mut self, index: usize) -> Result<(), CapacityError> {
+        assert!(index <= self.width.get(), "index out of range");
+
+        let (height, old_width) = (self.height.get(), self.width.get());
+        let width = old_width.checked_add(1).ok_or(CapacityError::Overflow)?;
+        let size = Self::try_size(height, width, None)?;
+        self.blocks
+            .try_reserve_exact(height)
+            .map_err(|_| CapacityError::AllocationFailed { blocks: size })?;
+        self.blocks.resize(size, Block::Empty);
+
+        // shift rows from the last one, so every row moves into space which is already copied
+        for row in (0..height).rev() {
+            let (src, dst) = (row * old_width, row * width);
+            self.blocks.copy_within(src + index..src + old_width, dst + index + 1);
+            self.blocks.copy_within(src..src + index, dst);
+            self.blocks[dst + index] = Block::Empty;
+        }
+
+        self.width = NonZeroUsize::new(width).unwrap();
+        Ok(())
+    }
+
+    /// Remove column at index, index range [0, world.width).
     ///
     /// ## Panics
     ///
@@ -241,15 +1045,18 @@ impl World {
     pub fn remove_column(&mut self, index: usize) {
         assert!(index < self.width.get(), "index out of range");
 
-        self.blocks = self
-            .blocks
-            .iter()
-            .enumerate()
-            .filter_map(|(i, b)| if i % self.width == index { None } else { Some(b) })
-            .copied()
-            .collect();
+        let (height, old_width) = (self.height.get(), self.width.get());
+        let width = NonZeroUsize::new(old_width - 1).expect("can't remove last column");
+
+        // shift rows from the first one, so every row moves into space which is already copied
+        for row in 0..height {
+            let (src, dst) = (row * old_width, row * width.get());
+            self.blocks.copy_within(src..src + index, dst);
+            self.blocks.copy_within(src + index + 1..src + old_width, dst + index);
+        }
+        self.blocks.truncate(height * width.get());
 
-        self.width = NonZeroUsize::new(self.width.get() - 1).expect("can't remove last row");
+        self.width = width;
     }
 
     /// Rotate the block at given index.
@@ -261,14 +1068,168 @@ impl World {
         self.get_mut(row, col).expect("block index out of range").rotate();
     }
 
+    /// Get result of mirror this world left to right, blocks are mirrored too.
+    pub fn flipped_horizontally(&self) -> Self {
+        let width = self.width.get();
+        Self::new_with(self.height, self.width, |row, col| {
+            self[(row, width - 1 - col)].flipped_horizontally()
+        })
+        .with_open_borders(self.open_borders)
+    }
+
+    /// Get result of mirror this world top to bottom, blocks are mirrored too.
+    pub fn flipped_vertically(&self) -> Self {
+        let height = self.height.get();
+        Self::new_with(self.height, self.width, |row, col| {
+            self[(height - 1 - row, col)].flipped_vertically()
+        })
+        .with_open_borders(self.open_borders)
+    }
+
+    /// Get result of mirror this world along the top left to bottom right diagonal, so rows become columns, blocks
+    /// are mirrored too.
+    pub fn transposed(&self) -> Self {
+        Self::new_with(self.width, self.height, |row, col| self[(col, row)].transposed())
+            .with_open_borders(self.open_borders)
+    }
+
+    /// Get result of rotate whole world clockwise, blocks are rotated too.
+    pub fn rotated(&self) -> Self {
+        let height = self.height.get();
+        Self::new_with(self.width, self.height, |row, col| {
+            self[(height - 1 - col, row)].rotated()
+        })
+        .with_open_borders(self.open_borders)
+    }
+
+    /// Replace all blocks matching `pred` with `replacement`.
+    pub fn replace_block_type<F>(&mut self, pred: F, replacement: Block)
+    where
+        F: Fn(&Block) -> bool,
+    {
+        self.blocks
+            .iter_mut()
+            .filter(|block| pred(block))
+            .for_each(|block| *block = replacement);
+    }
+
+    /// Replace all empty blocks with given block.
+    pub fn replace_empty_with(&mut self, block: Block) {
+        self.replace_block_type(|b| *b == Block::Empty, block);
+    }
+
+    /// Replace all blocks matching `pred` with empty blocks.
+    pub fn clear_type<F>(&mut self, pred: F)
+    where
+        F: Fn(&Block) -> bool,
+    {
+        self.replace_block_type(pred, Block::Empty);
+    }
+
+    /// Get positions of the orthogonally connected area around `(row, col)` whose blocks are the same kind as the
+    /// block there, directions are ignored, in the order they are found. Returns empty if position out of range.
+    pub(crate) fn flood_positions(&self, row: usize, col: usize) -> Vec<Pos> {
+        let Some(kind) = self.get(row, col).map(core::mem::discriminant) else {
+            return Vec::new();
+        };
+
+        let width = self.width.get();
+        let mut visited = vec![false; self.blocks.len()];
+        let mut stack = vec![Pos::new(row, col)];
+        let mut result = Vec::new();
+        visited[row * width + col] = true;
+
+        // iterative, a recursive fill may overflow the stack on large worlds
+        while let Some(pos) = stack.pop() {
+            result.push(pos);
+            for dir in [Direction::Up, Direction::Right, Direction::Down, Direction::Left] {
+                let Some(next) = pos.step(dir) else { continue };
+                let Some(block) = self.get(next.row, next.col) else {
+                    continue;
+                };
+                let index = next.row * width + next.col;
+                if !visited[index] && core::mem::discriminant(block) == kind {
+                    visited[index] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Replace blocks of the orthogonally connected area around `(row, col)` with given block, the area is made of
+    /// blocks of the same kind as the block there, directions are ignored, e.g. all turns connected to it.
+    ///
+    /// Returns count of changed blocks, 0 if position out of range.
+    pub fn flood_fill(&mut self, row: usize, col: usize, block: Block) -> usize {
+        let mut changed = 0;
+        for pos in self.flood_positions(row, col) {
+            if self[pos] != block {
+                self[pos] = block;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Rotate every block in given rows and columns clockwise `steps` times, ranges are clamped into the world.
+    pub fn turn_region(&mut self, rows: Range<usize>, cols: Range<usize>, steps: u8) {
+        let (height, width) = (self.height.get(), self.width.get());
+        for row in rows.start.min(height)..rows.end.min(height) {
+            for col in cols.start.min(width)..cols.end.min(width) {
+                self[(row, col)].turn_by(steps);
+            }
+        }
+    }
+
+    /// Format version of the string representation written by [`Display`].
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Get format version of the text this world is parsed from, 0 for legacy text without version line.
+    ///
+    /// Worlds created in other ways, including transformed copies like [`World::rotated`], are in
+    /// [`World::FORMAT_VERSION`]. It's not considered when comparing worlds.
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+
+    fn with_format_version(mut self, version: u32) -> Self {
+        self.format_version = version;
+        self
+    }
+
+    /// Check if pipes may end at the world's edge.
+    pub fn open_borders(&self) -> bool {
+        self.open_borders
+    }
+
+    /// Set whether pipes may end at the world's edge, default to `false`.
+    ///
+    /// With open borders, a block which passes to the boundary still fits, so the world is solved when all blocks
+    /// fit with their neighbors internally. This flag is compared in [`PartialEq`] too.
+    pub fn set_open_borders(&mut self, open: bool) {
+        self.open_borders = open;
+    }
+
+    fn with_open_borders(mut self, open: bool) -> Self {
+        self.open_borders = open;
+        self
+    }
+
+    /// Check if block at given position passes to the boundary while borders are closed.
+    pub(crate) fn passes_boundary(&self, row: usize, col: usize, block: &Block) -> bool {
+        !self.open_borders
+            && (row == 0 && block.passable(Direction::Up)
+                || row == self.height.get() - 1 && block.passable(Direction::Down)
+                || col == 0 && block.passable(Direction::Left)
+                || col == self.width.get() - 1 && block.passable(Direction::Right))
+    }
+
     fn check_block_fit_with_right_down(&self, row: usize, col: usize) -> bool {
         let block = self.get(row, col).unwrap();
 
-        if row == 0 && block.passable(Direction::Up)
-            || row == self.height.get() - 1 && block.passable(Direction::Down)
-            || col == 0 && block.passable(Direction::Left)
-            || col == self.width.get() - 1 && block.passable(Direction::Right)
-        {
+        if self.passes_boundary(row, col, block) {
             return false;
         }
 
@@ -293,10 +1254,964 @@ impl World {
         true
     }
 
-    /// Check if this world's blocks is all fit.
-    pub fn solved(&self) -> bool {
-        (0..self.height.get())
+    /// Check if `block` placed at `(row, col)` fits the neighbor at `side`, or the boundary if there is no
+    /// neighbor.
+    pub(crate) fn side_fits(&self, row: usize, col: usize, block: &Block, side: Direction) -> bool {
+        let neighbor = Pos::new(row, col).step(side).and_then(|pos| self.get(pos.row, pos.col));
+        match neighbor {
+            Some(neighbor) => block.fit(side, neighbor),
+            // boundary
+            None => self.open_borders || !block.passable(side),
+        }
+    }
+
+    fn check_block_fit(&self, row: usize, col: usize) -> bool {
+        const SIDES: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+        let block = &self[(row, col)];
+        SIDES.iter().all(|side| self.side_fits(row, col, block, *side))
+    }
+
+    /// Count sides of block at given position which fit, each is an edge counted by [`World::count_correct_edges`].
+    pub(crate) fn count_block_correct_edges(&self, row: usize, col: usize) -> usize {
+        const SIDES: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+        let block = &self[(row, col)];
+        SIDES
+            .iter()
+            .filter(|side| self.side_fits(row, col, block, **side))
+            .count()
+    }
+
+    /// Count of all edges, between blocks and on the boundary.
+    pub(crate) fn count_edges(&self) -> usize {
+        let (height, width) = (self.height.get(), self.width.get());
+        (height + 1) * width + height * (width + 1)
+    }
+
+    /// Get positions of non-empty blocks which fit with all their neighbors and don't pass to the boundary
+    /// (unless borders are open), in row-major order.
+    ///
+    /// These blocks are locally correct, all non-empty blocks are in the result when this world is solved.
+    pub fn solved_cells(&self) -> Vec<Pos> {
+        self.iter()
+            .filter(|(row, col, block)| **block != Block::Empty && self.check_block_fit(*row, *col))
+            .map(|(row, col, _)| Pos::new(row, col))
+            .collect()
+    }
+
+    /// Count edges which fit, every edge between two neighbor blocks is counted once, and so is every edge between
+    /// a block and the boundary.
+    ///
+    /// The world is solved if it's not empty and this equals `(height + 1) * width + height * (width + 1)`, the
+    /// count of all edges, so it measures how close the world is to be solved, see [`World::progress`].
+    pub fn count_correct_edges(&self) -> usize {
+        self.iter()
+            .map(|(row, col, block)| {
+                // up and left edges are counted by the previous block, except the boundary ones
+                let up = row == 0 && self.side_fits(row, col, block, Direction::Up);
+                let left = col == 0 && self.side_fits(row, col, block, Direction::Left);
+                let right = self.side_fits(row, col, block, Direction::Right);
+                let down = self.side_fits(row, col, block, Direction::Down);
+                [up, left, right, down].into_iter().filter(|fit| *fit).count()
+            })
+            .sum()
+    }
+
+    /// Fraction of edges which fit, from 0.0 to 1.0, it's 1.0 only if the world is solved, see
+    /// [`World::count_correct_edges`].
+    ///
+    /// An empty world is not considered solved, so it's 0.0 though all its edges fit.
+    pub fn progress(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.count_correct_edges() as f32 / self.count_edges() as f32
+    }
+
+    /// Check if all blocks of this world are empty.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.iter().all(|b| b == &Block::Empty)
+    }
+
+    /// Check if this world's blocks is all fit, and tell why it's not solved, see [`SolvedState`].
+    pub fn solved_state(&self) -> SolvedState {
+        if self.is_empty() {
+            SolvedState::Empty
+        } else if (0..self.height.get())
             .all(|row| (0..self.width.get()).all(|col| self.check_block_fit_with_right_down(row, col)))
-            && self.blocks.iter().any(|b| b != &Block::Empty)
+        {
+            SolvedState::Solved
+        } else {
+            SolvedState::Misfit
+        }
+    }
+
+    /// Check if this world's blocks is all fit, an all empty world is not considered solved.
+    pub fn solved(&self) -> bool {
+        self.solved_state() == SolvedState::Solved
+    }
+
+    /// Check if this world is won under given rule, see [`WinMode`].
+    ///
+    /// For [`WinMode::EndpointsConnected`], a world without endpoints is not won, and a block of the network leaks
+    /// if it passes to a neighbor not passing back, or to the boundary while borders are closed.
+    pub fn is_won(&self, mode: WinMode) -> bool {
+        match mode {
+            WinMode::AllEdgesFit => self.solved(),
+            WinMode::EndpointsConnected => self.endpoints_connected(),
+        }
+    }
+
+    fn endpoints_connected(&self) -> bool {
+        let components = self.components();
+        let mut endpoints = self
+            .iter()
+            .filter(|(_, _, block)| matches!(block, Block::Endpoint(_)))
+            .map(|(row, col, _)| components.component_of(row, col));
+
+        // a lonely endpoint belongs to no network
+        let Some(Some(network)) = endpoints.next() else {
+            return false;
+        };
+        if !endpoints.all(|component| component == Some(network)) {
+            return false;
+        }
+
+        for (row, col, component) in components.iter() {
+            if component == Some(network) && self.leaks(row, col) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Check if block at `(row, col)` passes to a side which doesn't pass back, blocks outside only passing to it are
+    /// not its leaks.
+    fn leaks(&self, row: usize, col: usize) -> bool {
+        const SIDES: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+        let block = &self[(row, col)];
+        SIDES
+            .iter()
+            .any(|side| block.passable(*side) && !self.side_fits(row, col, block, *side))
+    }
+
+    /// Check if this world is solved as a sparse board, where empty blocks are holes pipes must go around.
+    ///
+    /// The rules are:
+    ///
+    /// - Empty blocks pass to no side, so a non-empty block passing to an empty neighbor doesn't fit.
+    /// - Every non-empty block fits with all its neighbors, and doesn't pass to the boundary unless borders are
+    ///   open, see [`World::set_open_borders`].
+    /// - Unlike [`World::solved`], an all empty world is solved, it's a board made of holes only.
+    pub fn solved_allow_sparse(&self) -> bool {
+        self.solved_state() != SolvedState::Misfit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    const BLOCK_CHARS: &str = " ^>v<-/1793862455";
+
+    // xorshift64, enough to make the cases different and reproducible without a dependency
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    fn random_world(rng: &mut Rng) -> World {
+        let height = NonZeroUsize::new(1 + rng.below(5)).unwrap();
+        let width = NonZeroUsize::new(1 + rng.below(5)).unwrap();
+        let blocks = (0..height.get() * width.get())
+            .map(|_| {
+                let c = BLOCK_CHARS.chars().nth(rng.below(BLOCK_CHARS.len())).unwrap();
+                c.to_string().parse().unwrap()
+            })
+            .collect();
+        World::new_from_blocks(height, width, blocks)
+    }
+
+    fn assert_size_matches(world: &World) {
+        assert_eq!(world.blocks.len(), world.height.get() * world.width.get(), "{world:?}");
+    }
+
+    #[test]
+    fn out_of_range_access_returns_none() {
+        let mut world: World = "79\n31".parse().unwrap();
+        // column past the width must not wrap to the next row
+        assert_eq!(world.get(0, 2), None);
+        assert_eq!(world.get(1, 1), Some(&Block::Turn(Direction::Up)));
+        assert_eq!(world.get_mut(2, 0), None);
+        assert_eq!(world.get_mut(usize::MAX, usize::MAX), None);
+    }
+
+    #[test]
+    fn row_and_column_views() {
+        let world: World = "v1\n2,3\n>-<\n7 9\n".parse().unwrap();
+        let block = |c: &str| c.parse::<Block>().unwrap();
+
+        let row = world.row(1).unwrap();
+        assert_eq!(row, &[block("7"), Block::Empty, block("9")]);
+        // zero-copy view into the storage
+        assert!(core::ptr::eq(row, &world.blocks[3..6]));
+        assert_eq!(world.row(2), None);
+
+        assert_eq!(world.rows().collect::<Vec<_>>(), [world.row(0).unwrap(), row]);
+        let columns: Vec<_> = world.columns().collect();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0], [block(">"), block("7")]);
+        assert_eq!(columns[2], [block("<"), block("9")]);
+    }
+
+    #[test]
+    fn normalized_worlds_compare_by_connectivity() {
+        let stored = |through_vertical, through_horizontal| {
+            let blocks = [
+                Block::Endpoint(Direction::Right),
+                Block::Through(through_horizontal),
+                Block::Endpoint(Direction::Left),
+                Block::Through(through_vertical),
+                Block::Cross,
+                Block::Empty,
+            ];
+            World::new_from_blocks(
+                NonZeroUsize::new(2).unwrap(),
+                NonZeroUsize::new(3).unwrap(),
+                blocks.to_vec(),
+            )
+        };
+
+        let (mut a, mut b) = (
+            stored(Direction::Up, Direction::Left),
+            stored(Direction::Down, Direction::Right),
+        );
+        assert_ne!(a, b);
+
+        a.normalize();
+        b.normalize();
+        assert_eq!(a, b);
+        // parsed worlds are already normalized
+        assert_eq!(a, "v1\n2,3\n>-<\n/5 \n".parse().unwrap());
+    }
+
+    #[test]
+    fn parse_many_sections() {
+        let worlds = World::parse_many("v1\n1,2\n><\n\n\n2,2\n79\n13\n---\nv1\n1,1\n5\n").unwrap();
+        assert_eq!(worlds.len(), 3);
+        assert_eq!(worlds[0], "v1\n1,2\n><\n".parse().unwrap());
+        assert_eq!(worlds[1], "v1\n2,2\n79\n13\n".parse().unwrap());
+        assert_eq!(worlds[2].get(0, 0), Some(&Block::Cross));
+
+        assert_eq!(World::parse_many("").unwrap(), []);
+
+        let error = World::parse_many("v1\n1,2\n><\n---\nv1\n1,2\n>x\n").unwrap_err();
+        assert!(error.starts_with("section 2: "), "{error}");
+    }
+
+    #[test]
+    fn turn_region_only_changes_the_region() {
+        let origin: World = "v1\n3,3\n>89\n7^1\n3<6\n".parse().unwrap();
+        let mut world = origin.clone();
+
+        world.turn_region(0..2, 1..3, 1);
+        for (row, col, block) in world.iter() {
+            let expected = if row < 2 && col >= 1 {
+                origin[(row, col)].rotated()
+            } else {
+                origin[(row, col)]
+            };
+            assert_eq!(*block, expected, "({row}, {col})");
+        }
+        assert_eq!(world.to_string(), "v1\n3,3\n>63\n7>7\n3<6\n");
+
+        // ranges are clamped, and four turns are a full circle
+        let mut world = origin.clone();
+        world.turn_region(1..10, 0..10, 4);
+        world.turn_region(5..9, 0..3, 1);
+        assert_eq!(world, origin);
+        world.turn_region(2..10, 2..10, 5);
+        assert_eq!(world.to_string(), "v1\n3,3\n>89\n7^1\n3<2\n");
+    }
+
+    #[test]
+    fn insert_and_remove_keep_blocks_count() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..200 {
+            let mut world = random_world(&mut rng);
+
+            for _ in 0..50 {
+                let (height, width) = (world.height.get(), world.width.get());
+                match rng.below(4) {
+                    0 if height < 8 => world.insert_row(rng.below(height + 1)),
+                    1 if width < 8 => world.insert_column(rng.below(width + 1)),
+                    2 if height > 1 => world.remove_row(rng.below(height)),
+                    3 if width > 1 => world.remove_column(rng.below(width)),
+                    _ => continue,
+                }
+                assert_size_matches(&world);
+            }
+        }
+    }
+
+    #[test]
+    fn insert_then_remove_restores_world() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..500 {
+            let origin = random_world(&mut rng);
+            let (height, width) = (origin.height.get(), origin.width.get());
+
+            let index = rng.below(height + 1);
+            let mut world = origin.clone();
+            world.insert_row(index);
+            assert_size_matches(&world);
+            let rows: Vec<_> = world.rows().map(<[Block]>::to_vec).collect();
+            assert!(rows[index].iter().all(|block| *block == Block::Empty));
+            world.remove_row(index);
+            assert_eq!(world, origin);
+
+            let index = rng.below(width + 1);
+            let mut world = origin.clone();
+            world.insert_column(index);
+            assert_size_matches(&world);
+            for (row, col, block) in world.iter() {
+                let expected = match col.cmp(&index) {
+                    core::cmp::Ordering::Less => origin[(row, col)],
+                    core::cmp::Ordering::Equal => Block::Empty,
+                    core::cmp::Ordering::Greater => origin[(row, col - 1)],
+                };
+                assert_eq!(*block, expected, "insert column {index} into {origin:?}");
+            }
+            world.remove_column(index);
+            assert_eq!(world, origin);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn removing_last_row_or_column_keeps_world() {
+        let origin: World = "v1\n1,1\n5\n".parse().unwrap();
+
+        let mut world = origin.clone();
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| world.remove_row(0)));
+        assert!(result.is_err());
+        assert_eq!(world, origin);
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| world.remove_column(0)));
+        assert!(result.is_err());
+        assert_eq!(world, origin);
+    }
+
+    #[test]
+    fn into_iterator_yields_row_major_blocks() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..100 {
+            let mut world = random_world(&mut rng);
+            let width = world.width.get();
+            let expected: Vec<_> = world
+                .blocks
+                .iter()
+                .enumerate()
+                .map(|(i, b)| (i / width, i % width, *b))
+                .collect();
+
+            let by_ref: Vec<_> = (&world)
+                .into_iter()
+                .map(|(row, col, block)| (row, col, *block))
+                .collect();
+            assert_eq!(by_ref, expected);
+
+            for (row, col, block) in &mut world {
+                assert_eq!(*block, expected[row * width + col].2);
+                block.rotate();
+            }
+            let rotated = world.clone();
+
+            let owned: Vec<_> = world.into_iter().collect();
+            assert_eq!(owned.len(), expected.len());
+            for ((row, col, block), (e_row, e_col, e_block)) in owned.into_iter().zip(&expected) {
+                assert_eq!((row, col), (*e_row, *e_col));
+                assert_eq!(block, e_block.rotated());
+                assert_eq!(rotated.get(row, col), Some(&block));
+            }
+        }
+    }
+
+    #[test]
+    fn size_line_with_extra_fields_is_rejected() {
+        use core::num::IntErrorKind;
+
+        let width_error = |s: &str| match s.parse::<World>() {
+            Err(WorldParseError::BadWidth(e)) => *e.kind(),
+            other => panic!("{s:?}: {other:?}"),
+        };
+
+        assert_eq!(width_error("v1\n3,4,5\n^"), IntErrorKind::InvalidDigit);
+        assert_eq!(width_error("2,2,2\n79\n13"), IntErrorKind::InvalidDigit);
+        assert_eq!(width_error("1,1,\n^"), IntErrorKind::InvalidDigit);
+        assert_eq!(width_error("1,\n^"), IntErrorKind::Empty);
+        assert_eq!("v1\n2,2\n79\n13".parse::<World>().unwrap().size().1.get(), 2);
+    }
+
+    #[test]
+    fn parse_error_variants() {
+        use core::num::IntErrorKind;
+
+        let error = |s: &str| s.parse::<World>().unwrap_err();
+
+        assert_eq!(error(""), WorldParseError::MissingSizeLine);
+        assert_eq!(error("v1"), WorldParseError::MissingSizeLine);
+        assert_eq!(
+            error("v9\n1,1\n^"),
+            WorldParseError::UnsupportedVersion("9".to_string())
+        );
+        assert!(
+            matches!(error("v1\nx,1\n^"), WorldParseError::BadHeight(e) if *e.kind() == IntErrorKind::InvalidDigit)
+        );
+        assert!(matches!(error("v1\n0,1\n^"), WorldParseError::BadHeight(e) if *e.kind() == IntErrorKind::Zero));
+        assert!(matches!(error("v1\n1,x\n^"), WorldParseError::BadWidth(_)));
+        assert_eq!(error("v1\n99999999999,99999999999\n^"), WorldParseError::TooManyBlocks);
+        assert_eq!(
+            error("79\n3x"),
+            WorldParseError::InvalidBlock {
+                row: 1,
+                col: 1,
+                ch: 'x'
+            }
+        );
+        assert_eq!(
+            error("79\n3"),
+            WorldParseError::RowLengthMismatch {
+                row: 1,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            error("v1\n1,1\n^^"),
+            WorldParseError::RowLengthMismatch {
+                row: 0,
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(
+            error("v1\n2,1\n^"),
+            WorldParseError::RowCountMismatch { expected: 2, found: 1 }
+        );
+
+        // positions are shown 1-based
+        assert_eq!(error("79\n3x").to_string(), "row 2, column 2: invalid block char: x");
+        assert_eq!(String::from(error("v1\n1,1\n^^")), "row 1: expect 1 blocks, found 2");
+    }
+
+    #[test]
+    fn parse_all_errors_reports_every_problem() {
+        let errors = World::parse_all_errors("v1\n4,3\n7x9\n/5/\n1-3z\n").unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                WorldParseError::InvalidBlock {
+                    row: 0,
+                    col: 1,
+                    ch: 'x'
+                },
+                WorldParseError::InvalidBlock {
+                    row: 2,
+                    col: 3,
+                    ch: 'z'
+                },
+                WorldParseError::RowLengthMismatch {
+                    row: 2,
+                    expected: 3,
+                    found: 4
+                },
+                WorldParseError::RowCountMismatch { expected: 4, found: 3 },
+            ]
+        );
+
+        let errors = World::parse_all_errors("79\nx1\n3?\n").unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                WorldParseError::InvalidBlock {
+                    row: 1,
+                    col: 0,
+                    ch: 'x'
+                },
+                WorldParseError::InvalidBlock {
+                    row: 2,
+                    col: 1,
+                    ch: '?'
+                },
+            ]
+        );
+
+        // rows are still scanned after a bad size line
+        let errors = World::parse_all_errors("v1\nx,1\nx\n").unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [
+                WorldParseError::BadHeight(_),
+                WorldParseError::InvalidBlock {
+                    row: 0,
+                    col: 0,
+                    ch: 'x'
+                }
+            ]
+        ));
+        assert_eq!(World::parse_all_errors("79\n31"), Ok("79\n31".parse().unwrap()));
+    }
+
+    #[test]
+    fn parse_lenient_accepts_aliases() {
+        let world = World::parse_lenient("v1\n2,3\nF─┐\nL-J\n").unwrap();
+        assert_eq!(world, "v1\n2,3\n7-9\n1-3\n".parse().unwrap());
+        assert_eq!(world.to_string(), "v1\n2,3\n7-9\n1-3\n");
+
+        assert_eq!(
+            "v1\n2,3\nF─┐\nL-J\n".parse::<World>(),
+            Err(WorldParseError::InvalidBlock {
+                row: 0,
+                col: 0,
+                ch: 'F'
+            })
+        );
+    }
+
+    #[test]
+    fn index_with_tuple_and_pos() {
+        let mut world: World = "79\n31".parse().unwrap();
+        assert_eq!(world[(0, 1)], Block::Turn(Direction::Down));
+        assert_eq!(world[Pos::new(1, 0)], Block::Turn(Direction::Left));
+
+        world[(1, 0)].rotate();
+        world[Pos::new(1, 1)] = Block::Cross;
+        assert_eq!(world, "79\n15".parse().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "index (0, 2) out of range of 2x2 world")]
+    fn index_out_of_range_panics() {
+        let world: World = "79\n31".parse().unwrap();
+        let _ = world[(0, 2)];
+    }
+
+    #[test]
+    #[should_panic(expected = "index (2, 0) out of range of 2x2 world")]
+    fn index_mut_out_of_range_panics() {
+        let mut world: World = "79\n31".parse().unwrap();
+        world[(2, 0)] = Block::Empty;
+    }
+
+    #[test]
+    fn fallible_constructors_report_capacity_errors() {
+        let size = |n: usize| NonZeroUsize::new(n).unwrap();
+
+        assert_eq!(
+            World::try_empty(size(usize::MAX), size(2)).unwrap_err(),
+            CapacityError::Overflow
+        );
+        assert_eq!(
+            World::try_new_with(size(usize::MAX), size(usize::MAX), Some(16), |_, _| Block::Empty).unwrap_err(),
+            CapacityError::Overflow
+        );
+        assert_eq!(
+            World::try_new_with(size(4), size(5), Some(16), |_, _| Block::Empty).unwrap_err(),
+            CapacityError::ExceedsLimit { blocks: 20, limit: 16 }
+        );
+        // more bytes than isize::MAX, so allocator is not even asked
+        let blocks = usize::MAX / 2;
+        assert_eq!(
+            World::try_empty(size(blocks), size(1)).unwrap_err(),
+            CapacityError::AllocationFailed { blocks }
+        );
+
+        let world = World::try_new_with(size(4), size(4), Some(16), |row, col| {
+            if row == col {
+                Block::Cross
+            } else {
+                Block::Empty
+            }
+        })
+        .unwrap();
+        assert_eq!(world.block_count(), 16);
+        assert_eq!(world.heap_size_estimate(), 16 * core::mem::size_of::<Block>());
+        assert_eq!(world.block_counts().cross, 4);
+    }
+
+    #[test]
+    fn fallible_inserts_grow_world() {
+        let mut world: World = "79\n31".parse().unwrap();
+        world.try_insert_row(2).unwrap();
+        world.try_insert_column(0).unwrap();
+        assert_eq!(world, "v1\n3,3\n 79\n 31\n   \n".parse().unwrap());
+        assert_eq!(world.block_count(), 9);
+        assert!(world.heap_size_estimate() >= 9 * core::mem::size_of::<Block>());
+    }
+
+    #[test]
+    fn solved_cells_are_locally_correct_blocks() {
+        for s in ["79\n13", "><", "v1\n3,3\n7-9\n/ /\n1-3\n", "v1\n2,3\n>8<\n ^ \n"] {
+            let solved: World = s.parse().unwrap();
+            assert!(solved.solved(), "{s}");
+            // every non-empty block of a solved world is locally correct
+            let non_empty: Vec<_> = solved
+                .iter()
+                .filter(|(_, _, block)| **block != Block::Empty)
+                .map(|(row, col, _)| Pos::new(row, col))
+                .collect();
+            assert_eq!(solved.solved_cells(), non_empty, "{s}");
+        }
+
+        let size = NonZeroUsize::new(3).unwrap();
+        assert_eq!(World::empty(size, size).solved_cells(), []);
+
+        // bottom turns pass to the boundary, the top ones are fine
+        let mut world: World = "79\n31".parse().unwrap();
+        assert_eq!(world.solved_cells(), [Pos::new(0, 0), Pos::new(0, 1)]);
+        world.set_open_borders(true);
+        assert_eq!(world.solved_cells().len(), 4);
+    }
+
+    #[test]
+    fn open_borders_let_pipes_end_at_the_edge() {
+        let mut world: World = "79\n31".parse().unwrap();
+        assert!(!world.open_borders());
+        assert!(!world.solved());
+
+        world.set_open_borders(true);
+        assert!(world.solved());
+        assert_eq!(world.count_correct_edges(), 12);
+        // the flag is part of equality, and kept by size changes and transforms
+        assert_ne!(world, "79\n31".parse().unwrap());
+        assert!(World::empty_like(&world).open_borders());
+        assert!(world.transposed().open_borders());
+
+        // blocks must still fit internally
+        let mut world: World = "7-\n31".parse().unwrap();
+        world.set_open_borders(true);
+        assert!(!world.solved());
+        assert!(world.solve().unwrap().solved());
+    }
+
+    // the old allocating implementations, kept as reference for the in-place ones
+    fn naive_insert_row(world: &World, index: usize) -> World {
+        let width = world.width.get();
+        let mut blocks = world.blocks.clone();
+        let tail = blocks.split_off(index * width);
+        blocks.extend(core::iter::repeat_n(Block::Empty, width));
+        blocks.extend(tail);
+        World::new_from_blocks(world.height.checked_add(1).unwrap(), world.width, blocks)
+    }
+
+    fn naive_insert_column(world: &World, index: usize) -> World {
+        let mut blocks = Vec::new();
+        for row in world.rows() {
+            blocks.extend_from_slice(&row[..index]);
+            blocks.push(Block::Empty);
+            blocks.extend_from_slice(&row[index..]);
+        }
+        World::new_from_blocks(world.height, world.width.checked_add(1).unwrap(), blocks)
+    }
+
+    fn naive_remove_column(world: &World, index: usize) -> World {
+        let width = world.width.get();
+        let blocks = world
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % width != index)
+            .map(|(_, block)| *block)
+            .collect();
+        World::new_from_blocks(world.height, NonZeroUsize::new(width - 1).unwrap(), blocks)
+    }
+
+    #[test]
+    fn in_place_edits_match_naive_implementations() {
+        let mut rng = Rng(0xdead_beef_cafe_f00d);
+
+        for _ in 0..1000 {
+            let origin = random_world(&mut rng);
+            let (height, width) = (origin.height.get(), origin.width.get());
+
+            let index = rng.below(height + 1);
+            let mut world = origin.clone();
+            world.insert_row(index);
+            assert_eq!(
+                world,
+                naive_insert_row(&origin, index),
+                "insert row {index} into {origin:?}"
+            );
+
+            let index = rng.below(width + 1);
+            let mut world = origin.clone();
+            world.insert_column(index);
+            assert_eq!(
+                world,
+                naive_insert_column(&origin, index),
+                "insert column {index} into {origin:?}"
+            );
+
+            if width > 1 {
+                let index = rng.below(width);
+                let mut world = origin.clone();
+                world.remove_column(index);
+                assert_eq!(
+                    world,
+                    naive_remove_column(&origin, index),
+                    "remove column {index} from {origin:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn solved_state_tells_empty_from_misfit() {
+        let size = NonZeroUsize::new(2).unwrap();
+        let empty = World::empty(size, size);
+        assert!(empty.is_empty());
+        assert_eq!(empty.solved_state(), SolvedState::Empty);
+        assert!(!empty.solved());
+
+        let solved: World = "79\n13".parse().unwrap();
+        assert!(!solved.is_empty());
+        assert_eq!(solved.solved_state(), SolvedState::Solved);
+        assert!(solved.solved());
+
+        let misfit: World = "79\n31".parse().unwrap();
+        assert_eq!(misfit.solved_state(), SolvedState::Misfit);
+        assert!(!misfit.solved());
+
+        // a single block passing to the boundary is a misfit, not empty
+        let lonely: World = "v1\n2,2\n  \n ^\n".parse().unwrap();
+        assert!(!lonely.is_empty());
+        assert_eq!(lonely.solved_state(), SolvedState::Misfit);
+    }
+
+    #[test]
+    fn empty_like_matches_size_and_border_mode() {
+        let mut rng = Rng(0x5851_f42d_4c95_7f2d);
+        for _ in 0..50 {
+            let mut world = random_world(&mut rng);
+            world.set_open_borders(rng.below(2) == 0);
+
+            let empty = World::empty_like(&world);
+            assert_eq!(empty.size(), world.size());
+            assert_eq!(empty.open_borders(), world.open_borders());
+            assert!(empty.is_empty());
+            assert_size_matches(&empty);
+        }
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn shuffle_seeded_is_pinned() {
+        let origin: World = "v1\n3,3\n>8<\n/5/\n^1 \n".parse().unwrap();
+        let shuffled = |seed| {
+            let mut world = origin.clone();
+            world.shuffle_seeded(seed);
+            world
+        };
+
+        // pinned, so the same seed gives the same puzzle on every platform and version
+        assert_eq!(shuffled(7).to_string(), "v1\n3,3\n>8^\n/5-\n>1 \n");
+        assert_eq!(shuffled(42), shuffled(42));
+        assert_eq!(shuffled(7).block_counts(), origin.block_counts());
+    }
+
+    #[test]
+    fn checksum_tracks_changes() {
+        let world: World = "79\n13".parse().unwrap();
+        // pinned, it must be stable across runs, platforms and versions
+        assert_eq!(world.checksum(), 0xd9c0_3850_a884_d80d);
+        assert_eq!(world.clone().checksum(), world.checksum());
+
+        let mut rng = Rng(0x1405_7b7e_f767_814f);
+        for _ in 0..100 {
+            let origin = random_world(&mut rng);
+            let (row, col) = (rng.below(origin.height.get()), rng.below(origin.width.get()));
+            let mut world = origin.clone();
+            world.rotate(row, col);
+            // a rotation changes the checksum unless the block looks the same after it
+            assert_eq!(
+                world.checksum() == origin.checksum(),
+                world == origin,
+                "{origin:?} ({row}, {col})"
+            );
+        }
+
+        let mut open = world.clone();
+        open.set_open_borders(true);
+        assert_ne!(open.checksum(), world.checksum());
+        let line: World = "><".parse().unwrap();
+        assert_ne!(line.transposed().checksum(), line.checksum());
+    }
+
+    #[test]
+    fn sparse_board_with_a_hole() {
+        // solved loop around a hole in the center
+        let world: World = "v1\n3,3\n7-9\n/ /\n1-3\n".parse().unwrap();
+        assert!(world.solved_allow_sparse());
+        assert!(world.solved());
+
+        // a pipe pointing into the hole doesn't fit
+        let world: World = "v1\n3,3\n7-9\n/ 6\n1-3\n".parse().unwrap();
+        assert!(!world.solved_allow_sparse());
+        assert!(!world.solved());
+
+        // a board of holes only is a solved sparse board, but not a solved puzzle
+        let size = NonZeroUsize::new(3).unwrap();
+        assert!(World::empty(size, size).solved_allow_sparse());
+        assert!(!World::empty(size, size).solved());
+    }
+
+    #[test]
+    fn progress_of_solved_and_scrambled_boards() {
+        let solved: World = "79\n13".parse().unwrap();
+        assert_eq!(solved.progress(), 1.0);
+
+        let scrambled: World = "13\n79".parse().unwrap();
+        assert!(scrambled.progress() < 1.0);
+        // every block passes to the boundary once, the other 8 of 12 edges fit
+        assert_eq!(scrambled.count_correct_edges(), 8);
+        assert_eq!(scrambled.progress(), 8.0 / 12.0);
+
+        let size = NonZeroUsize::new(2).unwrap();
+        assert_eq!(World::empty(size, size).progress(), 0.0);
+
+        let mut rng = Rng(0x6a09_e667_f3bc_c908);
+        for _ in 0..200 {
+            let world = random_world(&mut rng);
+            let progress = world.progress();
+            assert!((0.0..=1.0).contains(&progress));
+            assert_eq!(progress == 1.0, world.solved(), "{world:?}");
+        }
+    }
+
+    #[test]
+    fn extend_changes_only_given_cells() {
+        let origin: World = "v1\n3,3\n>8<\n/5/\n^1 \n".parse().unwrap();
+        let mut world = origin.clone();
+        world.extend([
+            (0, 1, Block::Cross),
+            (2, 2, Block::Endpoint(Direction::Up)),
+            // out of range entries are ignored
+            (0, 3, Block::Cross),
+            (3, 0, Block::Cross),
+        ]);
+
+        for (row, col, block) in &world {
+            let expected = match (row, col) {
+                (0, 1) => Block::Cross,
+                (2, 2) => Block::Endpoint(Direction::Up),
+                _ => origin[(row, col)],
+            };
+            assert_eq!(*block, expected, "({row}, {col})");
+        }
+
+        // extending a blank world with all blocks of another one copies it
+        let mut copy = World::empty_like(&origin);
+        copy.extend(origin.clone());
+        assert_eq!(copy, origin);
+    }
+
+    #[test]
+    fn short_rows_are_padded_after_trailing_spaces_stripped() {
+        let stripped: World = "v1\n3,4\n>-9\n 7-3\n 1-<\n".parse().unwrap();
+        let full: World = "v1\n3,4\n>-9 \n 7-3\n 1-<\n".parse().unwrap();
+        assert_eq!(stripped, full);
+        assert_eq!(stripped.get(0, 3), Some(&Block::Empty));
+
+        // an all empty row may be stripped to nothing
+        let world: World = "v1\n3,2\n><\n\n><\n".parse().unwrap();
+        assert_eq!(world.row(1), Some(&[Block::Empty, Block::Empty][..]));
+
+        // rows longer than the width are still an error
+        assert_eq!(
+            "v1\n1,2\n><>\n".parse::<World>(),
+            Err(WorldParseError::RowLengthMismatch {
+                row: 0,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn flood_fill_regions() {
+        // whole board, turns of all directions are one region
+        let mut world: World = "79\n13".parse().unwrap();
+        assert_eq!(world.flood_fill(1, 1, Block::Cross), 4);
+        assert_eq!(world, "55\n55".parse().unwrap());
+
+        // single cell, surrounded by other kinds
+        let mut world: World = "v1\n3,3\n>-<\n-^-\n>-<\n".parse().unwrap();
+        assert_eq!(world.flood_fill(1, 1, Block::Empty), 1);
+        assert_eq!(world, "v1\n3,3\n>-<\n- -\n>-<\n".parse().unwrap());
+
+        // only the connected part, not every block of the kind
+        assert_eq!(world.flood_fill(0, 1, Block::Through(Direction::Up)), 1);
+        assert_eq!(world.flood_fill(1, 0, Block::Cross), 1);
+        assert_eq!(world, "v1\n3,3\n>/<\n5 -\n>-<\n".parse().unwrap());
+
+        // no-op fills
+        let origin = world.clone();
+        assert_eq!(world.flood_fill(1, 0, Block::Cross), 0);
+        assert_eq!(world.flood_fill(3, 0, Block::Empty), 0);
+        assert_eq!(world.flood_fill(0, 3, Block::Empty), 0);
+        assert_eq!(world, origin);
+
+        // mixed directions in the region, only blocks different from the new one are counted
+        let mut world: World = "v1\n1,3\n/-/\n".parse().unwrap();
+        assert_eq!(world.flood_fill(0, 0, Block::Through(Direction::Left)), 2);
+    }
+
+    #[test]
+    fn flood_fill_large_world_does_not_overflow_stack() {
+        let size = NonZeroUsize::new(1000).unwrap();
+        let mut world = World::empty(size, size);
+        assert_eq!(world.flood_fill(500, 500, Block::Cross), 1_000_000);
+        assert_eq!(world.block_counts().cross, 1_000_000);
+    }
+
+    #[test]
+    fn win_modes() {
+        let won = |s: &str| {
+            let world: World = s.parse().unwrap();
+            (
+                world.is_won(WinMode::AllEdgesFit),
+                world.is_won(WinMode::EndpointsConnected),
+            )
+        };
+
+        assert_eq!(won("><"), (true, true));
+        // all edges fit, but the endpoints form two separate networks
+        assert_eq!(won("><\n><"), (true, false));
+        // one network joins all endpoints, a stray turn outside of it is ignored
+        assert_eq!(won("v1\n2,3\n>-<\n7  \n"), (false, true));
+        // the network leaks to the boundary
+        assert_eq!(won("v1\n1,3\n>2<\n"), (false, false));
+        // the network leaks to a neighbor not passing back
+        assert_eq!(won("v1\n2,3\n>8<\n   \n"), (false, false));
+        // no endpoint at all
+        assert_eq!(won("79\n13"), (true, false));
+        assert_eq!(WinMode::default(), WinMode::AllEdgesFit);
     }
 }